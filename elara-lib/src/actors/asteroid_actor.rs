@@ -27,6 +27,7 @@ impl Actor for AsteroidActor {
         }
 
         // Check all the existing asteroids and update their anim state.
+        let mut impact_positions = vec![];
         for asteroid in state.asteroids.iter_mut() {
             if asteroid.anim_state == AsteroidAnimState::RecentlyHitGround {
                 // If the asteroid just hit the ground in the previous step, it should now be stationary.
@@ -35,6 +36,35 @@ impl Actor for AsteroidActor {
             if asteroid.anim_state == AsteroidAnimState::Falling {
                 // If the asteroid was falling in the previous step, it should now hit the ground.
                 asteroid.anim_state = AsteroidAnimState::RecentlyHitGround;
+                impact_positions.push(asteroid.pos.clone());
+            }
+        }
+
+        // Anything that was under an asteroid when it hit the ground is destroyed.
+        // Crates are marked as crushed rather than removed so that indexes into
+        // State.crates (e.g. Player.held_crate_index) stay valid. Note that the
+        // rover being hit is handled by check_win.
+        for pos in impact_positions.iter() {
+            for enemy in state.enemies.iter_mut() {
+                if enemy.pos == *pos {
+                    enemy.destroyed = true;
+                }
+            }
+            for big_enemy in state.big_enemies.iter_mut() {
+                if big_enemy.occupies(pos) {
+                    big_enemy.destroyed = true;
+                }
+            }
+            for (i, crt) in state.crates.iter_mut().enumerate() {
+                if !crt.held && crt.pos == *pos {
+                    crt.crushed = true;
+                    // A crushed crate no longer works as a bridge.
+                    for hazard in state.hazards.iter_mut() {
+                        if hazard.bridged_by == Some(i) {
+                            hazard.bridged_by = None;
+                        }
+                    }
+                }
             }
         }
 
@@ -69,7 +99,13 @@ impl Actor for AsteroidActor {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{simulation::AsteroidWarning, state_maker::StateMaker};
+    use crate::{
+        simulation::{
+            AsteroidWarning, BigEnemy, Crate, CrateColor, Enemy, Orientation,
+            OrientationWithDiagonals,
+        },
+        state_maker::StateMaker,
+    };
 
     #[test]
     fn test_asteroid_actor() {
@@ -162,4 +198,55 @@ mod test {
         let new_state = actor.apply(state.clone());
         assert_eq!(new_state, expected_state);
     }
+
+    #[test]
+    fn test_asteroid_impacts() {
+        let mut actor = AsteroidActor::new();
+        let state = StateMaker::new()
+            .with_asteroid_warnings(vec![
+                AsteroidWarning::new(0, 0, 2, true),
+                AsteroidWarning::new(1, 0, 2, true),
+            ])
+            .with_enemies(vec![
+                Enemy::new(0, 0, Orientation::Down),
+                Enemy::new(0, 1, Orientation::Down),
+            ])
+            .with_crates(vec![
+                Crate::new(1, 0, CrateColor::Red),
+                Crate::new(1, 1, CrateColor::Blue),
+            ])
+            .build();
+
+        // Nothing should happen while the asteroids are still falling.
+        let state = actor.apply(state);
+        assert!(!state.enemies[0].destroyed);
+        assert_eq!(state.crates.len(), 2);
+
+        // Once they hit the ground, the enemy and crate underneath should be
+        // destroyed but the others should be unaffected.
+        let state = actor.apply(state);
+        assert!(state.enemies[0].destroyed);
+        assert!(!state.enemies[1].destroyed);
+        assert_eq!(state.crates.len(), 2);
+        assert!(state.crates[0].crushed);
+        assert!(!state.crates[1].crushed);
+    }
+
+    #[test]
+    fn test_asteroid_impacts_big_enemy() {
+        let mut actor = AsteroidActor::new();
+        let state = StateMaker::new()
+            .with_asteroid_warnings(vec![AsteroidWarning::new(2, 1, 2, true)])
+            .with_big_enemies(vec![
+                BigEnemy::new(0, 0, OrientationWithDiagonals::Down),
+                BigEnemy::new(4, 0, OrientationWithDiagonals::Down),
+            ])
+            .build();
+
+        // Hitting any part of a big enemy should destroy it.
+        let state = actor.apply(state);
+        let state = actor.apply(state);
+        assert!(state.big_enemies[0].destroyed);
+        assert!(!state.big_enemies[1].destroyed);
+    }
 }
@@ -4,11 +4,10 @@ use crate::simulation::{
     Actor, BigEnemyAnimState, BumpAnimData, OrientationWithDiagonals, Pos, State,
 };
 
-use super::{can_move_to, Bounds, MoveDirection, TurnDirection};
+use super::{can_move_to_safely, nearest_rover_pos, Bounds, MoveDirection, TurnDirection};
 
-pub const BIG_ENEMY_SIZE: i32 = 3;
-
-/// An actor for a much larger "malfunctioning" rover enemies which takes up a 3x3 grid.
+/// An actor for a much larger "malfunctioning" rover enemies which takes up
+/// multiple spaces (3x3 by default, but see BigEnemy.width and BigEnemy.height).
 /// Similar to the smaller malfunctioning rovers, it always tries to chase
 /// the player down and follows the same general movement patterns, but it does take
 /// twice as long to turn.
@@ -26,17 +25,36 @@ enum BigEvilRoverAction {
     Bump(Pos),
 }
 
-/// Returns true if we can move to the desired position. Accounts for the entire
-/// body of the enemy, not just one space.
-fn can_move_entire_body(state: &State, bounds: &Bounds, desired_pos: &Pos) -> bool {
-    for x in desired_pos.x..desired_pos.x + BIG_ENEMY_SIZE {
-        for y in desired_pos.y..desired_pos.y + BIG_ENEMY_SIZE {
-            if !can_move_to(state, bounds, &Pos::new(x, y)) {
-                return false;
+/// Returns the first position which would block the enemy from having its
+/// top-left corner at desired_pos with the given footprint, or None if every
+/// space it would take up is free.
+fn blocked_pos(
+    state: &State,
+    bounds: &Bounds,
+    desired_pos: &Pos,
+    footprint: (i32, i32),
+) -> Option<Pos> {
+    let (width, height) = footprint;
+    for x in desired_pos.x..desired_pos.x + width {
+        for y in desired_pos.y..desired_pos.y + height {
+            let pos = Pos::new(x, y);
+            if !can_move_to_safely(state, bounds, &pos) {
+                return Some(pos);
             }
         }
     }
-    true
+    None
+}
+
+/// Returns true if we can move to the desired position. Accounts for the entire
+/// body of the enemy, not just one space.
+fn can_move_entire_body(
+    state: &State,
+    bounds: &Bounds,
+    desired_pos: &Pos,
+    footprint: (i32, i32),
+) -> bool {
+    blocked_pos(state, bounds, desired_pos, footprint).is_none()
 }
 
 impl BigEnemyActor {
@@ -148,15 +166,15 @@ impl BigEnemyActor {
     }
 
     fn get_next_action(&self, state: &State) -> BigEvilRoverAction {
-        let player_pos = &state.player.pos;
         let enemy = &state.big_enemies[self.index];
         // Note: BigEnemy.pos represents the top-left position (which is easier
         // for drawing sprites on the screen), but when we are figuring out which
         // way to move, it's better to consider the center position.
-        let center_pos = Pos::new(
-            enemy.pos.x + BIG_ENEMY_SIZE / 2,
-            enemy.pos.y + BIG_ENEMY_SIZE / 2,
-        );
+        let (width, height) = enemy.footprint();
+        let center_pos = Pos::new(enemy.pos.x + width / 2, enemy.pos.y + height / 2);
+        // Chase whichever rover is nearest.
+        let player_pos =
+            nearest_rover_pos(state.rovers(), &center_pos).unwrap_or(&state.player.pos);
 
         // Prioritize moving in the axis in which the player is the furthest away.
         let x_dist = player_pos.x.abs_diff(center_pos.x);
@@ -167,6 +185,7 @@ impl BigEnemyActor {
                     state,
                     &self.bounds,
                     &Pos::new(enemy.pos.x, enemy.pos.y - 1),
+                    enemy.footprint_for(OrientationWithDiagonals::Up),
                 )
             {
                 return self.move_or_turn(enemy.facing, OrientationWithDiagonals::Up);
@@ -175,6 +194,7 @@ impl BigEnemyActor {
                     state,
                     &self.bounds,
                     &Pos::new(enemy.pos.x, enemy.pos.y + 1),
+                    enemy.footprint_for(OrientationWithDiagonals::Down),
                 )
             {
                 return self.move_or_turn(enemy.facing, OrientationWithDiagonals::Down);
@@ -185,6 +205,7 @@ impl BigEnemyActor {
                     state,
                     &self.bounds,
                     &Pos::new(enemy.pos.x - 1, enemy.pos.y),
+                    enemy.footprint_for(OrientationWithDiagonals::Left),
                 )
             {
                 return self.move_or_turn(enemy.facing, OrientationWithDiagonals::Left);
@@ -193,6 +214,7 @@ impl BigEnemyActor {
                     state,
                     &self.bounds,
                     &Pos::new(enemy.pos.x + 1, enemy.pos.y),
+                    enemy.footprint_for(OrientationWithDiagonals::Right),
                 )
             {
                 return self.move_or_turn(enemy.facing, OrientationWithDiagonals::Right);
@@ -215,6 +237,7 @@ impl BigEnemyActor {
                     state,
                     &self.bounds,
                     &Pos::new(enemy.pos.x - 1, enemy.pos.y),
+                    enemy.footprint_for(OrientationWithDiagonals::Left),
                 )
             {
                 return self.move_or_turn(enemy.facing, OrientationWithDiagonals::Left);
@@ -223,6 +246,7 @@ impl BigEnemyActor {
                     state,
                     &self.bounds,
                     &Pos::new(enemy.pos.x + 1, enemy.pos.y),
+                    enemy.footprint_for(OrientationWithDiagonals::Right),
                 )
             {
                 return self.move_or_turn(enemy.facing, OrientationWithDiagonals::Right);
@@ -233,6 +257,7 @@ impl BigEnemyActor {
                     state,
                     &self.bounds,
                     &Pos::new(enemy.pos.x, enemy.pos.y - 1),
+                    enemy.footprint_for(OrientationWithDiagonals::Up),
                 )
             {
                 return self.move_or_turn(enemy.facing, OrientationWithDiagonals::Up);
@@ -241,6 +266,7 @@ impl BigEnemyActor {
                     state,
                     &self.bounds,
                     &Pos::new(enemy.pos.x, enemy.pos.y + 1),
+                    enemy.footprint_for(OrientationWithDiagonals::Down),
                 )
             {
                 return self.move_or_turn(enemy.facing, OrientationWithDiagonals::Down);
@@ -265,6 +291,11 @@ impl Actor for BigEnemyActor {
     fn apply(&mut self, state: State) -> State {
         let mut state = state.clone();
 
+        // Destroyed enemies can't do anything.
+        if state.big_enemies[self.index].destroyed {
+            return state;
+        }
+
         // Default to Idle state.
         state.big_enemies[self.index].anim_state = BigEnemyAnimState::Idle;
 
@@ -283,19 +314,34 @@ impl Actor for BigEnemyActor {
                 state.big_enemies[self.index].pos = desired_pos.unwrap();
                 state.big_enemies[self.index].anim_state = BigEnemyAnimState::Moving;
             }
-            BigEvilRoverAction::Turn(direction) => match direction {
-                TurnDirection::Left => {
-                    state.big_enemies[self.index].anim_state = BigEnemyAnimState::Turning;
-                    state.big_enemies[self.index].facing = state.big_enemies[self.index]
-                        .facing
-                        .rotate_counter_clockwise();
-                }
-                TurnDirection::Right => {
-                    state.big_enemies[self.index].anim_state = BigEnemyAnimState::Turning;
-                    state.big_enemies[self.index].facing =
-                        state.big_enemies[self.index].facing.rotate_clockwise();
+            BigEvilRoverAction::Turn(direction) => {
+                let enemy = &state.big_enemies[self.index];
+                let new_facing = match direction {
+                    TurnDirection::Left => enemy.facing.rotate_counter_clockwise(),
+                    TurnDirection::Right => enemy.facing.rotate_clockwise(),
+                };
+                // Non-square enemies take up a different area after turning, so
+                // there might not be enough room to turn. In that case, bump
+                // into whatever is in the way instead.
+                match blocked_pos(
+                    &state,
+                    &self.bounds,
+                    &enemy.pos,
+                    enemy.footprint_for(new_facing),
+                ) {
+                    None => {
+                        state.big_enemies[self.index].anim_state = BigEnemyAnimState::Turning;
+                        state.big_enemies[self.index].facing = new_facing;
+                    }
+                    Some(obstacle_pos) => {
+                        state.big_enemies[self.index].anim_state =
+                            BigEnemyAnimState::Bumping(BumpAnimData {
+                                pos: state.big_enemies[self.index].pos.clone(),
+                                obstacle_pos,
+                            });
+                    }
                 }
-            },
+            }
             BigEvilRoverAction::Bump(obstacle_pos) => {
                 state.big_enemies[self.index].anim_state =
                     BigEnemyAnimState::Bumping(BumpAnimData {
@@ -314,7 +360,7 @@ mod test {
     use super::*;
     use crate::{
         constants::{HEIGHT, WIDTH},
-        simulation::{BigEnemy, Orientation, Player},
+        simulation::{BigEnemy, Obstacle, Orientation, Player},
         state_maker::StateMaker,
     };
 
@@ -545,4 +591,125 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn chases_nearest_rover() {
+        // The extra rover is closer to the center of the enemy than the main
+        // rover, so the enemy should turn to chase it.
+        let state = StateMaker::new()
+            .with_player(Player::new(4, 0, 10, Orientation::Up))
+            .with_extra_rovers(vec![Player::new(7, 4, 10, Orientation::Up)])
+            .with_big_enemies(vec![BigEnemy::new(3, 3, OrientationWithDiagonals::Up)])
+            .build();
+        let actor = BigEnemyActor::new(0, Bounds::default());
+        assert_eq!(
+            actor.get_next_action(&state),
+            BigEvilRoverAction::Turn(TurnDirection::Right)
+        );
+    }
+
+    #[test]
+    fn different_sizes() {
+        let bounds = Bounds {
+            min_x: 0,
+            max_x: WIDTH as i32,
+            min_y: 0,
+            max_y: HEIGHT as i32,
+        };
+        let actor = BigEnemyActor::new(0, bounds);
+
+        // A 2x2 enemy with an obstacle just above its right side.
+        let mut state = StateMaker::new()
+            .with_player(Player::new(0, 0, 10, Orientation::Up))
+            .with_big_enemies(vec![BigEnemy::new_with_size(
+                0,
+                4,
+                OrientationWithDiagonals::Up,
+                2,
+                2,
+            )])
+            .with_obstacles(vec![Obstacle::new(1, 3)])
+            .build();
+        assert_eq!(
+            actor.get_next_action(&state),
+            BigEvilRoverAction::Bump(Pos::new(0, 3))
+        );
+
+        // Moving the obstacle one space to the right means it is no longer
+        // in the way.
+        state.obstacles = vec![Obstacle::new(2, 3)];
+        assert_eq!(
+            actor.get_next_action(&state),
+            BigEvilRoverAction::Move(MoveDirection::Forward)
+        );
+
+        // But a 4x4 enemy in the same position would still be blocked.
+        state.big_enemies = vec![BigEnemy::new_with_size(
+            0,
+            4,
+            OrientationWithDiagonals::Up,
+            4,
+            4,
+        )];
+        assert_eq!(
+            actor.get_next_action(&state),
+            BigEvilRoverAction::Bump(Pos::new(0, 3))
+        );
+    }
+
+    #[test]
+    fn turning_with_rectangular_body() {
+        let bounds = Bounds {
+            min_x: 0,
+            max_x: WIDTH as i32,
+            min_y: 0,
+            max_y: HEIGHT as i32,
+        };
+        let mut actor = BigEnemyActor::new(0, bounds);
+
+        // A 1x3 enemy facing up in a narrow corridor. The player is to the right,
+        // but there is not enough room for it to turn.
+        let state = StateMaker::new()
+            .with_player(Player::new(5, 1, 10, Orientation::Up))
+            .with_big_enemies(vec![BigEnemy::new_with_size(
+                1,
+                0,
+                OrientationWithDiagonals::Up,
+                1,
+                3,
+            )])
+            .with_obstacles(vec![
+                Obstacle::new(2, 0),
+                Obstacle::new(2, 2),
+                Obstacle::new(2, 3),
+            ])
+            .build();
+        let next_state = actor.apply(state.clone());
+        assert_eq!(
+            next_state.big_enemies[0].facing,
+            OrientationWithDiagonals::Up
+        );
+        assert_eq!(
+            next_state.big_enemies[0].anim_state,
+            BigEnemyAnimState::Bumping(BumpAnimData {
+                pos: Pos::new(1, 0),
+                obstacle_pos: Pos::new(2, 0),
+            })
+        );
+
+        // With the obstacles out of the way, it can turn and then move toward
+        // the player.
+        let mut state = state;
+        state.obstacles = vec![];
+        let state = actor.apply(state);
+        assert_eq!(
+            state.big_enemies[0].facing,
+            OrientationWithDiagonals::UpRight
+        );
+        let state = actor.apply(state);
+        assert_eq!(state.big_enemies[0].facing, OrientationWithDiagonals::Right);
+        assert_eq!(state.big_enemies[0].footprint(), (3, 1));
+        let state = actor.apply(state);
+        assert_eq!(state.big_enemies[0].pos, Pos::new(2, 0));
+    }
 }
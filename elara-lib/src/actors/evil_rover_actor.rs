@@ -1,38 +1,117 @@
 use std::any::Any;
+use std::collections::{HashSet, VecDeque};
 
 use crate::simulation::{
-    Actor, BumpAnimData, EnemyAnimState, Orientation, Pos, State, TeleAnimData,
+    Actor, BumpAnimData, Enemy, EnemyAnimState, EnemyVision, Orientation, Pos, State, TeleAnimData,
 };
 
-use super::{can_move_to, get_telepad_at, Bounds, MoveDirection, TurnDirection};
+use super::{
+    can_move_to_safely, get_telepad_at, has_line_of_sight, nearest_rover_pos, Bounds,
+    MoveDirection, TurnDirection,
+};
 
 /// An actor for "malfunctioning" or "evil" rover enemies which always tries to chase
 /// the player down. It follows the same basic movement rules as the player but doesn't
 /// have any energy restrictions.
+///
+/// Enemies with limited vision (see EnemyVision) will follow their patrol route
+/// (or stay still) until they spot the player, and only then start chasing.
 pub struct EvilRoverActor {
     /// The index in State.enemies of the enemy which will be controlled by
     /// this actor.
     index: usize,
     bounds: Bounds,
+    chase_mode: ChaseMode,
+}
+
+/// Determines how the enemy figures out which way to go.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChaseMode {
+    /// Move along whichever axis the target is furthest away in. Simple and
+    /// predictable, but the enemy can get stuck behind obstacles. This is the
+    /// default.
+    Direct,
+    /// Follow the shortest path to the target, going around any obstacles
+    /// in the way. Telepads are avoided.
+    ShortestPath,
+    /// Like ShortestPath, but telepads are used whenever they lead to a
+    /// shorter path.
+    ShortestPathWithTelepads,
 }
 
 #[derive(Debug, PartialEq)]
 enum EvilRoverAction {
+    Wait,
     Move(MoveDirection),
     Turn(TurnDirection),
     Bump(Pos),
 }
 
 /// Returns true if we can move to the desired position *and* it is not currently occupied
-/// by another enemy.
+/// by another enemy. Enemies also steer clear of hazards.
 fn can_move_and_is_empty(state: &State, bounds: &Bounds, desired_pos: &Pos) -> bool {
-    return can_move_to(state, bounds, desired_pos)
-        && !state.enemies.iter().any(|enemy| enemy.pos == *desired_pos);
+    can_move_to_safely(state, bounds, desired_pos)
+        && !state.enemies.iter().any(|enemy| enemy.pos == *desired_pos)
+}
+
+/// Returns true if the given enemy can currently see any of the rovers.
+fn can_see_player(state: &State, enemy: &Enemy) -> bool {
+    state
+        .rovers()
+        .any(|rover| can_see_pos(state, enemy, &rover.pos))
+}
+
+/// Returns true if the given enemy can currently see the given position.
+fn can_see_pos(state: &State, enemy: &Enemy, player_pos: &Pos) -> bool {
+    // Compute how far the player is in front of the enemy and how far
+    // they are off to the side, relative to the direction the enemy is facing.
+    let (forward_dist, side_dist) = match enemy.facing {
+        Orientation::Up => (enemy.pos.y - player_pos.y, player_pos.x - enemy.pos.x),
+        Orientation::Down => (player_pos.y - enemy.pos.y, player_pos.x - enemy.pos.x),
+        Orientation::Left => (enemy.pos.x - player_pos.x, player_pos.y - enemy.pos.y),
+        Orientation::Right => (player_pos.x - enemy.pos.x, player_pos.y - enemy.pos.y),
+    };
+    let in_view = match enemy.vision {
+        EnemyVision::Omniscient => return true,
+        EnemyVision::Line(range) => {
+            side_dist == 0 && forward_dist >= 1 && forward_dist <= range as i32
+        }
+        EnemyVision::Cone(range) => {
+            forward_dist >= 1 && forward_dist <= range as i32 && side_dist.abs() <= forward_dist
+        }
+    };
+    in_view && has_line_of_sight(state, &enemy.pos, player_pos)
 }
 
 impl EvilRoverActor {
     pub fn new(index: usize, bounds: Bounds) -> EvilRoverActor {
-        EvilRoverActor { index, bounds }
+        EvilRoverActor {
+            index,
+            bounds,
+            chase_mode: ChaseMode::Direct,
+        }
+    }
+
+    pub fn new_with_chase_mode(
+        index: usize,
+        bounds: Bounds,
+        chase_mode: ChaseMode,
+    ) -> EvilRoverActor {
+        EvilRoverActor {
+            index,
+            bounds,
+            chase_mode,
+        }
+    }
+
+    /// Marks the enemy as having spotted the player if the player is
+    /// currently visible.
+    fn update_spotted_player(&self, state: &mut State) {
+        if !state.enemies[self.index].spotted_player
+            && can_see_player(state, &state.enemies[self.index])
+        {
+            state.enemies[self.index].spotted_player = true;
+        }
     }
 
     /// Returns the position that is one space in front of the enemy.
@@ -139,14 +218,52 @@ impl EvilRoverActor {
     }
 
     fn get_next_action(&self, state: &State) -> EvilRoverAction {
-        let player_pos = &state.player.pos;
         let enemy = &state.enemies[self.index];
+        if enemy.spotted_player || enemy.vision == EnemyVision::Omniscient {
+            // Chase the nearest rover we can see, or if they are all out of
+            // sight, the nearest rover.
+            let visible = state
+                .rovers()
+                .filter(|rover| can_see_pos(state, enemy, &rover.pos));
+            let target = nearest_rover_pos(visible, &enemy.pos)
+                .or_else(|| nearest_rover_pos(state.rovers(), &enemy.pos))
+                .unwrap_or(&state.player.pos);
+            self.get_next_action_toward(state, target)
+        } else if let Some(patrol_pos) = enemy.patrol_route.get(enemy.patrol_index) {
+            // We haven't spotted the player yet. Keep following the patrol route.
+            self.get_next_action_toward(state, patrol_pos)
+        } else {
+            // No patrol route. Stay still and keep watch.
+            EvilRoverAction::Wait
+        }
+    }
+
+    /// Returns the next action that moves the enemy closer to the given target
+    /// position (typically the player).
+    fn get_next_action_toward(&self, state: &State, target: &Pos) -> EvilRoverAction {
+        let enemy = &state.enemies[self.index];
+        if enemy.pos == *target {
+            return EvilRoverAction::Wait;
+        }
+
+        let use_telepads = match self.chase_mode {
+            ChaseMode::Direct => None,
+            ChaseMode::ShortestPath => Some(false),
+            ChaseMode::ShortestPathWithTelepads => Some(true),
+        };
+        if let Some(use_telepads) = use_telepads {
+            if let Some(direction) = self.shortest_path_direction(state, target, use_telepads) {
+                return self.move_or_turn(enemy.facing, direction);
+            }
+            // If there is no path to the target, fall back to the direct
+            // approach below, which will at least turn toward the target.
+        }
 
-        // Prioritize moving in the axis in which the player is the furthest away.
-        let x_dist = player_pos.x.abs_diff(enemy.pos.x);
-        let y_dist = player_pos.y.abs_diff(enemy.pos.y);
+        // Prioritize moving in the axis in which the target is the furthest away.
+        let x_dist = target.x.abs_diff(enemy.pos.x);
+        let y_dist = target.y.abs_diff(enemy.pos.y);
         if y_dist >= x_dist {
-            if player_pos.y < enemy.pos.y
+            if target.y < enemy.pos.y
                 && can_move_and_is_empty(
                     state,
                     &self.bounds,
@@ -154,7 +271,7 @@ impl EvilRoverActor {
                 )
             {
                 return self.move_or_turn(enemy.facing, Orientation::Up);
-            } else if player_pos.y > enemy.pos.y
+            } else if target.y > enemy.pos.y
                 && can_move_and_is_empty(
                     state,
                     &self.bounds,
@@ -164,7 +281,7 @@ impl EvilRoverActor {
                 return self.move_or_turn(enemy.facing, Orientation::Down);
             }
 
-            if player_pos.x < enemy.pos.x
+            if target.x < enemy.pos.x
                 && can_move_and_is_empty(
                     state,
                     &self.bounds,
@@ -172,7 +289,7 @@ impl EvilRoverActor {
                 )
             {
                 return self.move_or_turn(enemy.facing, Orientation::Left);
-            } else if player_pos.x > enemy.pos.x
+            } else if target.x > enemy.pos.x
                 && can_move_and_is_empty(
                     state,
                     &self.bounds,
@@ -182,19 +299,19 @@ impl EvilRoverActor {
                 return self.move_or_turn(enemy.facing, Orientation::Right);
             }
 
-            // If we get here, we can't move toward the player. This means we should at least
-            // turn toward the player (if we are not already facing them). If we are facing them,
+            // If we get here, we can't move toward the target. This means we should at least
+            // turn toward the target (if we are not already facing it). If we are facing it,
             // we should do a bump animation. Note that we only need to check the y-axis here
-            // since we know that is the axis in which the player is furthest away.
-            if player_pos.y < enemy.pos.y {
+            // since we know that is the axis in which the target is furthest away.
+            if target.y < enemy.pos.y {
                 self.bump_or_turn(state, enemy.facing, Orientation::Up)
             } else {
                 self.bump_or_turn(state, enemy.facing, Orientation::Down)
             }
         } else {
-            // The player is further away in the x-axis, so we prioritize that while checking
+            // The target is further away in the x-axis, so we prioritize that while checking
             // movement options.
-            if player_pos.x < enemy.pos.x
+            if target.x < enemy.pos.x
                 && can_move_and_is_empty(
                     state,
                     &self.bounds,
@@ -202,7 +319,7 @@ impl EvilRoverActor {
                 )
             {
                 return self.move_or_turn(enemy.facing, Orientation::Left);
-            } else if player_pos.x > enemy.pos.x
+            } else if target.x > enemy.pos.x
                 && can_move_and_is_empty(
                     state,
                     &self.bounds,
@@ -212,7 +329,7 @@ impl EvilRoverActor {
                 return self.move_or_turn(enemy.facing, Orientation::Right);
             }
 
-            if player_pos.y < enemy.pos.y
+            if target.y < enemy.pos.y
                 && can_move_and_is_empty(
                     state,
                     &self.bounds,
@@ -220,7 +337,7 @@ impl EvilRoverActor {
                 )
             {
                 return self.move_or_turn(enemy.facing, Orientation::Up);
-            } else if player_pos.y > enemy.pos.y
+            } else if target.y > enemy.pos.y
                 && can_move_and_is_empty(
                     state,
                     &self.bounds,
@@ -230,9 +347,9 @@ impl EvilRoverActor {
                 return self.move_or_turn(enemy.facing, Orientation::Down);
             }
 
-            // If we get here, we can't move toward the player. Bump or turn while prioritizing
+            // If we get here, we can't move toward the target. Bump or turn while prioritizing
             // the x-axis.
-            if player_pos.x < enemy.pos.x {
+            if target.x < enemy.pos.x {
                 self.bump_or_turn(state, enemy.facing, Orientation::Left)
             } else {
                 self.bump_or_turn(state, enemy.facing, Orientation::Right)
@@ -241,6 +358,74 @@ impl EvilRoverActor {
     }
 }
 
+impl EvilRoverActor {
+    /// Uses a breadth-first search to find the shortest path from the enemy to
+    /// the target. Returns the direction of the first step along the path, or
+    /// None if there is no path.
+    fn shortest_path_direction(
+        &self,
+        state: &State,
+        target: &Pos,
+        use_telepads: bool,
+    ) -> Option<Orientation> {
+        let start = &state.enemies[self.index].pos;
+        let mut visited = HashSet::new();
+        visited.insert(start.clone());
+        // Each entry is a position along with the direction of the first step
+        // that was taken to get there.
+        let mut queue: VecDeque<(Pos, Orientation)> = VecDeque::new();
+
+        // Note: The order here determines which path is chosen if there are
+        // multiple shortest paths.
+        let directions = [
+            Orientation::Up,
+            Orientation::Down,
+            Orientation::Left,
+            Orientation::Right,
+        ];
+        let neighbor = |pos: &Pos, direction: Orientation| match direction {
+            Orientation::Up => Pos::new(pos.x, pos.y - 1),
+            Orientation::Down => Pos::new(pos.x, pos.y + 1),
+            Orientation::Left => Pos::new(pos.x - 1, pos.y),
+            Orientation::Right => Pos::new(pos.x + 1, pos.y),
+        };
+        for direction in directions {
+            queue.push_back((neighbor(start, direction), direction));
+        }
+
+        while let Some((pos, first_step)) = queue.pop_front() {
+            if pos == *target {
+                return Some(first_step);
+            }
+            if !visited.insert(pos.clone()) {
+                continue;
+            }
+            if !can_move_and_is_empty(state, &self.bounds, &pos) {
+                continue;
+            }
+            // Moving onto a telepad instantly moves the enemy to the other
+            // end of the telepad.
+            let pos = match get_telepad_at(state, &pos) {
+                Some(telepad) if use_telepads => {
+                    if telepad.end_pos == *target {
+                        return Some(first_step);
+                    }
+                    if !visited.insert(telepad.end_pos.clone()) {
+                        continue;
+                    }
+                    telepad.end_pos
+                }
+                Some(_) => continue,
+                None => pos,
+            };
+            for direction in directions {
+                queue.push_back((neighbor(&pos, direction), first_step));
+            }
+        }
+        None
+    }
+}
+
 impl Actor for EvilRoverActor {
     fn as_any(&self) -> &dyn Any {
         self
@@ -249,13 +434,29 @@ impl Actor for EvilRoverActor {
     fn apply(&mut self, state: State) -> State {
         let mut state = state.clone();
 
+        // Destroyed enemies can't do anything.
+        if state.enemies[self.index].destroyed {
+            return state;
+        }
+
         // Default to Idle state.
         state.enemies[self.index].anim_state = EnemyAnimState::Idle;
 
+        // Check if we can see the player (e.g. if the player just moved into view).
+        self.update_spotted_player(&mut state);
+
+        // If we reached the current position in our patrol route, start
+        // heading toward the next one.
+        let enemy = &mut state.enemies[self.index];
+        if !enemy.spotted_player && enemy.patrol_route.get(enemy.patrol_index) == Some(&enemy.pos) {
+            enemy.patrol_index = (enemy.patrol_index + 1) % enemy.patrol_route.len();
+        }
+
         // Update own state based on desired action.
         let action = self.get_next_action(&state);
 
         match action {
+            EvilRoverAction::Wait => {}
             EvilRoverAction::Move(direction) => {
                 let desired_pos = match direction {
                     MoveDirection::Forward => self.forward_pos(&state),
@@ -298,6 +499,9 @@ impl Actor for EvilRoverActor {
             }
         }
 
+        // Check again in case we just moved or turned to face the player.
+        self.update_spotted_player(&mut state);
+
         state
     }
 }
@@ -307,7 +511,9 @@ mod test {
     use super::*;
     use crate::{
         constants::{HEIGHT, WIDTH},
-        simulation::{Enemy, Player},
+        simulation::{
+            Crate, CrateColor, Gate, GateVariant, Hazard, HazardKind, Obstacle, Player, Telepad,
+        },
         state_maker::StateMaker,
     };
 
@@ -413,4 +619,190 @@ mod test {
             assert_eq!(action, tc.expected_action);
         }
     }
+
+    #[test]
+    fn can_see_player_blocked_by_obstacles() {
+        let mut state = StateMaker::new()
+            .with_player(Player::new(3, 0, 10, Orientation::Up))
+            .with_enemies(vec![Enemy::new_with_vision(
+                0,
+                0,
+                Orientation::Right,
+                EnemyVision::Line(5),
+                vec![],
+            )])
+            .build();
+        assert!(can_see_player(&state, &state.enemies[0]));
+
+        // Out of range.
+        state.enemies[0].vision = EnemyVision::Line(2);
+        assert!(!can_see_player(&state, &state.enemies[0]));
+
+        // Facing the wrong way.
+        state.enemies[0].vision = EnemyVision::Line(5);
+        state.enemies[0].facing = Orientation::Left;
+        assert!(!can_see_player(&state, &state.enemies[0]));
+
+        // Blocked by an obstacle.
+        state.enemies[0].facing = Orientation::Right;
+        state.obstacles = vec![Obstacle::new(2, 0)];
+        assert!(!can_see_player(&state, &state.enemies[0]));
+
+        // Blocked by a closed gate, but not an open one.
+        state.obstacles = vec![];
+        state.gates = vec![Gate::new(2, 0, false, GateVariant::NESW)];
+        assert!(!can_see_player(&state, &state.enemies[0]));
+        state.gates[0].open = true;
+        assert!(can_see_player(&state, &state.enemies[0]));
+
+        // Cones can see off to the side, but lines can't.
+        state.gates = vec![];
+        state.player.pos = Pos::new(3, 2);
+        assert!(!can_see_player(&state, &state.enemies[0]));
+        state.enemies[0].vision = EnemyVision::Cone(5);
+        assert!(can_see_player(&state, &state.enemies[0]));
+        state.crates = vec![Crate::new(2, 1, CrateColor::Red)];
+        assert!(!can_see_player(&state, &state.enemies[0]));
+    }
+
+    #[test]
+    fn chases_nearest_rover() {
+        // The extra rover is closer to the enemy than the main rover, so the
+        // enemy should turn to chase it.
+        let mut state = StateMaker::new()
+            .with_player(Player::new(5, 0, 10, Orientation::Up))
+            .with_extra_rovers(vec![Player::new(7, 5, 10, Orientation::Up)])
+            .with_enemies(vec![Enemy::new(5, 5, Orientation::Up)])
+            .build();
+        let actor = EvilRoverActor::new(0, Bounds::default());
+        assert_eq!(
+            actor.get_next_action(&state),
+            EvilRoverAction::Turn(TurnDirection::Right)
+        );
+
+        // Enemies with limited vision should spot the extra rover and chase
+        // it, even if they can't see the main rover.
+        state.enemies[0] =
+            Enemy::new_with_vision(5, 5, Orientation::Right, EnemyVision::Line(3), vec![]);
+        actor.update_spotted_player(&mut state);
+        assert!(state.enemies[0].spotted_player);
+        assert_eq!(
+            actor.get_next_action(&state),
+            EvilRoverAction::Move(MoveDirection::Forward)
+        );
+    }
+
+    #[test]
+    fn avoids_hazards() {
+        // The player is directly above the enemy, but there is a crater in
+        // the way.
+        let mut state = StateMaker::new()
+            .with_player(Player::new(1, 0, 10, Orientation::Up))
+            .with_enemies(vec![Enemy::new(1, 2, Orientation::Up)])
+            .with_hazards(vec![Hazard::new(1, 1, HazardKind::Crater)])
+            .build();
+        let bounds = Bounds::new(0, 3, 0, 3);
+
+        // The direct approach refuses to drive into the crater.
+        let actor = EvilRoverActor::new(0, Bounds::new(0, 3, 0, 3));
+        assert_eq!(
+            actor.get_next_action(&state),
+            EvilRoverAction::Bump(Pos::new(1, 1))
+        );
+
+        // The shortest path approach goes around it.
+        let actor = EvilRoverActor::new_with_chase_mode(0, bounds, ChaseMode::ShortestPath);
+        assert_ne!(
+            actor.shortest_path_direction(&state, &state.player.pos, false),
+            Some(Orientation::Up)
+        );
+
+        // Once the crater is bridged, it is safe to drive over.
+        state.crates = vec![Crate::new(1, 1, CrateColor::Red)];
+        state.hazards[0].bridged_by = Some(0);
+        assert_eq!(
+            actor.shortest_path_direction(&state, &state.player.pos, false),
+            Some(Orientation::Up)
+        );
+    }
+
+    #[test]
+    fn shortest_path_around_obstacles() {
+        // The player is directly above the enemy, but there is a wall in the
+        // way. The only way around is to the right.
+        let state = StateMaker::new()
+            .with_player(Player::new(1, 0, 10, Orientation::Up))
+            .with_enemies(vec![Enemy::new(1, 2, Orientation::Up)])
+            .with_obstacles(vec![
+                Obstacle::new(0, 1),
+                Obstacle::new(1, 1),
+                Obstacle::new(2, 1),
+            ])
+            .build();
+        let bounds = Bounds::new(0, 3, 0, 3);
+
+        // The direct approach just bumps into the wall.
+        let actor = EvilRoverActor::new(0, Bounds::new(0, 3, 0, 3));
+        assert_eq!(
+            actor.get_next_action(&state),
+            EvilRoverAction::Bump(Pos::new(1, 1))
+        );
+
+        // The shortest path approach goes around it.
+        let actor = EvilRoverActor::new_with_chase_mode(0, bounds, ChaseMode::ShortestPath);
+        assert_eq!(
+            actor.shortest_path_direction(&state, &state.player.pos, false),
+            Some(Orientation::Right)
+        );
+        assert_eq!(
+            actor.get_next_action(&state),
+            EvilRoverAction::Turn(TurnDirection::Right)
+        );
+
+        // If there is no path at all, fall back to the direct approach.
+        let mut state = state;
+        state.obstacles.push(Obstacle::new(3, 1));
+        assert_eq!(
+            actor.shortest_path_direction(&state, &state.player.pos, false),
+            None
+        );
+        assert_eq!(
+            actor.get_next_action(&state),
+            EvilRoverAction::Bump(Pos::new(1, 1))
+        );
+    }
+
+    #[test]
+    fn shortest_path_with_telepads() {
+        // The player is walled off, but there is a telepad which leads to
+        // the other side of the wall.
+        let state = StateMaker::new()
+            .with_player(Player::new(1, 0, 10, Orientation::Up))
+            .with_enemies(vec![Enemy::new(1, 2, Orientation::Up)])
+            .with_obstacles(vec![
+                Obstacle::new(0, 1),
+                Obstacle::new(1, 1),
+                Obstacle::new(2, 1),
+                Obstacle::new(3, 1),
+            ])
+            .with_telepads(vec![Telepad::new((0, 2), (3, 0), Orientation::Left)])
+            .build();
+        let bounds = Bounds::new(0, 3, 0, 3);
+
+        // Telepads are ignored unless the mode allows them.
+        let actor = EvilRoverActor::new_with_chase_mode(0, bounds, ChaseMode::ShortestPath);
+        assert_eq!(
+            actor.shortest_path_direction(&state, &state.player.pos, false),
+            None
+        );
+        let actor = EvilRoverActor::new_with_chase_mode(
+            0,
+            Bounds::new(0, 3, 0, 3),
+            ChaseMode::ShortestPathWithTelepads,
+        );
+        assert_eq!(
+            actor.get_next_action(&state),
+            EvilRoverAction::Turn(TurnDirection::Left)
+        );
+    }
 }
@@ -2,30 +2,32 @@ mod asteroid_actor;
 mod big_enemy_actor;
 mod evil_rover_actor;
 mod player_actor;
+mod turret_actor;
 
 use crate::{
     constants::{HEIGHT, WIDTH},
-    simulation::{AsteroidAnimState, Pos, State, Telepad},
+    simulation::{is_bridge, AsteroidAnimState, Player, Pos, State, Telepad},
 };
 
 pub use asteroid_actor::AsteroidActor;
 pub use big_enemy_actor::BigEnemyActor;
-pub use big_enemy_actor::BIG_ENEMY_SIZE;
-pub use evil_rover_actor::EvilRoverActor;
+pub use evil_rover_actor::{ChaseMode, EvilRoverActor};
 pub use player_actor::PlayerChannelActor;
+pub use turret_actor::TurretActor;
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub enum MoveDirection {
     Forward,
     Backward,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub enum TurnDirection {
     Right,
     Left,
 }
 
+#[derive(PartialEq, Debug, Clone)]
 pub enum Action {
     Wait,
     Move(MoveDirection),
@@ -37,6 +39,77 @@ pub enum Action {
     Drop,
 }
 
+/// The amount of energy each kind of action costs. Levels can override this via
+/// Level::energy_costs.
+#[derive(PartialEq, Debug, Clone)]
+pub struct EnergyCosts {
+    pub wait: u32,
+    /// Applies to moving forward and backward.
+    pub movement: u32,
+    /// Used instead of movement if the rover is holding a crate.
+    pub movement_while_holding: u32,
+    pub turn: u32,
+    pub say: u32,
+    pub read_data: u32,
+    pub press_button: u32,
+    pub pick_up: u32,
+    pub drop: u32,
+}
+
+impl EnergyCosts {
+    /// Returns the energy cost for the given action.
+    pub fn cost(&self, action: &Action, holding_crate: bool) -> u32 {
+        match action {
+            Action::Wait => self.wait,
+            Action::Move(_) if holding_crate => self.movement_while_holding,
+            Action::Move(_) => self.movement,
+            Action::Turn(_) => self.turn,
+            Action::Say(_) => self.say,
+            Action::ReadData => self.read_data,
+            Action::PressButton => self.press_button,
+            Action::PickUp => self.pick_up,
+            Action::Drop => self.drop,
+        }
+    }
+
+    /// Returns the cost of the cheapest action. A rover with less energy than
+    /// this can't do anything else for the rest of the level.
+    pub fn min_cost(&self) -> u32 {
+        [
+            self.wait,
+            self.movement,
+            self.movement_while_holding,
+            self.turn,
+            self.say,
+            self.read_data,
+            self.press_button,
+            self.pick_up,
+            self.drop,
+        ]
+        .iter()
+        .copied()
+        .min()
+        .unwrap()
+    }
+}
+
+impl Default for EnergyCosts {
+    /// By default, moving costs one energy and everything else is free.
+    fn default() -> Self {
+        EnergyCosts {
+            wait: 0,
+            movement: 1,
+            movement_while_holding: 1,
+            turn: 0,
+            say: 0,
+            read_data: 0,
+            press_button: 0,
+            pick_up: 0,
+            drop: 0,
+        }
+    }
+}
+
 pub struct Bounds {
     pub min_x: i32,
     pub max_x: i32,
@@ -95,9 +168,16 @@ fn is_obstacle_at(state: &State, pos: &Pos) -> bool {
             return true;
         }
     }
-    // Unheld crates are treated as obstacles.
-    for crt in &state.crates {
-        if crt.pos == *pos && !crt.held {
+    // Unheld crates are treated as obstacles (unless they are being used as a
+    // bridge over a hazard or have been crushed).
+    for (i, crt) in state.crates.iter().enumerate() {
+        if crt.pos == *pos && !crt.held && !crt.crushed && !is_bridge(state, i) {
+            return true;
+        }
+    }
+    // Turrets never move.
+    for turret in &state.turrets {
+        if turret.pos == *pos {
             return true;
         }
     }
@@ -130,6 +210,65 @@ fn can_move_to(state: &State, bounds: &Bounds, desired_pos: &Pos) -> bool {
     !is_obstacle_at(state, desired_pos) && !is_outside_bounds(bounds, desired_pos)
 }
 
+/// Returns the index of the hazard at the given position if there is one that
+/// has not been bridged.
+fn get_active_hazard_at(state: &State, pos: &Pos) -> Option<usize> {
+    state
+        .hazards
+        .iter()
+        .position(|hazard| hazard.pos == *pos && hazard.is_active())
+}
+
+/// Like can_move_to, but also returns false if there is an active hazard at the
+/// given position. Enemies use this since they know better than to drive into
+/// hazards.
+fn can_move_to_safely(state: &State, bounds: &Bounds, desired_pos: &Pos) -> bool {
+    can_move_to(state, bounds, desired_pos) && get_active_hazard_at(state, desired_pos).is_none()
+}
+
+/// Returns true if nothing is blocking the view between the two given positions.
+/// Only the spaces in between are checked, so e.g. an obstacle at the end
+/// position does not block the view.
+fn has_line_of_sight(state: &State, from: &Pos, to: &Pos) -> bool {
+    if from == to {
+        return true;
+    }
+    // Walk along the line between the two positions using Bresenham's line
+    // algorithm. See https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm
+    let dx = (to.x - from.x).abs();
+    let dy = -(to.y - from.y).abs();
+    let step_x = if from.x < to.x { 1 } else { -1 };
+    let step_y = if from.y < to.y { 1 } else { -1 };
+    let mut err = dx + dy;
+    let mut curr = from.clone();
+    loop {
+        let double_err = 2 * err;
+        if double_err >= dy {
+            err += dy;
+            curr.x += step_x;
+        }
+        if double_err <= dx {
+            err += dx;
+            curr.y += step_y;
+        }
+        if curr == *to {
+            return true;
+        }
+        if is_obstacle_at(state, &curr) {
+            return false;
+        }
+    }
+}
+
+/// Returns the position of whichever of the given rovers is nearest to pos
+/// (counting the spaces horizontally plus vertically), or None if there are no
+/// rovers. Ties go to the rover which comes first.
+fn nearest_rover_pos<'a>(rovers: impl Iterator<Item = &'a Player>, pos: &Pos) -> Option<&'a Pos> {
+    rovers
+        .map(|rover| &rover.pos)
+        .min_by_key(|rover_pos| rover_pos.x.abs_diff(pos.x) + rover_pos.y.abs_diff(pos.y))
+}
+
 /// Returns the index of any password gates adjacent to the given position.
 /// Returns an empty vector if there is no adjacent gate.
 fn get_adjacent_password_gates(state: &State, pos: &Pos) -> Vec<usize> {
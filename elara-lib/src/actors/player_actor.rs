@@ -1,34 +1,76 @@
 use std::any::Any;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::mpsc;
 
-use crate::constants::{
-    ENERGY_CELL_AMOUNT, PLAYER_ERR_ALREADY_HOLDING, PLAYER_ERR_NOTHING_TO_DROP,
-    PLAYER_ERR_NOTHING_TO_PICK_UP, PLAYER_ERR_NO_SPACE_TO_DROP,
-};
+use crate::constants::{ENERGY_CELL_AMOUNT, MAX_ENERGY};
+use crate::i18n::translate;
 use crate::simulation::{
     get_adjacent_button, get_adjacent_point, get_crate_in_front, Actor, BumpAnimData,
-    ButtonConnection, Orientation, PlayerAnimState, Pos, State, TeleAnimData,
+    ButtonConnection, HazardEffect, Orientation, Player, PlayerAnimState, Pos, State, TeleAnimData,
 };
 
 use super::{
-    can_move_to, get_adjacent_password_gates, get_telepad_at, is_obstacle_at, is_outside_bounds,
-    Action, Bounds, MoveDirection, TurnDirection,
+    can_move_to, get_active_hazard_at, get_adjacent_password_gates, get_telepad_at, is_obstacle_at,
+    is_outside_bounds, Action, Bounds, EnergyCosts, MoveDirection, TurnDirection,
 };
 
 pub struct PlayerChannelActor {
     rx: Rc<RefCell<mpsc::Receiver<Action>>>,
     bounds: Bounds,
+    energy_costs: EnergyCosts,
+    /// The index of the rover which actions received through the channel are
+    /// applied to. 0 is the main rover (i.e. state.player). See State::rover.
+    active_rover: usize,
+    /// Previously recorded actions for rovers which are not controlled by the
+    /// running script, indexed by rover and then by step. This is used to
+    /// replay an opponent's actions in versus mode.
+    replays: HashMap<usize, Vec<Action>>,
+    /// The actions received through the channel for each step so far (Wait if
+    /// no action was received).
+    received: Vec<Action>,
 }
 
 impl PlayerChannelActor {
     pub fn new(rx: Rc<RefCell<mpsc::Receiver<Action>>>, bounds: Bounds) -> PlayerChannelActor {
-        PlayerChannelActor { rx, bounds }
+        PlayerChannelActor {
+            rx,
+            bounds,
+            energy_costs: EnergyCosts::default(),
+            active_rover: 0,
+            replays: HashMap::new(),
+            received: vec![],
+        }
     }
 
-    pub fn set_bounds(&mut self, bounds: Bounds) {
+    /// Resets the actor so that it is ready for a new simulation run. Clears any
+    /// replays and received actions and makes the main rover active again.
+    pub fn reset(&mut self, bounds: Bounds, energy_costs: EnergyCosts) {
         self.bounds = bounds;
+        self.energy_costs = energy_costs;
+        self.active_rover = 0;
+        self.replays.clear();
+        self.received.clear();
+    }
+
+    pub fn active_rover(&self) -> usize {
+        self.active_rover
+    }
+
+    pub fn set_active_rover(&mut self, index: usize) {
+        self.active_rover = index;
+    }
+
+    /// Sets the actions to replay for the given rover, one per step. Actions
+    /// received through the channel take precedence for the active rover.
+    pub fn set_replay(&mut self, rover_index: usize, actions: Vec<Action>) {
+        self.replays.insert(rover_index, actions);
+    }
+
+    /// Returns the actions received through the channel for each step so far.
+    pub fn received_actions(&self) -> &[Action] {
+        &self.received
     }
 }
 
@@ -39,11 +81,18 @@ impl Actor for PlayerChannelActor {
 
     fn apply(&mut self, state: State) -> State {
         let mut state = state.clone();
+        let step = self.received.len();
+        let received = self.rx.clone().borrow().try_recv().ok();
+        self.received.push(received.clone().unwrap_or(Action::Wait));
 
-        // Reset the player message and error message every time an
-        // action is taken. We only want messages to persist for one step.
+        // Reset the message and error message for every rover. We only want
+        // messages to persist for one step.
         state.player.message = String::new();
         state.player.err_message = String::new();
+        for rover in state.extra_rovers.iter_mut() {
+            rover.message = String::new();
+            rover.err_message = String::new();
+        }
 
         // Reset the reading state of all data points.
         for d_point in state.data_points.iter_mut() {
@@ -60,18 +109,93 @@ impl Actor for PlayerChannelActor {
             gate.wrong_password = false;
         }
 
-        let rx = self.rx.clone();
-        match rx.borrow().try_recv() {
-            Ok(Action::Wait) => {}
-            Ok(Action::Move(direction)) => {
-                // We can't move if we're out of energy.
-                if state.player.energy == 0 {
-                    return state;
+        // Charging pads cool down a little bit each step.
+        for pad in state.charging_pads.iter_mut() {
+            pad.charging = false;
+            pad.cooldown_remaining = pad.cooldown_remaining.saturating_sub(1);
+        }
+
+        // Rovers always act in order of their index, so that the outcome of
+        // each step does not depend on which rover is controlled by the script.
+        for index in 0..state.num_rovers() {
+            let replayed = self
+                .replays
+                .get(&index)
+                .and_then(|actions| actions.get(step).cloned());
+            let action = if index == self.active_rover {
+                received.clone().or(replayed)
+            } else {
+                replayed
+            };
+            if action.is_none() && index != self.active_rover {
+                // Rovers which are not being controlled don't do anything this step.
+                if let Some(rover) = rover_mut(&mut state, index) {
+                    rover.anim_state = PlayerAnimState::Idle;
+                }
+            }
+            state = self.apply_to_rover(state, index, action);
+        }
+
+        state
+    }
+}
+
+/// Returns a mutable reference to the rover with the given index, where 0 is the
+/// main rover.
+fn rover_mut(state: &mut State, index: usize) -> Option<&mut Player> {
+    if index == 0 {
+        Some(&mut state.player)
+    } else {
+        state.extra_rovers.get_mut(index - 1)
+    }
+}
+
+impl PlayerChannelActor {
+    /// Applies the given action (if any) to the rover with the given index.
+    fn apply_to_rover(&self, state: State, index: usize, action: Option<Action>) -> State {
+        if index == 0 {
+            return self.apply_to_player(state, action);
+        }
+
+        // All of the logic for handling actions operates on state.player, so we
+        // temporarily swap the rover into that position. While swapped, the main
+        // rover is just another one of the extra rovers.
+        let mut state = state;
+        std::mem::swap(&mut state.player, &mut state.extra_rovers[index - 1]);
+        let mut state = self.apply_to_player(state, action);
+        std::mem::swap(&mut state.player, &mut state.extra_rovers[index - 1]);
+        state
+    }
+
+    /// Applies the given action (if any) to state.player.
+    fn apply_to_player(&self, state: State, action: Option<Action>) -> State {
+        let mut state = state;
+
+        let start_pos = state.player.pos.clone();
+
+        // Actions which cost more energy than we have don't do anything except
+        // show an error message.
+        let action = match action {
+            Some(action) => {
+                let cost = self
+                    .energy_costs
+                    .cost(&action, state.player.held_crate_index.is_some());
+                if state.player.energy < cost {
+                    state.player.err_message = translate("player_err_not_enough_energy", &[]);
+                    None
+                } else {
+                    state.player.energy -= cost;
+                    state.player.total_energy_used += cost;
+                    Some(action)
                 }
+            }
+            None => None,
+        };
 
-                // Moving in any direction costs one energy.
-                state.player.energy -= 1;
-                state.player.total_energy_used += 1;
+        let waited = action == Some(Action::Wait);
+        match action {
+            Some(Action::Wait) => {}
+            Some(Action::Move(direction)) => {
                 // Update the position and animation state. Note that the player may not
                 // be able to actually move if there are obstacles in the way.
                 let (new_pos, new_facing, new_anim_state) = self.try_to_move(&state, direction);
@@ -84,7 +208,7 @@ impl Actor for PlayerChannelActor {
                     state.crates[crate_index].pos = state.player.pos.clone();
                 }
             }
-            Ok(Action::Turn(direction)) => {
+            Some(Action::Turn(direction)) => {
                 state.player.anim_state = PlayerAnimState::Turning;
                 if direction == TurnDirection::Right {
                     state.player.facing = match state.player.facing {
@@ -102,7 +226,7 @@ impl Actor for PlayerChannelActor {
                     };
                 }
             }
-            Ok(Action::Say(message)) => {
+            Some(Action::Say(message)) => {
                 // If we're next to any password gates and we said the password, toggle the gate.
                 get_adjacent_password_gates(&state, &state.player.pos)
                     .iter()
@@ -119,7 +243,7 @@ impl Actor for PlayerChannelActor {
                 state.player.anim_state = PlayerAnimState::Idle;
                 state.player.message = message;
             }
-            Ok(Action::ReadData) => {
+            Some(Action::ReadData) => {
                 // If we're next to a data point, mark it as being currently read.
                 // (The reading state only affects the UI).
                 if let Some(d_point_index) = get_adjacent_point(&state, &state.player.pos) {
@@ -127,20 +251,20 @@ impl Actor for PlayerChannelActor {
                 }
                 state.player.anim_state = PlayerAnimState::Idle;
             }
-            Ok(Action::PressButton) => {
+            Some(Action::PressButton) => {
                 if let Some(button_index) = get_adjacent_button(&state, &state.player.pos) {
                     self.handle_button_press(&mut state, button_index);
                 }
                 state.player.anim_state = PlayerAnimState::Idle;
             }
-            Ok(Action::PickUp) => {
+            Some(Action::PickUp) => {
                 // If the player is already holding a crate, show an error message.
                 if state
                     .crates
                     .iter()
                     .any(|c| c.pos == state.player.pos && c.held)
                 {
-                    state.player.err_message = PLAYER_ERR_ALREADY_HOLDING.to_string();
+                    state.player.err_message = translate("player_err_already_holding", &[]);
                 } else if let Some(crate_index) = get_crate_in_front(&state) {
                     // If there is a crate in front of the player, pick it up.
                     state.player.anim_state = PlayerAnimState::PickingUp;
@@ -149,13 +273,13 @@ impl Actor for PlayerChannelActor {
                     state.player.held_crate_index = Some(crate_index);
                 } else {
                     // If there is no crate in front of the player, show an error message.
-                    state.player.err_message = PLAYER_ERR_NOTHING_TO_PICK_UP.to_string();
+                    state.player.err_message = translate("player_err_nothing_to_pick_up", &[]);
                 }
             }
-            Ok(Action::Drop) => {
+            Some(Action::Drop) => {
                 if state.player.held_crate_index.is_none() {
                     // If the player is not holding a crate, show an error message.
-                    state.player.err_message = PLAYER_ERR_NOTHING_TO_DROP.to_string();
+                    state.player.err_message = translate("player_err_nothing_to_drop", &[]);
                 } else if let Some(held_crate_index) = state.player.held_crate_index {
                     // If the player is holding a crate, try to drop it directly in front of
                     // the player if possible.
@@ -171,7 +295,7 @@ impl Actor for PlayerChannelActor {
                         // If there is an obstacle in the way of where we want to drop the crate,
                         // don't drop it and show an error message instead. We also apply a special
                         // "drop bump" animation state.
-                        state.player.err_message = PLAYER_ERR_NO_SPACE_TO_DROP.to_string();
+                        state.player.err_message = translate("player_err_no_space_to_drop", &[]);
                         state.player.anim_state = PlayerAnimState::DropBumping(BumpAnimData {
                             pos: state.player.pos.clone(),
                             obstacle_pos: new_crate_pos,
@@ -180,12 +304,19 @@ impl Actor for PlayerChannelActor {
                         // If we've reached here we can drop the crate.
                         state.player.anim_state = PlayerAnimState::Dropping;
                         state.crates[held_crate_index].held = false;
-                        state.crates[held_crate_index].pos = new_crate_pos;
+                        state.crates[held_crate_index].pos = new_crate_pos.clone();
                         state.player.held_crate_index = None;
+                        // Crates dropped into some hazards turn them into a bridge.
+                        if let Some(hazard_index) = get_active_hazard_at(&state, &new_crate_pos) {
+                            let hazard = &mut state.hazards[hazard_index];
+                            if hazard.bridgeable {
+                                hazard.bridged_by = Some(held_crate_index);
+                            }
+                        }
                     }
                 }
             }
-            Err(_) => {}
+            None => {}
         }
 
         // If we're on a energy cell *after moving*, increase our current energy
@@ -198,11 +329,39 @@ impl Actor for PlayerChannelActor {
             }
         }
 
+        // If we drove onto a hazard which drains energy, lose some energy. (Hazards
+        // which cause a failure are handled by Level::check_win.)
+        if state.player.pos != start_pos {
+            if let Some(hazard_index) = get_active_hazard_at(&state, &state.player.pos) {
+                if let HazardEffect::Drain(amount) = state.hazards[hazard_index].effect {
+                    let drained = amount.min(state.player.energy);
+                    state.player.energy -= drained;
+                    state.player.total_energy_used += drained;
+                }
+            }
+        }
+
+        // If we waited on a charging pad, restore some energy. (Doing anything
+        // else on the pad, even if it doesn't move the rover, doesn't count.)
+        if waited {
+            let pos = state.player.pos.clone();
+            if let Some(pad) = state
+                .charging_pads
+                .iter_mut()
+                .find(|pad| pad.pos == pos && pad.cooldown_remaining == 0)
+            {
+                if state.player.energy < MAX_ENERGY {
+                    state.player.energy = (state.player.energy + pad.rate).min(MAX_ENERGY);
+                    pad.charging = true;
+                    // Counting the next step, which is when the cooldown starts.
+                    pad.cooldown_remaining = pad.cooldown + 1;
+                }
+            }
+        }
+
         state
     }
-}
 
-impl PlayerChannelActor {
     /// First checks if we can move in the desired direction, and if so, returns the
     /// new position. Otherwise, returns the current position.
     fn try_to_move(
@@ -231,7 +390,8 @@ impl PlayerChannelActor {
                 }),
             );
         }
-        if can_move_to(state, &self.bounds, &desired_pos) {
+        let blocked_by_rover = state.extra_rovers.iter().any(|r| r.pos == desired_pos);
+        if !blocked_by_rover && can_move_to(state, &self.bounds, &desired_pos) {
             (desired_pos, state.player.facing, PlayerAnimState::Moving)
         } else {
             (
@@ -265,10 +425,12 @@ impl PlayerChannelActor {
 mod test {
     use super::*;
     use crate::{
-        constants::MAX_ENERGY,
+        actors::AsteroidActor,
+        constants::{ELECTRIFIED_FLOOR_DRAIN, MAX_ENERGY},
         simulation::{
-            Asteroid, AsteroidAnimState, Button, Crate, CrateColor, DataPoint, Gate, GateVariant,
-            Obstacle, PasswordGate, Player, PlayerAnimState, Pos, State, Telepad,
+            Asteroid, AsteroidAnimState, AsteroidWarning, Button, ChargingPad, Crate, CrateColor,
+            DataPoint, Gate, GateVariant, Hazard, HazardKind, Obstacle, PasswordGate, Player,
+            PlayerAnimState, Pos, State, Telepad,
         },
     };
 
@@ -956,6 +1118,7 @@ mod test {
                 pos: Pos::new(1, 1),
                 held: true,
                 color: CrateColor::Red,
+                crushed: false,
             }
         );
         assert_eq!(new_state.player.held_crate_index, Some(0));
@@ -983,12 +1146,55 @@ mod test {
         // should be the same as the player's.
         assert_eq!(
             new_state.player.err_message,
-            PLAYER_ERR_NOTHING_TO_PICK_UP.to_string()
+            translate("player_err_nothing_to_pick_up", &[])
         );
         assert_eq!(new_state.player.held_crate_index, None);
         assert_eq!(new_state.player.anim_state, PlayerAnimState::Idle);
     }
 
+    #[test]
+    fn drop_after_other_crate_crushed() {
+        let bounds = Bounds {
+            min_x: 0,
+            max_x: 10,
+            min_y: 0,
+            max_y: 10,
+        };
+        let (tx, rx) = mpsc::channel();
+        let mut actor = PlayerChannelActor::new(Rc::new(RefCell::new(rx)), bounds);
+        let mut asteroid_actor = AsteroidActor::new();
+        let mut state = State::new();
+        state.player = Player::new(1, 1, MAX_ENERGY, Orientation::Right);
+        state.crates = vec![
+            Crate::new(5, 5, CrateColor::Red),
+            Crate::new(1, 1, CrateColor::Blue),
+        ];
+        state.crates[1].held = true;
+        state.player.held_crate_index = Some(1);
+        state.asteroid_warnings = vec![AsteroidWarning::new(5, 5, 2, true)];
+
+        // Crush the crate which is not being held.
+        let state = asteroid_actor.apply(state);
+        let state = asteroid_actor.apply(state);
+        assert!(state.crates[0].crushed);
+        assert!(!state.crates[1].crushed);
+
+        // The player should still be able to drop the crate they are holding.
+        tx.send(Action::Drop).unwrap();
+        let new_state = actor.apply(state);
+        assert_eq!(new_state.player.held_crate_index, None);
+        assert_eq!(new_state.crates[0].pos, Pos::new(5, 5));
+        assert_eq!(
+            new_state.crates[1],
+            Crate {
+                pos: Pos::new(2, 1),
+                held: false,
+                color: CrateColor::Blue,
+                crushed: false,
+            }
+        );
+    }
+
     #[test]
     fn already_holding_something() {
         let bounds = Bounds {
@@ -1015,7 +1221,7 @@ mod test {
         // The player should still be holding the first crate and showing an error message.
         assert_eq!(
             new_state.player.err_message,
-            PLAYER_ERR_ALREADY_HOLDING.to_string()
+            translate("player_err_already_holding", &[])
         );
         assert_eq!(new_state.player.held_crate_index, Some(0));
         assert_eq!(new_state.player.anim_state, PlayerAnimState::Idle);
@@ -1045,6 +1251,7 @@ mod test {
                 pos: Pos::new(1, 1),
                 held: true,
                 color: CrateColor::Red,
+                crushed: false,
             }
         );
         assert_eq!(new_state.player.held_crate_index, Some(1));
@@ -1094,6 +1301,7 @@ mod test {
                 pos: Pos::new(2, 1),
                 held: true,
                 color: CrateColor::Red,
+                crushed: false,
             }
         );
 
@@ -1119,6 +1327,7 @@ mod test {
                 pos: Pos::new(1, 1),
                 held: true,
                 color: CrateColor::Red,
+                crushed: false,
             }
         );
 
@@ -1147,6 +1356,7 @@ mod test {
                 pos: Pos::new(1, 2),
                 held: true,
                 color: CrateColor::Red,
+                crushed: false,
             }
         );
 
@@ -1172,6 +1382,7 @@ mod test {
                 pos: Pos::new(1, 1),
                 held: true,
                 color: CrateColor::Red,
+                crushed: false,
             }
         );
     }
@@ -1211,6 +1422,7 @@ mod test {
                 pos: Pos::new(3, 1),
                 held: false,
                 color: CrateColor::Red,
+                crushed: false,
             }
         );
 
@@ -1230,6 +1442,7 @@ mod test {
                 pos: Pos::new(2, 2),
                 held: false,
                 color: CrateColor::Red,
+                crushed: false,
             }
         );
     }
@@ -1255,7 +1468,7 @@ mod test {
 
         assert_eq!(
             new_state.player.err_message,
-            PLAYER_ERR_NOTHING_TO_DROP.to_string()
+            translate("player_err_nothing_to_drop", &[])
         );
     }
 
@@ -1291,7 +1504,7 @@ mod test {
         // Should still be holding the crate and showing an error message.
         assert_eq!(
             new_state.player.err_message,
-            PLAYER_ERR_NO_SPACE_TO_DROP.to_string()
+            translate("player_err_no_space_to_drop", &[])
         );
         assert_eq!(new_state.player.held_crate_index, Some(0));
         assert!(new_state.crates[0].held);
@@ -1313,7 +1526,7 @@ mod test {
         // Should still be holding the crate and showing an error message.
         assert_eq!(
             new_state.player.err_message,
-            PLAYER_ERR_NO_SPACE_TO_DROP.to_string()
+            translate("player_err_no_space_to_drop", &[])
         );
         assert_eq!(new_state.player.held_crate_index, Some(0));
         assert!(new_state.crates[0].held);
@@ -1336,7 +1549,7 @@ mod test {
         // Should still be holding the crate and showing an error message.
         assert_eq!(
             new_state.player.err_message,
-            PLAYER_ERR_NO_SPACE_TO_DROP.to_string()
+            translate("player_err_no_space_to_drop", &[])
         );
         assert_eq!(new_state.player.held_crate_index, Some(0));
         assert!(new_state.crates[0].held);
@@ -1360,7 +1573,7 @@ mod test {
         // Should still be holding the crate and showing an error message.
         assert_eq!(
             new_state.player.err_message,
-            PLAYER_ERR_NO_SPACE_TO_DROP.to_string()
+            translate("player_err_no_space_to_drop", &[])
         );
         assert_eq!(new_state.player.held_crate_index, Some(0));
         assert!(new_state.crates[0].held);
@@ -1372,4 +1585,223 @@ mod test {
             })
         );
     }
+
+    #[test]
+    fn multiple_rovers() {
+        let bounds = Bounds {
+            min_x: 0,
+            max_x: 10,
+            min_y: 0,
+            max_y: 10,
+        };
+        let (tx, rx) = mpsc::channel();
+        let mut actor = PlayerChannelActor::new(Rc::new(RefCell::new(rx)), bounds);
+        let mut state = State::new();
+        state.player = Player::new(1, 1, MAX_ENERGY, Orientation::Right);
+        state.extra_rovers = vec![Player::new(3, 1, MAX_ENERGY, Orientation::Left)];
+
+        // By default, actions are applied to the main rover.
+        tx.send(Action::Move(MoveDirection::Forward)).unwrap();
+        let state = actor.apply(state);
+        assert_eq!(state.player.pos, Pos::new(2, 1));
+        assert_eq!(state.extra_rovers[0].pos, Pos::new(3, 1));
+
+        // Rovers can't move onto each other.
+        actor.set_active_rover(1);
+        tx.send(Action::Move(MoveDirection::Forward)).unwrap();
+        let state = actor.apply(state);
+        assert_eq!(state.player.pos, Pos::new(2, 1));
+        assert_eq!(state.player.anim_state, PlayerAnimState::Idle);
+        assert_eq!(state.extra_rovers[0].pos, Pos::new(3, 1));
+        assert_eq!(state.extra_rovers[0].energy, MAX_ENERGY - 1);
+        assert_eq!(
+            state.extra_rovers[0].anim_state,
+            PlayerAnimState::Bumping(BumpAnimData {
+                pos: Pos::new(3, 1),
+                obstacle_pos: Pos::new(2, 1),
+            })
+        );
+
+        // Each rover keeps track of its own position and facing.
+        tx.send(Action::Move(MoveDirection::Backward)).unwrap();
+        let state = actor.apply(state);
+        assert_eq!(state.extra_rovers[0].pos, Pos::new(4, 1));
+        assert_eq!(state.extra_rovers[0].facing, Orientation::Left);
+        assert_eq!(state.player.facing, Orientation::Right);
+        assert_eq!(state.player.energy, MAX_ENERGY - 1);
+    }
+
+    #[test]
+    fn custom_energy_costs() {
+        let bounds = Bounds {
+            min_x: 0,
+            max_x: 10,
+            min_y: 0,
+            max_y: 10,
+        };
+        let (tx, rx) = mpsc::channel();
+        let mut actor = PlayerChannelActor::new(Rc::new(RefCell::new(rx)), bounds);
+        actor.reset(
+            Bounds::new(0, 10, 0, 10),
+            EnergyCosts {
+                wait: 1,
+                movement: 2,
+                movement_while_holding: 4,
+                turn: 1,
+                ..EnergyCosts::default()
+            },
+        );
+        let mut state = State::new();
+        state.player = Player::new(1, 1, 8, Orientation::Right);
+        state.crates = vec![Crate::new(3, 1, CrateColor::Red)];
+
+        tx.send(Action::Wait).unwrap();
+        let state = actor.apply(state);
+        assert_eq!(state.player.energy, 7);
+
+        tx.send(Action::Move(MoveDirection::Forward)).unwrap();
+        let state = actor.apply(state);
+        assert_eq!(state.player.energy, 5);
+        assert_eq!(state.player.pos, Pos::new(2, 1));
+
+        // Picking up is still free, but moving while holding a crate is more
+        // expensive.
+        tx.send(Action::PickUp).unwrap();
+        let state = actor.apply(state);
+        assert_eq!(state.player.energy, 5);
+        tx.send(Action::Move(MoveDirection::Backward)).unwrap();
+        let state = actor.apply(state);
+        assert_eq!(state.player.energy, 1);
+        assert_eq!(state.player.total_energy_used, 7);
+
+        // Actions which cost more energy than we have don't do anything except
+        // show an error message.
+        tx.send(Action::Move(MoveDirection::Forward)).unwrap();
+        let state = actor.apply(state);
+        assert_eq!(state.player.energy, 1);
+        assert_eq!(state.player.pos, Pos::new(1, 1));
+        assert_eq!(
+            state.player.err_message,
+            translate("player_err_not_enough_energy", &[])
+        );
+        tx.send(Action::Turn(TurnDirection::Left)).unwrap();
+        let state = actor.apply(state);
+        assert_eq!(state.player.energy, 0);
+        assert_eq!(state.player.facing, Orientation::Up);
+    }
+
+    #[test]
+    fn charging_pads() {
+        let bounds = Bounds {
+            min_x: 0,
+            max_x: 10,
+            min_y: 0,
+            max_y: 10,
+        };
+        let (tx, rx) = mpsc::channel();
+        let mut actor = PlayerChannelActor::new(Rc::new(RefCell::new(rx)), bounds);
+        let mut state = State::new();
+        state.player = Player::new(1, 1, MAX_ENERGY - 7, Orientation::Right);
+        state.charging_pads = vec![ChargingPad::new_with_cooldown(2, 1, 3, 2)];
+
+        // Arriving on the pad doesn't restore any energy.
+        tx.send(Action::Move(MoveDirection::Forward)).unwrap();
+        let state = actor.apply(state);
+        assert_eq!(state.player.energy, MAX_ENERGY - 8);
+        assert!(!state.charging_pads[0].charging);
+
+        // Waiting on the pad restores energy, then the pad needs to rest for
+        // two steps.
+        let mut energy = vec![];
+        let mut state = state;
+        for _ in 0..4 {
+            tx.send(Action::Wait).unwrap();
+            state = actor.apply(state);
+            energy.push(state.player.energy);
+        }
+        assert_eq!(
+            energy,
+            vec![
+                MAX_ENERGY - 5,
+                MAX_ENERGY - 5,
+                MAX_ENERGY - 5,
+                MAX_ENERGY - 2
+            ]
+        );
+
+        // Turning on the pad doesn't restore any energy, even though the pad
+        // is ready again.
+        for _ in 0..3 {
+            tx.send(Action::Turn(TurnDirection::Left)).unwrap();
+            state = actor.apply(state);
+        }
+        assert_eq!(state.player.energy, MAX_ENERGY - 2);
+        assert!(!state.charging_pads[0].charging);
+
+        // Energy is never restored beyond MAX_ENERGY.
+        for _ in 0..3 {
+            tx.send(Action::Wait).unwrap();
+            state = actor.apply(state);
+        }
+        assert_eq!(state.player.energy, MAX_ENERGY);
+    }
+
+    #[test]
+    fn hazards() {
+        let bounds = Bounds {
+            min_x: 0,
+            max_x: 10,
+            min_y: 0,
+            max_y: 10,
+        };
+        let (tx, rx) = mpsc::channel();
+        let mut actor = PlayerChannelActor::new(Rc::new(RefCell::new(rx)), bounds);
+        let mut state = State::new();
+        state.player = Player::new(1, 1, 10, Orientation::Right);
+        state.crates = vec![Crate::new(1, 2, CrateColor::Red)];
+        state.hazards = vec![
+            Hazard::new(2, 1, HazardKind::ElectrifiedFloor),
+            Hazard::new(2, 2, HazardKind::Crater),
+        ];
+
+        // Driving onto an electrified floor drains energy.
+        tx.send(Action::Move(MoveDirection::Forward)).unwrap();
+        let state = actor.apply(state);
+        assert_eq!(state.player.pos, Pos::new(2, 1));
+        assert_eq!(state.player.energy, 10 - 1 - ELECTRIFIED_FLOOR_DRAIN);
+        assert_eq!(state.player.total_energy_used, 1 + ELECTRIFIED_FLOOR_DRAIN);
+
+        // Staying on it doesn't drain any more energy.
+        tx.send(Action::Wait).unwrap();
+        let state = actor.apply(state);
+        assert_eq!(state.player.energy, 10 - 1 - ELECTRIFIED_FLOOR_DRAIN);
+
+        // Dropping a crate into a crater turns it into a bridge.
+        tx.send(Action::Move(MoveDirection::Backward)).unwrap();
+        let state = actor.apply(state);
+        tx.send(Action::Turn(TurnDirection::Right)).unwrap();
+        let state = actor.apply(state);
+        tx.send(Action::PickUp).unwrap();
+        let state = actor.apply(state);
+        assert!(state.crates[0].held);
+        tx.send(Action::Move(MoveDirection::Forward)).unwrap();
+        let state = actor.apply(state);
+        tx.send(Action::Turn(TurnDirection::Left)).unwrap();
+        let state = actor.apply(state);
+        tx.send(Action::Drop).unwrap();
+        let state = actor.apply(state);
+        assert_eq!(state.crates[0].pos, Pos::new(2, 2));
+        assert_eq!(state.hazards[1].bridged_by, Some(0));
+
+        // The bridge can be driven over but not picked up again.
+        tx.send(Action::PickUp).unwrap();
+        let state = actor.apply(state);
+        assert_eq!(
+            state.player.err_message,
+            translate("player_err_nothing_to_pick_up", &[])
+        );
+        tx.send(Action::Move(MoveDirection::Forward)).unwrap();
+        let state = actor.apply(state);
+        assert_eq!(state.player.pos, Pos::new(2, 2));
+    }
 }
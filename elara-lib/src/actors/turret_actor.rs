@@ -0,0 +1,148 @@
+use std::any::Any;
+
+use crate::simulation::{Actor, Orientation, Pos, State};
+
+use super::{is_obstacle_at, is_outside_bounds, Bounds};
+
+/// An actor for stationary turrets which fire a beam in the direction they are
+/// facing every few steps. The beam is blocked by anything which counts as an
+/// obstacle (including crates and closed gates).
+pub struct TurretActor {
+    /// The index in State.turrets of the turret which will be controlled by
+    /// this actor.
+    index: usize,
+    bounds: Bounds,
+}
+
+impl TurretActor {
+    pub fn new(index: usize, bounds: Bounds) -> TurretActor {
+        TurretActor { index, bounds }
+    }
+
+    /// Returns the positions the beam would cover if the turret fired right
+    /// now. The beam stops at the first rover in the way.
+    fn beam_path(&self, state: &State) -> Vec<Pos> {
+        let turret = &state.turrets[self.index];
+        let (dx, dy) = match turret.facing {
+            Orientation::Up => (0, -1),
+            Orientation::Down => (0, 1),
+            Orientation::Left => (-1, 0),
+            Orientation::Right => (1, 0),
+        };
+        let mut path = vec![];
+        let mut pos = Pos::new(turret.pos.x + dx, turret.pos.y + dy);
+        while !is_outside_bounds(&self.bounds, &pos) && !is_obstacle_at(state, &pos) {
+            path.push(pos.clone());
+            if state.rovers().any(|rover| rover.pos == pos) {
+                break;
+            }
+            pos = Pos::new(pos.x + dx, pos.y + dy);
+        }
+        path
+    }
+}
+
+impl Actor for TurretActor {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn apply(&mut self, state: State) -> State {
+        let mut state = state.clone();
+
+        // The beam only lasts for a single step.
+        state.turrets[self.index].beam.clear();
+        state.turrets[self.index].hit_player = false;
+
+        let turret = &mut state.turrets[self.index];
+        turret.steps_until_fire = turret.steps_until_fire.saturating_sub(1);
+        if turret.steps_until_fire == 0 {
+            let beam = self.beam_path(&state);
+            let hit_player = beam
+                .last()
+                .is_some_and(|end| state.rovers().any(|rover| rover.pos == *end));
+            let turret = &mut state.turrets[self.index];
+            turret.hit_player = hit_player;
+            turret.beam = beam;
+            turret.steps_until_fire = turret.cadence;
+        }
+
+        state
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        simulation::{Crate, CrateColor, Obstacle, Player, Turret},
+        state_maker::StateMaker,
+    };
+
+    #[test]
+    fn fires_on_cadence() {
+        let mut actor = TurretActor::new(0, Bounds::new(0, 5, 0, 5));
+        let mut state = StateMaker::new()
+            .with_player(Player::new(5, 5, 10, Orientation::Up))
+            .with_turrets(vec![Turret::new(0, 2, Orientation::Right, 3, 2, 1)])
+            .build();
+        assert!(!state.turrets[0].is_warming_up());
+
+        // The turret should warm up for one step before firing.
+        state = actor.apply(state);
+        assert_eq!(state.turrets[0].steps_until_fire, 1);
+        assert!(state.turrets[0].is_warming_up());
+        assert!(state.turrets[0].beam.is_empty());
+
+        // Then it fires all the way to the edge of the map.
+        state = actor.apply(state);
+        assert_eq!(state.turrets[0].steps_until_fire, 3);
+        assert!(!state.turrets[0].is_warming_up());
+        assert_eq!(
+            state.turrets[0].beam,
+            vec![
+                Pos::new(1, 2),
+                Pos::new(2, 2),
+                Pos::new(3, 2),
+                Pos::new(4, 2),
+                Pos::new(5, 2),
+            ]
+        );
+        assert!(!state.turrets[0].hit_player);
+
+        // The beam disappears on the next step.
+        state = actor.apply(state);
+        assert_eq!(state.turrets[0].steps_until_fire, 2);
+        assert!(state.turrets[0].beam.is_empty());
+    }
+
+    #[test]
+    fn beam_is_blocked() {
+        let actor = TurretActor::new(0, Bounds::new(0, 5, 0, 5));
+        let mut state = StateMaker::new()
+            .with_player(Player::new(4, 2, 10, Orientation::Up))
+            .with_turrets(vec![Turret::new(0, 2, Orientation::Right, 3, 1, 1)])
+            .build();
+
+        // With nothing in the way, the beam hits the rover.
+        assert_eq!(
+            actor.beam_path(&state),
+            vec![
+                Pos::new(1, 2),
+                Pos::new(2, 2),
+                Pos::new(3, 2),
+                Pos::new(4, 2)
+            ]
+        );
+
+        // Obstacles and crates block the beam.
+        state.obstacles = vec![Obstacle::new(3, 2)];
+        assert_eq!(
+            actor.beam_path(&state),
+            vec![Pos::new(1, 2), Pos::new(2, 2)]
+        );
+        state.obstacles = vec![];
+        state.crates = vec![Crate::new(2, 2, CrateColor::Red)];
+        assert_eq!(actor.beam_path(&state), vec![Pos::new(1, 2)]);
+    }
+}
@@ -1,21 +1,46 @@
 use std::collections::HashMap;
+use std::fmt::Display;
 
 use regex::Regex;
 use rhai::EvalAltResult;
 
-use crate::constants::{
-    BAD_INPUT_UNEXPECTED_LINE_BREAK_IN_FUNCTION_CALL, BUILTIN_FUNCTIONS,
-    ERR_UNEXPECTED_LINE_BREAK_IN_FUNCTION_CALL, ERR_UNEXPECTED_SPACE_IN_FUNC_NAME,
-    ERR_UNEXPECTED_SPACE_IN_VAR_NAME,
-};
+use crate::constants::BUILTIN_FUNCTIONS;
+use crate::i18n::translate;
+use crate::suggestions::{closest_match, declared_variables, defined_functions};
 
 #[derive(Debug, PartialEq)]
 pub struct BetterError {
+    /// The ID of the message (see i18n), which can be used to identify the
+    /// kind of error. Errors which we don't have a better message for use
+    /// "err_other".
+    pub code: &'static str,
     pub message: String,
     pub line: Option<usize>,
     pub col: Option<usize>,
 }
 
+impl BetterError {
+    /// Returns an error with the message for the given code in the current
+    /// language.
+    pub fn new(
+        code: &'static str,
+        args: &[(&str, &dyn Display)],
+        line: Option<usize>,
+        col: Option<usize>,
+    ) -> Self {
+        BetterError {
+            code,
+            message: translate(code, args),
+            line,
+            col,
+        }
+    }
+
+    fn at(code: &'static str, args: &[(&str, &dyn Display)], pos: &rhai::Position) -> Self {
+        BetterError::new(code, args, pos.line(), pos.position())
+    }
+}
+
 impl std::fmt::Display for BetterError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.message)
@@ -79,7 +104,8 @@ pub fn search_prev_lines(script: &str, start_line: usize) -> usize {
 
 fn convert_func_not_found_err(
     avail_funcs: &[String],
-    disabled_funcs: &'static [&'static str],
+    disabled_funcs: &[&str],
+    script: &str,
     fn_sig: &str,
     pos: &rhai::Position,
 ) -> BetterError {
@@ -87,74 +113,61 @@ fn convert_func_not_found_err(
 
     // First check if the function is disabled.
     if disabled_funcs.contains(&fn_name.as_str()) {
-        return BetterError {
-            message: format!("Error: The {fn_name} function is disabled for this level"),
-            line: pos.line(),
-            col: pos.position(),
-        };
+        return BetterError::at("err_fn_disabled", &[("fn_name", &fn_name)], pos);
     }
 
     // Then check if the function has not yet been unlocked.
     if BUILTIN_FUNCTIONS.contains_key(fn_name.as_str()) && !avail_funcs.contains(&fn_name) {
-        return BetterError {
-            message: format!("Error: You haven't unlocked the {fn_name} function yet"),
-            line: pos.line(),
-            col: pos.position(),
-        };
+        return BetterError::at("err_fn_locked", &[("fn_name", &fn_name)], pos);
     }
 
     // If the function is unlocked and not disabled, give a better error message based on how
     // many arguments and of what type the function expects.
     if let Some(builtin_fn) = BUILTIN_FUNCTIONS.get(fn_name.as_str()) {
+        let fn_name = builtin_fn.name;
         return match builtin_fn.arg_types.len() {
-            0 => BetterError {
-                message: format!(
-                    "Error: The {} function should not have any inputs.",
-                    builtin_fn.name
-                ),
-                line: pos.line(),
-                col: pos.position(),
-            },
-            1 => {
-                if builtin_fn.arg_types[0] == "any" {
-                    BetterError {
-                        message: format!(
-                            "Error: The {} function should have one input of any type.",
-                            builtin_fn.name
-                        ),
-                        line: pos.line(),
-                        col: pos.position(),
-                    }
-                } else {
-                    BetterError {
-                        message: format!(
-                            "Error: The {} function should have one {} as an input.",
-                            builtin_fn.name, builtin_fn.arg_types[0]
-                        ),
-                        line: pos.line(),
-                        col: pos.position(),
-                    }
-                }
+            0 => BetterError::at("err_fn_no_inputs", &[("fn_name", &fn_name)], pos),
+            1 if builtin_fn.arg_types[0] == "any" => {
+                BetterError::at("err_fn_one_input_any", &[("fn_name", &fn_name)], pos)
             }
-            _ => BetterError {
-                message: format!(
-                    "Error: Wrong inputs for the {} function. Should have {} inputs ({}).",
-                    builtin_fn.name,
-                    builtin_fn.arg_types.len(),
-                    builtin_fn.arg_types.join(", ")
-                ),
-                line: pos.line(),
-                col: pos.position(),
-            },
+            1 => BetterError::at(
+                "err_fn_one_input",
+                &[
+                    ("fn_name", &fn_name),
+                    ("arg_type", &builtin_fn.arg_types[0]),
+                ],
+                pos,
+            ),
+            count => BetterError::at(
+                "err_fn_wrong_inputs",
+                &[
+                    ("fn_name", &fn_name),
+                    ("count", &count),
+                    ("arg_types", &builtin_fn.arg_types.join(", ")),
+                ],
+                pos,
+            ),
         };
     }
 
-    // If we reached here this is not a built-in function, just return a generic error.
-    BetterError {
-        message: format!("Error: There is no function named {fn_name} (maybe you made a typo?)"),
-        line: pos.line(),
-        col: pos.position(),
+    // If we reached here this is not a built-in function. Check if it looks like a typo of one of
+    // the available built-in functions or a function defined in the script.
+    let defined_funcs = defined_functions(script);
+    let candidates = avail_funcs
+        .iter()
+        .filter(|name| !disabled_funcs.contains(&name.as_str()))
+        .chain(defined_funcs.iter())
+        .map(|name| name.as_str());
+    if let Some(suggestion) = closest_match(&fn_name, candidates) {
+        return BetterError::at(
+            "err_fn_not_found_suggestion",
+            &[("fn_name", &fn_name), ("suggestion", &suggestion)],
+            pos,
+        );
     }
+
+    // Otherwise, just return a generic error.
+    BetterError::at("err_fn_not_found", &[("fn_name", &fn_name)], pos)
 }
 
 /// Returns true if the script contains an extra set of parentheses on the line
@@ -219,109 +232,83 @@ fn convert_missing_semicolon_error(script: &str, desc: &str, pos: &rhai::Positio
     // this kind of error because technically you can write `let foo;` and it will be valid code.
     // However, we can do better here by giving a more helpful error message.
     if is_space_in_variable_name(script, pos) {
-        return BetterError {
-            message: String::from(ERR_UNEXPECTED_SPACE_IN_VAR_NAME),
-            line: pos.line(),
-            col: pos.position(),
-        };
+        return BetterError::at("err_unexpected_space_in_var_name", &[], pos);
     }
 
     if desc == "at end of line" {
-        return BetterError {
-            message: String::from("Syntax Error: Missing semicolon ';' at end of line."),
-            line: pos.line(),
-            col: pos.position(),
-        };
+        return BetterError::at("err_missing_semicolon", &[], pos);
     }
     if desc == "to terminate this statement" {
         // Sometimes the Rhai parser spits out a missing semicolon error when the real culprit
         // is extra parentheses. (E.g. `turn_left()()` instead of `turn_left()`). Check if this
         // is the case.
         if is_extra_parentheses_set(script, pos) {
-            return BetterError {
-                message: String::from("Syntax Error: Unexpected extra parentheses '()'."),
-                line: pos.line(),
-                col: pos.position(),
-            };
+            return BetterError::at("err_extra_parentheses", &[], pos);
         } else if is_extra_closing_parentheses(script, pos) {
-            return BetterError {
-                message: String::from("Syntax Error: Unexpected extra closing parentheses ')'."),
-                line: pos.line(),
-                col: pos.position(),
-            };
+            return BetterError::at("err_extra_closing_parenthesis", &[], pos);
         }
 
         // Sometimes Rhai will give a missing semicolon error on the next line instead of
         // the line where the semicolon is actually missing. Check for this and then change
         // the line number if needed.
         let orig_line = pos.line().unwrap();
-        let mut message = String::from(
-            "Syntax Error: Missing semicolon ';' after function call or other statement.",
-        );
+        let mut code = "err_missing_semicolon_after_statement";
         let line = search_prev_lines(script, orig_line);
         if line != orig_line {
             // If we found a better line to put the error message on, we should
             // also change the message for the sake of clarity.
-            message = String::from("Syntax Error: Missing semicolon ';' at end of line.");
+            code = "err_missing_semicolon";
         }
 
-        return BetterError {
-            message,
-            line: Some(line),
-            col: pos.position(),
-        };
+        return BetterError::new(code, &[], Some(line), pos.position());
     }
 
     // In all other cases, just return a generic missing semicolon error.
-    BetterError {
-        message: String::from("Syntax Error: Missing semicolon ';' at end of line."),
-        line: pos.line(),
-        col: pos.position(),
-    }
+    BetterError::at("err_missing_semicolon", &[], pos)
 }
 
 lazy_static! {
-    /// A map of common variable name typos to helpful hints.
+    /// A map of common variable name typos to the IDs of helpful hints.
     static ref UNDEF_VARIABLE_HINTS: HashMap<&'static str, &'static str> = {
         let mut m: HashMap<&'static str, &'static str> = HashMap::new();
-        m.insert("Loop", "Did you mean loop with a lowercase 'l'?");
-        m.insert("Let", "Did you mean let with a lowercase 'l'?");
-        m.insert("If", "Did you mean if with a lowercase 'i'?");
-        m.insert("lovelace", "If you wanted this to be a string, maybe you forgot the quotation marks?");
-        m.insert("left", "If you wanted this to be a string, maybe you forgot the quotation marks?");
-        m.insert("right", "If you wanted this to be a string, maybe you forgot the quotation marks?");
-        m.insert("top", "If you wanted this to be a string, maybe you forgot the quotation marks?");
-        m.insert("middle", "If you wanted this to be a string, maybe you forgot the quotation marks?");
-        m.insert("bottom", "If you wanted this to be a string, maybe you forgot the quotation marks?");
+        m.insert("Loop", "hint_lowercase_loop");
+        m.insert("Let", "hint_lowercase_let");
+        m.insert("If", "hint_lowercase_if");
+        m.insert("lovelace", "hint_missing_quotes");
+        m.insert("left", "hint_missing_quotes");
+        m.insert("right", "hint_missing_quotes");
+        m.insert("top", "hint_missing_quotes");
+        m.insert("middle", "hint_missing_quotes");
+        m.insert("bottom", "hint_missing_quotes");
         m
     };
 }
 
-fn convert_var_not_found_error(var_name: &str, pos: &rhai::Position) -> BetterError {
-    if UNDEF_VARIABLE_HINTS.contains_key(var_name) {
-        BetterError {
-            message: format!(
-                r#"Error: Variable not found: {}. (Hint: {})"#,
-                var_name, UNDEF_VARIABLE_HINTS[var_name]
-            ),
-            line: pos.line(),
-            col: pos.position(),
-        }
+fn convert_var_not_found_error(script: &str, var_name: &str, pos: &rhai::Position) -> BetterError {
+    let declared_vars = declared_variables(script, *pos);
+    let hint = if let Some(hint) = UNDEF_VARIABLE_HINTS.get(var_name) {
+        Some(translate(hint, &[]))
     } else if BUILTIN_FUNCTIONS.contains_key(var_name) {
-        BetterError {
-            message: format!(
-                r#"Error: Variable not found: {}. (Hint: If you meant to call a function, make sure you include parentheses after the function name.)"#,
-                var_name,
-            ),
-            line: pos.line(),
-            col: pos.position(),
-        }
+        Some(translate("hint_missing_parentheses", &[]))
     } else {
-        BetterError {
-            message: format!("Error: Variable not found: {}", var_name),
-            line: pos.line(),
-            col: pos.position(),
-        }
+        None
+    };
+    if let Some(hint) = hint {
+        BetterError::at(
+            "err_var_not_found_hint",
+            &[("var_name", &var_name), ("hint", &hint)],
+            pos,
+        )
+    } else if let Some(suggestion) =
+        closest_match(var_name, declared_vars.iter().map(|name| name.as_str()))
+    {
+        BetterError::at(
+            "err_var_not_found_suggestion",
+            &[("var_name", &var_name), ("suggestion", &suggestion)],
+            pos,
+        )
+    } else {
+        BetterError::at("err_var_not_found", &[("var_name", &var_name)], pos)
     }
 }
 
@@ -376,30 +363,24 @@ fn convert_missing_comma_separate_args_error(
     // should have multiple arguments. This helps us narrow down the error message.
     if let Some(builtin_fn) = BUILTIN_FUNCTIONS.get(fn_name) {
         if builtin_fn.arg_types.len() <= 1 {
-            return BetterError {
-                message: format!(
-                    "Syntax Error: Missing a closing parenthesis ')' for the {} function.",
-                    fn_name
-                ),
-                line: Some(line),
-                col: Some(col),
-            };
+            return BetterError::new(
+                "err_missing_closing_parenthesis",
+                &[("fn_name", &fn_name)],
+                Some(line),
+                Some(col),
+            );
         }
     }
 
     // Otherwise, if the function is not a built-in function, or if it has more than
     // one argument, we can't narrow down the error message. The error message should
     // mention both possibilities.
-    BetterError {
-        message: format!(
-            "Syntax Error: Might be a missing closing parenthesis ')' for the \
-            {} function. Or if the function expects more than one input, you might \
-            be missing a comma ',' to separate them.",
-            fn_name
-        ),
-        line: Some(line),
-        col: Some(col),
-    }
+    BetterError::new(
+        "err_missing_closing_parenthesis_or_comma",
+        &[("fn_name", &fn_name)],
+        Some(line),
+        Some(col),
+    )
 }
 
 fn convert_missing_fn_params_error(
@@ -411,38 +392,23 @@ fn convert_missing_fn_params_error(
     // doesn't differentiate this kind of error and just expects parentheses instead of
     // a space. We can do better here by giving a more helpful error message.
     if is_space_in_func_name(&script, err_pos) {
-        BetterError {
-            message: String::from(ERR_UNEXPECTED_SPACE_IN_FUNC_NAME),
-            line: err_pos.line(),
-            col: err_pos.position(),
-        }
+        BetterError::at("err_unexpected_space_in_func_name", &[], err_pos)
     } else {
         // Otherwise, just wrap the original error.
-        BetterError {
-            message: format!(
-                "Syntax Error: Missing parentheses '()' after function name '{}'.",
-                fn_name
-            ),
-            line: err_pos.line(),
-            col: err_pos.position(),
-        }
+        BetterError::at("err_missing_fn_params", &[("fn_name", &fn_name)], err_pos)
     }
 }
 
 pub fn convert_err(
     avail_funcs: &[String],
-    disabled_funcs: &'static [&'static str],
+    disabled_funcs: &[&str],
     script: String,
     err: Box<EvalAltResult>,
 ) -> BetterError {
     log!("{:?}", err);
     match *err {
         EvalAltResult::ErrorTooManyOperations(ref pos) => {
-            return BetterError {
-                message: String::from("Error: Possible infinite loop detected."),
-                line: pos.line(),
-                col: pos.position(),
-            };
+            return BetterError::at("err_infinite_loop", &[], pos);
         }
         EvalAltResult::ErrorParsing(
             rhai::ParseErrorType::MissingToken(ref token, ref desc),
@@ -462,59 +428,168 @@ pub fn convert_err(
             return convert_missing_fn_params_error(script, fn_name, pos);
         }
         EvalAltResult::ErrorFunctionNotFound(ref fn_sig, ref pos) => {
-            return convert_func_not_found_err(avail_funcs, disabled_funcs, fn_sig, pos);
+            return convert_func_not_found_err(avail_funcs, disabled_funcs, &script, fn_sig, pos);
         }
         EvalAltResult::ErrorVariableNotFound(ref var_name, ref pos) => {
-            return convert_var_not_found_error(var_name, pos);
+            return convert_var_not_found_error(&script, var_name, pos);
         }
         EvalAltResult::ErrorParsing(
             rhai::ParseErrorType::VariableUndefined(ref var_name),
             ref pos,
         ) => {
-            return convert_var_not_found_error(var_name, pos);
+            return convert_var_not_found_error(&script, var_name, pos);
         }
         EvalAltResult::ErrorParsing(
             rhai::ParseErrorType::BadInput(rhai::LexError::UnterminatedString),
             ref pos,
         ) => {
-            return BetterError {
-                message: String::from("Error: String is missing a quotation mark at the end."),
-                line: pos.line(),
-                col: pos.position(),
-            };
-        }
-        EvalAltResult::ErrorParsing(
-            rhai::ParseErrorType::BadInput(rhai::LexError::UnexpectedInput(ref input)),
-            ref pos,
-        ) => {
-            // This is a special case of 'unexpected input' that we added in script_runner.rs.
-            // What this really means is that the user tried breaking up function arguments across multiple
-            // lines. Normally this would be allowed, but it is not allowed in Elara because it makes the
-            // semicolon checker too complicated.
-            if input == BAD_INPUT_UNEXPECTED_LINE_BREAK_IN_FUNCTION_CALL {
-                return BetterError {
-                    message: String::from(ERR_UNEXPECTED_LINE_BREAK_IN_FUNCTION_CALL),
-                    line: pos.line(),
-                    col: pos.position(),
-                };
-            }
+            return BetterError::at("err_unterminated_string", &[], pos);
         }
         _ => {}
     }
 
+    // We don't have a better message for this error, so we use the message from
+    // Rhai (which is only available in English).
     log!("Error: {}", err);
     let message = trim_message(err.to_string().as_str()).to_string();
     let line = err.position().line();
     let col = err.position().position();
-    BetterError { message, line, col }
+    BetterError {
+        code: "err_other",
+        message,
+        line,
+        col,
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::constants::{
-        ERR_UNEXPECTED_LINE_BREAK_IN_FUNCTION_CALL, ERR_UNEXPECTED_SPACE_IN_FUNC_NAME,
+/// The kind of error which caused a script to fail while it was running. The
+/// UI uses this to decide which help to show.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RuntimeErrorKind {
+    /// read_data was called when not next to a data point.
+    NoDataPoint,
+    /// press_button was called when not next to a button.
+    NoButton,
+    /// rover or select_rover was called with a rover number that doesn't exist.
+    NoRover,
+    /// An array or string was indexed past its end.
+    IndexOutOfBounds,
+    /// A value had the wrong type (e.g. a string was used instead of a number).
+    TypeMismatch,
+    /// Any other runtime error.
+    Other,
+}
+
+impl RuntimeErrorKind {
+    /// Returns the identifier for this kind of error which is used by the UI.
+    pub fn id(&self) -> &'static str {
+        match self {
+            RuntimeErrorKind::NoDataPoint => "err_no_data_point",
+            RuntimeErrorKind::NoButton => "err_no_button",
+            RuntimeErrorKind::NoRover => "err_no_rover",
+            RuntimeErrorKind::IndexOutOfBounds => "err_index_out_of_bounds",
+            RuntimeErrorKind::TypeMismatch => "err_type_mismatch",
+            RuntimeErrorKind::Other => "err_other",
+        }
+    }
+}
+
+/// An error which caused a script to fail while it was running.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeError {
+    pub kind: RuntimeErrorKind,
+    pub message: String,
+    pub line: Option<usize>,
+    pub col: Option<usize>,
+}
+
+/// Returns the error which actually caused the given error. Errors which
+/// happen inside a function are wrapped in an error for the function call, but
+/// we want to point to the line inside the function where things went wrong.
+pub fn root_cause(err: &EvalAltResult) -> &EvalAltResult {
+    match err {
+        EvalAltResult::ErrorInFunctionCall(_, _, inner, _) => root_cause(inner),
+        _ => err,
+    }
+}
+
+/// Returns a friendlier name for a Rhai type.
+fn friendly_type_name(type_name: &str) -> String {
+    match type_name {
+        "i64" | "i32" | "f64" | "f32" => translate("type_number", &[]),
+        "string" | "ImmutableString" | "char" => translate("type_string", &[]),
+        "bool" => translate("type_boolean", &[]),
+        "()" => translate("type_empty", &[]),
+        other => other.to_string(),
+    }
+}
+
+/// Converts an error which happened while running a script into a
+/// RuntimeError. Returns None if the error should be treated as a regular
+/// error instead (e.g. a function that doesn't exist).
+pub fn convert_runtime_err(err: &EvalAltResult) -> Option<RuntimeError> {
+    let err = root_cause(err);
+    let (kind, message) = match err {
+        // Our own errors are thrown as a RuntimeErrorKind, which we replace
+        // with the message in the current language.
+        EvalAltResult::ErrorRuntime(ref value, _) if value.is::<RuntimeErrorKind>() => {
+            let kind = value.clone_cast::<RuntimeErrorKind>();
+            let message = translate(kind.id(), &[]);
+            (kind, translate("err_runtime", &[("message", &message)]))
+        }
+        EvalAltResult::ErrorRuntime(ref value, _) => (
+            RuntimeErrorKind::Other,
+            translate("err_runtime", &[("message", &value.to_string())]),
+        ),
+        EvalAltResult::ErrorArrayBounds(len, index, _) => (
+            RuntimeErrorKind::IndexOutOfBounds,
+            if *len == 0 {
+                translate("err_index_out_of_bounds_empty", &[("index", index)])
+            } else {
+                translate(
+                    "err_index_out_of_bounds",
+                    &[
+                        ("index", index as &dyn Display),
+                        ("len", len),
+                        ("last", &(len - 1)),
+                    ],
+                )
+            },
+        ),
+        EvalAltResult::ErrorStringBounds(len, index, _) => (
+            RuntimeErrorKind::IndexOutOfBounds,
+            translate(
+                "err_string_index_out_of_bounds",
+                &[("index", index as &dyn Display), ("len", len)],
+            ),
+        ),
+        EvalAltResult::ErrorMismatchDataType(ref expected, ref actual, _)
+        | EvalAltResult::ErrorMismatchOutputType(ref expected, ref actual, _) => (
+            RuntimeErrorKind::TypeMismatch,
+            translate(
+                "err_type_mismatch",
+                &[
+                    ("expected", &friendly_type_name(expected)),
+                    ("actual", &friendly_type_name(actual)),
+                ],
+            ),
+        ),
+        EvalAltResult::ErrorArithmetic(ref message, _) => (
+            RuntimeErrorKind::Other,
+            translate("err_runtime", &[("message", message)]),
+        ),
+        _ => return None,
     };
+    Some(RuntimeError {
+        kind,
+        message,
+        line: err.position().line(),
+        col: err.position().position(),
+    })
+}
 
+#[cfg(test)]
+mod tests {
     use super::*;
 
     lazy_static! {
@@ -563,6 +638,7 @@ mod tests {
         assert_eq!(
             err,
             BetterError {
+                code: "err_missing_semicolon",
                 message: String::from("Syntax Error: Missing semicolon ';' at end of line."),
                 line: Some(4),
                 col: Some(21),
@@ -591,6 +667,7 @@ mod tests {
         assert_eq!(
             err,
             BetterError {
+                code: "err_missing_semicolon",
                 message: String::from("Syntax Error: Missing semicolon ';' at end of line."),
                 line: Some(3),
                 col: Some(13),
@@ -614,6 +691,7 @@ mod tests {
         assert_eq!(
             err,
             BetterError {
+                code: "err_missing_semicolon_after_statement",
                 message: String::from(
                     "Syntax Error: Missing semicolon ';' after function call or other statement."
                 ),
@@ -642,6 +720,7 @@ mod tests {
         assert_eq!(
             err,
             BetterError {
+                code: "err_missing_semicolon_after_statement",
                 message: String::from(
                     "Syntax Error: Missing semicolon ';' after function call or other statement."
                 ),
@@ -668,6 +747,7 @@ mod tests {
         assert_eq!(
             err,
             BetterError {
+                code: "err_missing_semicolon_after_statement",
                 message: String::from(
                     "Syntax Error: Missing semicolon ';' after function call or other statement."
                 ),
@@ -694,6 +774,7 @@ mod tests {
         assert_eq!(
             err,
             BetterError {
+                code: "err_missing_semicolon_after_statement",
                 message: String::from(
                     "Syntax Error: Missing semicolon ';' after function call or other statement."
                 ),
@@ -722,6 +803,7 @@ mod tests {
         assert_eq!(
             err,
             BetterError {
+                code: "err_missing_semicolon",
                 message: String::from("Syntax Error: Missing semicolon ';' at end of line."),
                 line: Some(1),
                 col: Some(1),
@@ -741,6 +823,7 @@ mod tests {
         assert_eq!(
             err,
             BetterError {
+                code: "err_fn_locked",
                 message: String::from("Error: You haven't unlocked the press_button function yet"),
                 line: Some(1),
                 col: Some(1),
@@ -762,6 +845,7 @@ mod tests {
         assert_eq!(
             err,
             BetterError {
+                code: "err_fn_disabled",
                 message: String::from(
                     "Error: The move_forward function is disabled for this level"
                 ),
@@ -780,6 +864,7 @@ mod tests {
         assert_eq!(
             err,
             BetterError {
+                code: "err_fn_one_input",
                 message: String::from(
                     "Error: The move_forward function should have one number as an input."
                 ),
@@ -798,6 +883,7 @@ mod tests {
         assert_eq!(
             err,
             BetterError {
+                code: "err_fn_one_input",
                 message: String::from(
                     "Error: The move_forward function should have one number as an input."
                 ),
@@ -816,6 +902,7 @@ mod tests {
         assert_eq!(
             err,
             BetterError {
+                code: "err_fn_not_found",
                 message: String::from(
                     "Error: There is no function named move_diagonally (maybe you made a typo?)"
                 ),
@@ -823,30 +910,78 @@ mod tests {
                 col: Some(1),
             }
         );
+
+        // Typo of an available built-in function.
+        let script = String::from(r"move_foward(1);");
+        let err = EvalAltResult::ErrorFunctionNotFound(
+            String::from("move_foward (i64)"),
+            rhai::Position::new(1, 1),
+        );
+        let err = convert_err(&AVAIL_FUNCS, &NO_DISABLED_FUNCS, script, Box::new(err));
+        assert_eq!(
+            err.message,
+            "Error: There is no function named move_foward. (Hint: Did you mean move_forward?)"
+        );
+
+        // Typo of a function defined in the script.
+        let script = String::from("fn turn_around() {}\ntrun_around();");
+        let err = EvalAltResult::ErrorFunctionNotFound(
+            String::from("trun_around ()"),
+            rhai::Position::new(2, 1),
+        );
+        let err = convert_err(&AVAIL_FUNCS, &NO_DISABLED_FUNCS, script, Box::new(err));
+        assert_eq!(
+            err.message,
+            "Error: There is no function named trun_around. (Hint: Did you mean turn_around?)"
+        );
+
+        // Disabled functions should not be suggested.
+        let script = String::from(r"move_foward(1);");
+        let err = EvalAltResult::ErrorFunctionNotFound(
+            String::from("move_foward (i64)"),
+            rhai::Position::new(1, 1),
+        );
+        let err = convert_err(
+            &AVAIL_FUNCS_IMPAIRED_MOVEMENT,
+            &DISABLED_FUNCS_IMPAIRED_MOVEMENT,
+            script,
+            Box::new(err),
+        );
+        assert_eq!(
+            err.message,
+            "Error: There is no function named move_foward (maybe you made a typo?)"
+        );
     }
 
     #[test]
-    fn test_convert_err_line_break_in_function_call() {
-        let script = String::from(
-            r"move_forward(
-                1
-            );",
-        );
+    fn test_convert_var_not_found_err() {
+        // Typo of a variable declared earlier in the script.
+        let script = String::from("let steps = 3;\nmove_forward(stesp);");
         let err = EvalAltResult::ErrorParsing(
-            rhai::ParseErrorType::BadInput(rhai::LexError::UnexpectedInput(
-                BAD_INPUT_UNEXPECTED_LINE_BREAK_IN_FUNCTION_CALL.to_string(),
-            )),
-            rhai::Position::new(1, 13),
+            rhai::ParseErrorType::VariableUndefined(String::from("stesp")),
+            rhai::Position::new(2, 14),
         );
         let err = convert_err(&AVAIL_FUNCS, &NO_DISABLED_FUNCS, script, Box::new(err));
         assert_eq!(
             err,
             BetterError {
-                message: String::from(ERR_UNEXPECTED_LINE_BREAK_IN_FUNCTION_CALL),
-                line: Some(1),
-                col: Some(13),
+                code: "err_var_not_found_suggestion",
+                message: String::from(
+                    "Error: Variable not found: stesp. (Hint: Did you mean steps?)"
+                ),
+                line: Some(2),
+                col: Some(14),
             }
         );
+
+        // Variables declared after the error are not suggested.
+        let script = String::from("move_forward(stesp);\nlet steps = 3;");
+        let err = EvalAltResult::ErrorParsing(
+            rhai::ParseErrorType::VariableUndefined(String::from("stesp")),
+            rhai::Position::new(1, 14),
+        );
+        let err = convert_err(&AVAIL_FUNCS, &NO_DISABLED_FUNCS, script, Box::new(err));
+        assert_eq!(err.message, "Error: Variable not found: stesp");
     }
 
     #[test]
@@ -863,7 +998,8 @@ mod tests {
         assert_eq!(
             err,
             BetterError {
-                message: String::from(ERR_UNEXPECTED_SPACE_IN_VAR_NAME),
+                code: "err_unexpected_space_in_var_name",
+                message: translate("err_unexpected_space_in_var_name", &[]),
                 line: Some(1),
                 col: Some(6),
             }
@@ -881,7 +1017,8 @@ mod tests {
         assert_eq!(
             err,
             BetterError {
-                message: String::from(ERR_UNEXPECTED_SPACE_IN_FUNC_NAME),
+                code: "err_unexpected_space_in_func_name",
+                message: translate("err_unexpected_space_in_func_name", &[]),
                 line: Some(1),
                 col: Some(7),
             }
@@ -893,4 +1030,35 @@ mod tests {
         assert_eq!(fn_name_from_sig("move_down (i64, i64)"), "move_down");
         assert_eq!(fn_name_from_sig("move_down ()"), "move_down");
     }
+
+    #[test]
+    fn test_convert_runtime_err_kind() {
+        let err = EvalAltResult::ErrorRuntime(
+            rhai::Dynamic::from(RuntimeErrorKind::NoRover),
+            rhai::Position::new(2, 5),
+        );
+        assert_eq!(
+            convert_runtime_err(&err),
+            Some(RuntimeError {
+                kind: RuntimeErrorKind::NoRover,
+                message: translate(
+                    "err_runtime",
+                    &[("message", &translate("err_no_rover", &[]))]
+                ),
+                line: Some(2),
+                col: Some(5),
+            })
+        );
+
+        // Only errors thrown by our built-in functions have a specific kind,
+        // even if a script throws the same message.
+        let err = EvalAltResult::ErrorRuntime(
+            translate("err_no_rover", &[]).into(),
+            rhai::Position::new(2, 5),
+        );
+        assert_eq!(
+            convert_runtime_err(&err).unwrap().kind,
+            RuntimeErrorKind::Other
+        );
+    }
 }
@@ -0,0 +1,520 @@
+use std::collections::HashSet;
+
+use crate::i18n::{translate, translate_or};
+use crate::levels::Level;
+use crate::script_query::ScriptQuery;
+use crate::script_runner::ScriptStats;
+use crate::simulation::{PlayerAnimState, State, TermData};
+
+/// A kind of language construct which a challenge can require or forbid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Construct {
+    /// Any kind of loop (loop, for, while, or do).
+    Loop,
+    /// A while loop specifically.
+    WhileLoop,
+    /// Defining a new function with fn.
+    FunctionDefinition,
+    /// An if statement.
+    Conditional,
+    /// Declaring a variable with let.
+    Variable,
+    /// A function which calls itself (directly or indirectly).
+    Recursion,
+}
+
+impl Construct {
+    /// Returns the number of times the construct is used in the script.
+    fn count(&self, query: &ScriptQuery) -> usize {
+        match self {
+            Construct::Loop => query.num_loops(),
+            Construct::WhileLoop => query.num_while_loops(),
+            Construct::FunctionDefinition => query.num_function_definitions(),
+            Construct::Conditional => query.num_conditionals(),
+            Construct::Variable => query.num_variable_declarations(),
+            Construct::Recursion => query.recursive_functions().len(),
+        }
+    }
+
+    /// Returns the message ID for the label used when showing progress.
+    fn label_id(&self) -> &'static str {
+        match self {
+            Construct::Loop => "challenge_label_loops",
+            Construct::WhileLoop => "challenge_label_while_loops",
+            Construct::FunctionDefinition => "challenge_label_function_definitions",
+            Construct::Conditional => "challenge_label_conditionals",
+            Construct::Variable => "challenge_label_variables",
+            Construct::Recursion => "challenge_label_recursive_functions",
+        }
+    }
+}
+
+/// A single condition which must be met in order to pass a challenge.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChallengeRule {
+    /// Complete the objective in the given number of steps or fewer.
+    MaxSteps(u32),
+    /// Use the given amount of energy or less.
+    MaxEnergy(u32),
+    /// Have a (compacted) code length of the given number of characters or
+    /// less.
+    MaxCodeLen(usize),
+    /// Use the given number of statements or fewer.
+    MaxStatements(usize),
+    /// Use the given number of tokens or fewer. Unlike the code length, this
+    /// doesn't depend on the length of variable names.
+    MaxTokens(usize),
+    /// Don't call the given built-in function.
+    ForbidFunction(&'static str),
+    /// Define a new function with the given name.
+    DefineFunction(&'static str),
+    /// Use the given construct at least once.
+    RequireConstruct(Construct),
+    /// Don't use the given construct.
+    ForbidConstruct(Construct),
+    /// Don't use any telepads.
+    NoTeleporting,
+    /// Use the say function to repeat the message on every data point.
+    SayAllMessages,
+    /// A condition which can't be expressed by any of the other rules. The
+    /// description is shown to the player and the rule is evaluated by
+    /// Level::check_challenge. Like other level text, id should be of the form
+    /// level.<short_name>.challenge and description is the English text.
+    Custom {
+        id: &'static str,
+        description: &'static str,
+    },
+}
+
+impl ChallengeRule {
+    /// Returns a description of the rule which is meant to be used as part of
+    /// a sentence (i.e. it is not capitalized and has no punctuation).
+    pub fn description(&self) -> String {
+        match self {
+            ChallengeRule::MaxSteps(steps) => translate("challenge_max_steps", &[("steps", steps)]),
+            ChallengeRule::MaxEnergy(energy) => {
+                translate("challenge_max_energy", &[("energy", energy)])
+            }
+            ChallengeRule::MaxCodeLen(len) => translate("challenge_max_code_len", &[("len", len)]),
+            ChallengeRule::MaxStatements(statements) => {
+                translate("challenge_max_statements", &[("statements", statements)])
+            }
+            ChallengeRule::MaxTokens(tokens) => {
+                translate("challenge_max_tokens", &[("tokens", tokens)])
+            }
+            ChallengeRule::ForbidFunction(name) => {
+                translate("challenge_forbid_function", &[("fn_name", name)])
+            }
+            ChallengeRule::DefineFunction(name) => {
+                translate("challenge_define_function", &[("fn_name", name)])
+            }
+            ChallengeRule::RequireConstruct(construct) => translate(
+                match construct {
+                    Construct::Loop => "challenge_require_loop",
+                    Construct::WhileLoop => "challenge_require_while_loop",
+                    Construct::FunctionDefinition => "challenge_require_function_definition",
+                    Construct::Conditional => "challenge_require_conditional",
+                    Construct::Variable => "challenge_require_variable",
+                    Construct::Recursion => "challenge_require_recursion",
+                },
+                &[],
+            ),
+            ChallengeRule::ForbidConstruct(construct) => translate(
+                match construct {
+                    Construct::Loop => "challenge_forbid_loop",
+                    Construct::WhileLoop => "challenge_forbid_while_loop",
+                    Construct::FunctionDefinition => "challenge_forbid_function_definition",
+                    Construct::Conditional => "challenge_forbid_conditional",
+                    Construct::Variable => "challenge_forbid_variable",
+                    Construct::Recursion => "challenge_forbid_recursion",
+                },
+                &[],
+            ),
+            ChallengeRule::NoTeleporting => translate("challenge_no_teleporting", &[]),
+            ChallengeRule::SayAllMessages => translate("challenge_say_all_messages", &[]),
+            ChallengeRule::Custom { id, description } => translate_or(id, description),
+        }
+    }
+}
+
+/// How close the player is to passing a single challenge rule. For example,
+/// "energy 15/12" means the player used 15 energy but the limit is 12.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChallengeProgress {
+    pub description: String,
+    /// What is being measured (e.g. "energy" or "steps").
+    pub label: String,
+    /// The value achieved by the player.
+    pub current: usize,
+    /// The limit for the rule (or for rules which require something, the
+    /// minimum).
+    pub target: usize,
+    pub passed: bool,
+}
+
+/// An optional, harder goal for a level. A challenge consists of one or more
+/// rules, all of which must be passed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Challenge {
+    pub rules: Vec<ChallengeRule>,
+}
+
+impl Challenge {
+    pub fn new(rules: Vec<ChallengeRule>) -> Challenge {
+        Challenge { rules }
+    }
+
+    /// Returns a description of the challenge which can be shown to the
+    /// player, e.g. "Use 12 energy or less _and_ have a code length of 190
+    /// characters or less."
+    pub fn description(&self) -> String {
+        let clauses: Vec<String> = self.rules.iter().map(|rule| rule.description()).collect();
+        let sentence = match clauses.split_last() {
+            None => String::new(),
+            Some((last, [])) => last.clone(),
+            Some((last, rest)) => translate(
+                "challenge_and",
+                &[("rest", &rest.join(", ")), ("last", last)],
+            ),
+        };
+        let mut chars = sentence.chars();
+        match chars.next() {
+            None => String::new(),
+            Some(first) => format!("{}{}.", first.to_uppercase(), chars.as_str()),
+        }
+    }
+
+    /// Evaluates each rule of the challenge. Custom rules are evaluated by the
+    /// given level.
+    pub fn progress(
+        &self,
+        level: &dyn Level,
+        states: &[State],
+        script: &str,
+        stats: &ScriptStats,
+    ) -> Vec<ChallengeProgress> {
+        // None if the script could not be compiled (e.g. due to a syntax error).
+        let query = ScriptQuery::new(script);
+        self.rules
+            .iter()
+            .map(|rule| {
+                let (label, current, target, passed) = match rule {
+                    ChallengeRule::MaxSteps(steps) => (
+                        translate("challenge_label_steps", &[]),
+                        stats.time_taken as usize,
+                        *steps as usize,
+                        stats.time_taken <= *steps,
+                    ),
+                    ChallengeRule::MaxEnergy(energy) => (
+                        translate("challenge_label_energy", &[]),
+                        stats.energy_used as usize,
+                        *energy as usize,
+                        stats.energy_used <= *energy,
+                    ),
+                    ChallengeRule::MaxCodeLen(len) => (
+                        translate("challenge_label_code_len", &[]),
+                        stats.code_len,
+                        *len,
+                        stats.code_len <= *len,
+                    ),
+                    ChallengeRule::MaxStatements(statements) => (
+                        translate("challenge_label_statements", &[]),
+                        stats.metrics.statements,
+                        *statements,
+                        stats.metrics.statements <= *statements,
+                    ),
+                    ChallengeRule::MaxTokens(tokens) => (
+                        translate("challenge_label_tokens", &[]),
+                        stats.metrics.tokens,
+                        *tokens,
+                        stats.metrics.tokens <= *tokens,
+                    ),
+                    ChallengeRule::ForbidFunction(name) => {
+                        let uses = query.as_ref().map(|query| query.num_calls(name));
+                        (
+                            translate("challenge_label_uses_of_function", &[("fn_name", name)]),
+                            uses.unwrap_or(0),
+                            0,
+                            uses == Some(0),
+                        )
+                    }
+                    ChallengeRule::DefineFunction(name) => {
+                        let defined = query
+                            .as_ref()
+                            .is_some_and(|query| query.defines_function(name));
+                        (
+                            translate(
+                                "challenge_label_definitions_of_function",
+                                &[("fn_name", name)],
+                            ),
+                            defined as usize,
+                            1,
+                            defined,
+                        )
+                    }
+                    ChallengeRule::RequireConstruct(construct) => {
+                        let uses = query.as_ref().map(|query| construct.count(query));
+                        (
+                            translate(construct.label_id(), &[]),
+                            uses.unwrap_or(0),
+                            1,
+                            uses.is_some_and(|uses| uses >= 1),
+                        )
+                    }
+                    ChallengeRule::ForbidConstruct(construct) => {
+                        let uses = query.as_ref().map(|query| construct.count(query));
+                        (
+                            translate(construct.label_id(), &[]),
+                            uses.unwrap_or(0),
+                            0,
+                            uses == Some(0),
+                        )
+                    }
+                    ChallengeRule::NoTeleporting => {
+                        let teleports = count_teleports(states);
+                        (
+                            translate("challenge_label_teleports", &[]),
+                            teleports,
+                            0,
+                            teleports == 0,
+                        )
+                    }
+                    ChallengeRule::SayAllMessages => {
+                        let (said, total) = count_messages_said(states);
+                        (
+                            translate("challenge_label_messages", &[]),
+                            said,
+                            total,
+                            said == total,
+                        )
+                    }
+                    ChallengeRule::Custom { .. } => {
+                        let passed = level.check_challenge(states, script, stats);
+                        (
+                            translate("challenge_label_completed", &[]),
+                            passed as usize,
+                            1,
+                            passed,
+                        )
+                    }
+                };
+                ChallengeProgress {
+                    description: rule.description(),
+                    label,
+                    current,
+                    target,
+                    passed,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns the number of times any of the rovers started teleporting.
+fn count_teleports(states: &[State]) -> usize {
+    states
+        .iter()
+        .map(|state| {
+            state
+                .rovers()
+                .filter(|rover| matches!(rover.anim_state, PlayerAnimState::Teleporting(_)))
+                .count()
+        })
+        .sum()
+}
+
+/// Returns the number of distinct data point messages which were said by the
+/// main rover, along with the total number of data point messages.
+fn count_messages_said(states: &[State]) -> (usize, usize) {
+    let messages: HashSet<&str> = states
+        .first()
+        .map(|state| {
+            state
+                .data_points
+                .iter()
+                .filter_map(|data_point| match &data_point.data {
+                    TermData::String(message) => Some(message.as_str()),
+                    TermData::Array(_) => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let said: HashSet<&str> = states
+        .iter()
+        .map(|state| state.player.message.as_str())
+        .filter(|message| messages.contains(message))
+        .collect();
+    (said.len(), messages.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::SANDBOX_LEVEL;
+
+    fn stats(code_len: usize, energy_used: u32, time_taken: u32) -> ScriptStats {
+        ScriptStats {
+            code_len,
+            energy_used,
+            time_taken,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn description() {
+        let challenge = Challenge::new(vec![ChallengeRule::MaxSteps(17)]);
+        assert_eq!(
+            challenge.description(),
+            "Complete the objective in 17 steps or fewer."
+        );
+
+        let challenge = Challenge::new(vec![
+            ChallengeRule::MaxEnergy(12),
+            ChallengeRule::MaxCodeLen(190),
+        ]);
+        assert_eq!(
+            challenge.description(),
+            "Use 12 energy or less _and_ have a code length of 190 characters or less."
+        );
+
+        let challenge = Challenge::new(vec![
+            ChallengeRule::MaxCodeLen(84),
+            ChallengeRule::ForbidFunction("read_data"),
+            ChallengeRule::ForbidConstruct(Construct::Loop),
+        ]);
+        assert_eq!(
+            challenge.description(),
+            "Have a code length of 84 characters or less, don't use the `read_data` function _and_ don't use a loop."
+        );
+    }
+
+    #[test]
+    fn description_translated() {
+        let challenge = Challenge::new(vec![
+            ChallengeRule::MaxSteps(17),
+            ChallengeRule::Custom {
+                id: "level.stealth_part_one.challenge",
+                description: "reach the goal without any of the rovers spotting you",
+            },
+        ]);
+        let mut catalog = std::collections::HashMap::new();
+        catalog.insert(
+            String::from("challenge_max_steps"),
+            String::from("termine l'objectif en {steps} étapes ou moins"),
+        );
+        catalog.insert(
+            String::from("challenge_and"),
+            String::from("{rest} _et_ {last}"),
+        );
+        crate::i18n::set_catalog(catalog.clone());
+        // Custom rules fall back to the English text defined by the level.
+        assert_eq!(
+            challenge.description(),
+            "Termine l'objectif en 17 étapes ou moins _et_ reach the goal without any of the rovers spotting you."
+        );
+        catalog.insert(
+            String::from("level.stealth_part_one.challenge"),
+            String::from("atteins le but sans être repéré"),
+        );
+        crate::i18n::set_catalog(catalog);
+        let description = challenge.description();
+        crate::i18n::set_catalog(std::collections::HashMap::new());
+        assert_eq!(
+            description,
+            "Termine l'objectif en 17 étapes ou moins _et_ atteins le but sans être repéré."
+        );
+    }
+
+    #[test]
+    fn progress() {
+        let challenge = Challenge::new(vec![
+            ChallengeRule::MaxEnergy(12),
+            ChallengeRule::MaxSteps(20),
+            ChallengeRule::ForbidFunction("read_data"),
+            ChallengeRule::RequireConstruct(Construct::Loop),
+            ChallengeRule::ForbidConstruct(Construct::FunctionDefinition),
+        ]);
+        let script = r"
+            // read_data() in a comment doesn't count.
+            loop {
+                move_forward(1);
+            }
+        ";
+        let states = vec![State::new()];
+        let progress = challenge.progress(SANDBOX_LEVEL, &states, script, &stats(30, 15, 20));
+        assert_eq!(
+            progress
+                .iter()
+                .map(|p| (p.label.as_str(), p.current, p.target, p.passed))
+                .collect::<Vec<_>>(),
+            vec![
+                ("energy", 15, 12, false),
+                ("steps", 20, 20, true),
+                ("uses of `read_data`", 0, 0, true),
+                ("loops", 1, 1, true),
+                ("function definitions", 0, 0, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn script_rules() {
+        let challenge = Challenge::new(vec![
+            ChallengeRule::ForbidFunction("read_data"),
+            ChallengeRule::DefineFunction("three_lefts"),
+            ChallengeRule::RequireConstruct(Construct::WhileLoop),
+            ChallengeRule::ForbidConstruct(Construct::Recursion),
+        ]);
+        let states = vec![State::new()];
+        let script = r"
+            fn three_lefts() {
+                turn_left();
+                turn_left();
+                three_lefts();
+            }
+            let read_data_count = 0;
+            loop {
+                three_lefts();
+            }
+        ";
+        let progress = challenge.progress(SANDBOX_LEVEL, &states, script, &stats(0, 0, 0));
+        assert_eq!(
+            progress
+                .iter()
+                .map(|p| (p.label.as_str(), p.current, p.target, p.passed))
+                .collect::<Vec<_>>(),
+            vec![
+                ("uses of `read_data`", 0, 0, true),
+                ("definitions of `three_lefts`", 1, 1, true),
+                ("while loops", 0, 1, false),
+                ("recursive functions", 1, 0, false),
+            ]
+        );
+
+        // Scripts which can't be compiled don't pass any of the rules.
+        let progress = challenge.progress(SANDBOX_LEVEL, &states, "loop {", &stats(0, 0, 0));
+        assert!(progress.iter().all(|p| !p.passed));
+    }
+
+    #[test]
+    fn say_all_messages() {
+        let mut state = State::new();
+        state.data_points = vec![
+            crate::simulation::DataPoint::new(0, 1, "hello".into()),
+            crate::simulation::DataPoint::new(1, 1, "world".into()),
+        ];
+        let mut states = vec![state.clone(), state.clone(), state];
+        states[1].player.message = String::from("hello");
+        states[2].player.message = String::from("hello");
+
+        let challenge = Challenge::new(vec![ChallengeRule::SayAllMessages]);
+        let progress = challenge.progress(SANDBOX_LEVEL, &states, "", &stats(0, 0, 2));
+        assert_eq!((progress[0].current, progress[0].target), (1, 2));
+        assert!(!progress[0].passed);
+
+        states[2].player.message = String::from("world");
+        let progress = challenge.progress(SANDBOX_LEVEL, &states, "", &stats(0, 0, 2));
+        assert!(progress[0].passed);
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+
+use crate::constants::BUILTIN_FUNCTIONS;
+use crate::diagnostics::{raw_engine, tokenize};
+use crate::script_query::ScriptQuery;
+
+/// Structural metrics for a script, computed from its AST. Unlike the code
+/// length, these can't be improved by shortening variable names or removing
+/// whitespace.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CodeMetrics {
+    /// The number of statements, including statements inside blocks and
+    /// function bodies.
+    pub statements: usize,
+    /// The number of loops (loop, while, do, and for).
+    pub loops: usize,
+    /// The number of conditionals (if and switch).
+    pub conditionals: usize,
+    /// The number of user-defined functions.
+    pub user_functions: usize,
+    /// The maximum depth of nested blocks (loops, conditionals, and function
+    /// bodies). A script with only top-level statements has a depth of 0.
+    pub max_nesting_depth: usize,
+    /// The number of times each built-in function is called in the code (not
+    /// the number of times it was called while the script was running).
+    pub builtin_calls: BTreeMap<String, usize>,
+    /// The number of tokens (identifiers, literals, operators, etc.) in the
+    /// script, not counting comments.
+    pub tokens: usize,
+}
+
+impl CodeMetrics {
+    /// Computes metrics for the given script. Returns the default (empty)
+    /// metrics if the script can't be compiled.
+    pub fn from_script(script: &str) -> CodeMetrics {
+        let query = match ScriptQuery::new(script) {
+            Some(query) => query,
+            None => return CodeMetrics::default(),
+        };
+        CodeMetrics {
+            statements: query.num_statements(),
+            loops: query.num_loops(),
+            conditionals: query.num_conditionals(),
+            user_functions: query.num_function_definitions(),
+            max_nesting_depth: query.max_nesting_depth(),
+            builtin_calls: query
+                .function_calls()
+                .into_iter()
+                .filter(|(name, _)| BUILTIN_FUNCTIONS.contains_key(name.as_str()))
+                .collect(),
+            tokens: tokenize(&raw_engine(), script).len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic_metrics() {
+        let metrics = CodeMetrics::from_script(
+            r"
+            // Comments don't count.
+            move_forward(1);
+            let x = 2;
+            move_forward(x);
+            ",
+        );
+        assert_eq!(metrics.statements, 3);
+        assert_eq!(metrics.loops, 0);
+        assert_eq!(metrics.conditionals, 0);
+        assert_eq!(metrics.user_functions, 0);
+        assert_eq!(metrics.max_nesting_depth, 0);
+        assert_eq!(
+            metrics.builtin_calls,
+            BTreeMap::from([(String::from("move_forward"), 2)])
+        );
+        // move_forward ( 1 ) ; let x = 2 ; move_forward ( x ) ;
+        assert_eq!(metrics.tokens, 15);
+
+        // Longer variable names don't change the number of tokens.
+        let longer = CodeMetrics::from_script(
+            r"
+            move_forward(1);
+            let distance_to_move = 2;
+            move_forward(distance_to_move);
+            ",
+        );
+        assert_eq!(longer.tokens, metrics.tokens);
+    }
+
+    #[test]
+    fn nested_metrics() {
+        let metrics = CodeMetrics::from_script(
+            r#"
+            fn spin(n) {
+                for i in 0..n {
+                    if i % 2 == 0 {
+                        turn_left();
+                    } else {
+                        turn_right();
+                    }
+                }
+            }
+            loop {
+                spin(2);
+                while get_orientation() != "up" {
+                    turn_left();
+                }
+            }
+            "#,
+        );
+        assert_eq!(metrics.user_functions, 1);
+        assert_eq!(metrics.loops, 3);
+        assert_eq!(metrics.conditionals, 1);
+        // fn body -> for -> if
+        assert_eq!(metrics.max_nesting_depth, 3);
+        // for, if, turn_left, turn_right, loop, spin, while, turn_left
+        assert_eq!(metrics.statements, 8);
+        assert_eq!(metrics.builtin_calls.get("turn_left"), Some(&2));
+        assert_eq!(metrics.builtin_calls.get("turn_right"), Some(&1));
+        assert_eq!(metrics.builtin_calls.get("get_orientation"), Some(&1));
+        assert_eq!(metrics.builtin_calls.get("spin"), None);
+    }
+
+    #[test]
+    fn invalid_script() {
+        assert_eq!(
+            CodeMetrics::from_script("move_forward(1"),
+            CodeMetrics::default()
+        );
+    }
+}
@@ -0,0 +1,735 @@
+use rhai::{
+    ASTNode, Engine, EvalAltResult, Expr, FlowControl, OptimizationLevel, ParseErrorType, Position,
+    Stmt, Token, AST,
+};
+use std::collections::HashSet;
+
+use crate::better_errors::{convert_err, BetterError};
+use crate::constants::BUILTIN_FUNCTIONS;
+
+/// The maximum number of times we try to recover from a syntax error in order
+/// to look for more errors further along in the script.
+const MAX_RECOVERIES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    /// The script can't be run until this is fixed.
+    Error,
+    /// The script can still be run, but there might be something wrong with
+    /// it.
+    Warning,
+}
+
+/// A range of characters in a script. Lines and columns start at 1 and both
+/// ends are inclusive. Zero means the position is unknown.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Span {
+    pub line: usize,
+    pub col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+
+impl Span {
+    /// Returns the span of the token which starts at the given position, or
+    /// just the position itself if there is no such token.
+    pub fn at(script: &str, line: Option<usize>, col: Option<usize>) -> Span {
+        let (line, col) = match (line, col) {
+            (Some(line), Some(col)) => (line, col),
+            (Some(line), None) => (line, 0),
+            _ => return Span::default(),
+        };
+        let pos = Position::new(line as u16, col as u16);
+        let end = tokenize(&raw_engine(), script)
+            .into_iter()
+            .find(|(_, start, _)| *start == pos)
+            .map_or(pos, |(_, _, end)| end);
+        Span {
+            line,
+            col,
+            end_line: end.line().unwrap_or(line),
+            end_col: end.position().unwrap_or(col),
+        }
+    }
+}
+
+/// A problem with a script, e.g. a syntax error.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// The ID of the message (see BetterError::code).
+    pub code: &'static str,
+    pub message: String,
+    pub severity: Severity,
+    pub span: Span,
+}
+
+impl Diagnostic {
+    /// Returns an error diagnostic for the given error in the script.
+    pub fn error(script: &str, err: BetterError) -> Diagnostic {
+        Diagnostic {
+            span: Span::at(script, err.line, err.col),
+            code: err.code,
+            message: err.message,
+            severity: Severity::Error,
+        }
+    }
+
+    /// Returns a warning diagnostic for the given problem in the script.
+    pub fn warning(script: &str, err: BetterError) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Warning,
+            ..Diagnostic::error(script, err)
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Checks the script for problems which can be found without running it (e.g.
+/// syntax errors, missing semicolons, and locked functions). Returns the
+/// compiled script if there are no errors, otherwise returns every error which
+/// could be found, ordered by position.
+///
+/// Rhai stops at the first syntax error, so when possible we fix the error
+/// (e.g. by inserting the missing semicolon) and compile the script again to
+/// look for more errors.
+pub fn check_script(
+    engine: &Engine,
+    avail_funcs: &[String],
+    disabled_funcs: &[&str],
+    script: &str,
+) -> Result<AST, Vec<Diagnostic>> {
+    let mut diagnostics = vec![];
+    let mut script = script.to_string();
+    // The positions of any characters inserted by recover, in the order they
+    // were inserted. Used to map positions in the fixed script back to the
+    // original script.
+    let mut insertions: Vec<Position> = vec![];
+    let mut to_diagnostic = |script: &String, insertions: &[Position], err: Box<EvalAltResult>| {
+        let better_err = convert_err(avail_funcs, disabled_funcs, script.clone(), err);
+        let mut diagnostic = Diagnostic::error(script, better_err);
+        diagnostic.span = original_span(diagnostic.span, insertions);
+        diagnostics.push(diagnostic);
+    };
+
+    let mut ast = None;
+    for _ in 0..=MAX_RECOVERIES {
+        match engine.compile(&script) {
+            Ok(compiled) => {
+                ast = Some(compiled);
+                break;
+            }
+            Err(err) => {
+                let (err_type, pos) = (*err.0, err.1);
+                let recovered = recover(&script, &err_type, pos);
+                to_diagnostic(
+                    &script,
+                    &insertions,
+                    Box::new(EvalAltResult::ErrorParsing(err_type, pos)),
+                );
+                match recovered {
+                    Some((recovered, inserted)) => {
+                        script = recovered;
+                        insertions.extend(inserted);
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+
+    if let Some(ast) = &ast {
+        if let Err(errs) = check_semicolons(&script) {
+            for err in errs {
+                to_diagnostic(&script, &insertions, Box::new(err));
+            }
+        }
+        for err in check_functions(engine, avail_funcs, ast) {
+            to_diagnostic(&script, &insertions, Box::new(err));
+        }
+    }
+
+    match ast {
+        Some(ast) if diagnostics.is_empty() => Ok(ast),
+        _ => {
+            diagnostics.sort_by_key(|diagnostic| (diagnostic.span.line, diagnostic.span.col));
+            Err(diagnostics)
+        }
+    }
+}
+
+/// Attempts to fix the given syntax error so that we can keep looking for
+/// more errors. Returns the fixed script along with the position of the
+/// inserted character (if any), or None if the error can't be fixed. Fixes
+/// never change the line numbers in the script.
+fn recover(
+    script: &str,
+    err_type: &ParseErrorType,
+    pos: Position,
+) -> Option<(String, Option<Position>)> {
+    match err_type {
+        ParseErrorType::MissingToken(token, _) if token == ";" => {
+            // Insert the semicolon right after the end of the previous token.
+            let tokens = tokenize(&raw_engine(), script);
+            let (_, _, prev_end) = tokens
+                .iter()
+                .rev()
+                .find(|(_, start, _)| is_before(*start, pos))?;
+            let offset = char_offset(script, *prev_end)? + 1;
+            let mut chars: Vec<char> = script.chars().collect();
+            chars.insert(offset, ';');
+            let inserted = Position::new(prev_end.line()? as u16, prev_end.position()? as u16 + 1);
+            Some((chars.into_iter().collect(), Some(inserted)))
+        }
+        ParseErrorType::VariableUndefined(name) => {
+            // Replace the variable with a number, padding with spaces so that the
+            // positions of everything else stay the same.
+            let offset = char_offset(script, pos)?;
+            let mut chars: Vec<char> = script.chars().collect();
+            let len = name.chars().count();
+            let found: String = chars.get(offset..offset + len)?.iter().collect();
+            if found != *name {
+                return None;
+            }
+            let replacement = format!("{:<width$}", "0", width = len);
+            chars.splice(offset..offset + len, replacement.chars());
+            Some((chars.into_iter().collect(), None))
+        }
+        _ => None,
+    }
+}
+
+/// Maps a span in a script which was fixed by recover back to the original
+/// script. insertions are the positions of each inserted character, in the
+/// order they were inserted.
+fn original_span(span: Span, insertions: &[Position]) -> Span {
+    let mut span = span;
+    for inserted in insertions.iter().rev() {
+        let (line, col) = (inserted.line(), inserted.position());
+        if Some(span.line) == line && Some(span.col) > col {
+            span.col -= 1;
+        }
+        if Some(span.end_line) == line && Some(span.end_col) > col {
+            span.end_col -= 1;
+        }
+    }
+    span
+}
+
+/// Returns the index of the character at the given position.
+fn char_offset(script: &str, pos: Position) -> Option<usize> {
+    let (line, col) = (pos.line()?, pos.position()?);
+    let mut offset = 0;
+    for (i, text) in script.split('\n').enumerate() {
+        if i + 1 == line {
+            return if col <= text.chars().count() {
+                Some(offset + col - 1)
+            } else {
+                None
+            };
+        }
+        offset += text.chars().count() + 1;
+    }
+    None
+}
+
+/// Functions which are built into the Rhai language itself rather than
+/// registered with the engine.
+const KEYWORD_FUNCTIONS: &[&str] = &[
+    "print",
+    "debug",
+    "type_of",
+    "eval",
+    "Fn",
+    "call",
+    "curry",
+    "is_shared",
+    "is_def_fn",
+    "is_def_var",
+];
+
+/// Returns an error for every function call which can't be resolved. This
+/// includes built-in functions which are not available (i.e. they are locked
+/// or disabled for the current level) and functions which don't exist at all
+/// (e.g. typos).
+fn check_functions(engine: &Engine, avail_funcs: &[String], ast: &AST) -> Vec<EvalAltResult> {
+    let user_funcs: HashSet<String> = ast
+        .iter_fn_def()
+        .map(|func| func.name.to_string())
+        .collect();
+    // Listing every function registered with the engine is relatively slow,
+    // so it is only done if the script calls an unfamiliar function.
+    let mut engine_funcs: Option<HashSet<String>> = None;
+    let mut is_engine_func = |name: &str| {
+        engine_funcs
+            .get_or_insert_with(|| {
+                // Signatures look like "name(param: type, ...) -> type".
+                engine
+                    .gen_fn_signatures(true)
+                    .into_iter()
+                    .filter_map(|sig| sig.split('(').next().map(String::from))
+                    .collect()
+            })
+            .contains(name)
+    };
+    // A variable which holds a function pointer can be called like a function.
+    let mut variables: HashSet<String> = HashSet::new();
+    ast.walk(&mut |path| {
+        if let ASTNode::Stmt(Stmt::Var(var, ..)) = path.last().unwrap() {
+            variables.insert(var.0.name.to_string());
+        }
+        true
+    });
+
+    let mut errs = vec![];
+    ast.walk(&mut |path| {
+        if let ASTNode::Stmt(Stmt::FnCall(call, pos)) | ASTNode::Expr(Expr::FnCall(call, pos)) =
+            path.last().unwrap()
+        {
+            let name = call.name.as_str();
+            let is_available = if BUILTIN_FUNCTIONS.contains_key(name) {
+                avail_funcs.iter().any(|func| func == name)
+            } else {
+                // Calls to functions in other modules and operators (e.g. +)
+                // are resolved by Rhai.
+                !call.namespace.is_empty()
+                    || !name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                    || KEYWORD_FUNCTIONS.contains(&name)
+                    || variables.contains(name)
+                    || user_funcs.contains(name)
+                    || is_engine_func(name)
+            };
+            if !is_available && !user_funcs.contains(name) {
+                errs.push(EvalAltResult::ErrorFunctionNotFound(
+                    format!("{} ()", name),
+                    *pos,
+                ));
+            }
+        }
+        true
+    });
+    errs
+}
+
+/// Returns an engine for checking, querying, or tokenizing a script without
+/// running it. Optimizations could remove or fold parts of the script, so they
+/// are turned off in order to see the script as written.
+pub fn raw_engine() -> Engine {
+    let mut engine = Engine::new_raw();
+    engine.set_optimization_level(OptimizationLevel::None);
+    engine
+}
+
+// We want to simplify the learning process by making semicolons required in places where Rhai
+// considers them optional (i.e. after the last statement of a block or of the script). Rhai already
+// requires semicolons between statements, so we only need to check the last statement in each
+// block. We use the AST to find the blocks and the tokenizer to check what comes before the closing
+// '}', which means comments, strings, and statements split across multiple lines are handled
+// correctly.
+fn check_semicolons(source: &str) -> Result<(), Vec<EvalAltResult>> {
+    let engine = raw_engine();
+    let ast = engine
+        .compile(source)
+        .map_err(|err| vec![EvalAltResult::ErrorParsing(*err.0, err.1)])?;
+    let tokens = tokenize(&engine, source);
+
+    let mut errs = vec![];
+    check_block_semicolons(&tokens, ast.statements(), None, &mut errs);
+    for func in ast.iter_fn_def() {
+        // Closures are also compiled into functions, but their bodies are
+        // expressions and don't need semicolons.
+        if func.name.starts_with("anon$") {
+            continue;
+        }
+        check_block_semicolons(
+            &tokens,
+            func.body.statements(),
+            Some(func.body.end_position()),
+            &mut errs,
+        );
+    }
+    errs.sort_by_key(|err| (err.position().line(), err.position().position()));
+    if errs.is_empty() {
+        Ok(())
+    } else {
+        Err(errs)
+    }
+}
+
+/// Returns every token in the source (not including comments) along with the
+/// positions of its first and last characters.
+pub fn tokenize(engine: &Engine, source: &str) -> Vec<(Token, Position, Position)> {
+    let (mut stream, _) = engine.lex([&source]);
+    let mut tokens = vec![];
+    while let Some((token, start)) = stream.next() {
+        if token == Token::EOF {
+            break;
+        }
+        tokens.push((token, start, stream.pos));
+    }
+    tokens
+}
+
+/// Checks that the last statement in the given block (and recursively in any
+/// nested blocks) is followed by a semicolon. end is the position of the
+/// closing '}' for the block, or None for the top-level statements. Any errors
+/// are added to errs.
+fn check_block_semicolons(
+    tokens: &[(Token, Position, Position)],
+    stmts: &[Stmt],
+    end: Option<Position>,
+    errs: &mut Vec<EvalAltResult>,
+) {
+    for stmt in stmts {
+        match stmt {
+            Stmt::If(flow, _) | Stmt::While(flow, _) | Stmt::Do(flow, ..) => {
+                check_flow_semicolons(tokens, flow, errs);
+            }
+            Stmt::For(for_loop, _) => check_flow_semicolons(tokens, &for_loop.2, errs),
+            Stmt::Block(block) => {
+                check_block_semicolons(tokens, block.statements(), Some(block.end_position()), errs)
+            }
+            _ => {}
+        }
+    }
+
+    let last = match stmts.last() {
+        Some(last) if !last.is_self_terminated() => last,
+        _ => return,
+    };
+    // Function definitions are not statements, so the top-level statements
+    // end at the first function defined after the last statement (if any).
+    let end = end.or_else(|| {
+        let fn_index = tokens.iter().position(|(token, start, _)| {
+            *token == Token::Fn && !is_before(*start, last.position())
+        })?;
+        match fn_index.checked_sub(1).map(|i| &tokens[i]) {
+            Some((Token::Private, start, _)) => Some(*start),
+            _ => Some(tokens[fn_index].1),
+        }
+    });
+    let prev_token = tokens
+        .iter()
+        .rev()
+        .find(|(_, start, _)| end.is_none_or(|end| is_before(*start, end)));
+    if let Some((token, _, token_end)) = prev_token {
+        if *token != Token::SemiColon {
+            errs.push(EvalAltResult::ErrorParsing(
+                ParseErrorType::MissingToken(String::from(';'), String::from("at end of line")),
+                *token_end,
+            ));
+        }
+    }
+}
+
+fn check_flow_semicolons(
+    tokens: &[(Token, Position, Position)],
+    flow: &FlowControl,
+    errs: &mut Vec<EvalAltResult>,
+) {
+    for block in [&flow.body, &flow.branch] {
+        check_block_semicolons(tokens, block.statements(), Some(block.end_position()), errs);
+    }
+}
+
+/// Returns true if position a comes before position b in the source.
+fn is_before(a: Position, b: Position) -> bool {
+    (a.line(), a.position()) < (b.line(), b.position())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_script_multiple_errors() {
+        let mut engine = Engine::new();
+        engine.set_strict_variables(true);
+        let avail_funcs = vec![String::from("move_forward"), String::from("say")];
+        let script = r"let a = 1
+let b = c
+move_forward(a);
+turn_left();
+say(d);";
+        let diagnostics = check_script(&engine, &avail_funcs, &[], script).unwrap_err();
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| (d.span.line, d.span.col, d.message.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, 1, "Syntax Error: Missing semicolon ';' at end of line."),
+                (2, 1, "Syntax Error: Missing semicolon ';' at end of line."),
+                (2, 9, "Error: Variable not found: c"),
+                (
+                    4,
+                    1,
+                    "Error: You haven't unlocked the turn_left function yet"
+                ),
+                (5, 5, "Error: Variable not found: d"),
+            ],
+        );
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+        assert_eq!(
+            diagnostics[3].span,
+            Span {
+                line: 4,
+                col: 1,
+                end_line: 4,
+                end_col: 9,
+            }
+        );
+
+        // Scripts without any errors are compiled.
+        let script = "let a = 1;\nmove_forward(a);";
+        assert!(check_script(&engine, &avail_funcs, &[], script).is_ok());
+    }
+
+    #[test]
+    fn test_check_script_columns_after_recovery() {
+        let mut engine = Engine::new();
+        engine.set_strict_variables(true);
+        let avail_funcs = vec![String::from("say")];
+        // Inserting the missing semicolon must not shift the position of later
+        // errors on the same line.
+        let script = "let a = 1 say(b);";
+        let diagnostics = check_script(&engine, &avail_funcs, &[], script).unwrap_err();
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| (d.span.line, d.span.col, d.span.end_col))
+                .collect::<Vec<_>>(),
+            vec![(1, 11, 13), (1, 15, 15)],
+        );
+        assert_eq!(diagnostics[1].message, "Error: Variable not found: b");
+    }
+
+    #[test]
+    fn test_check_script_unknown_functions() {
+        let engine = Engine::new();
+        let avail_funcs = vec![String::from("move_forward")];
+        let script = r#"fn drive(n) {
+    move_forward(n);
+}
+let f = Fn("drive");
+f.call(1);
+drive(len("abc"));
+print(to_string(1));
+move_foward(1);"#;
+        let diagnostics = check_script(&engine, &avail_funcs, &[], script).unwrap_err();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "err_fn_not_found_suggestion");
+        assert!(diagnostics[0].message.contains("move_forward"));
+        assert_eq!((diagnostics[0].span.line, diagnostics[0].span.col), (8, 1));
+    }
+
+    #[test]
+    fn test_check_script_translated() {
+        let engine = Engine::new();
+        let avail_funcs = vec![String::from("move_forward")];
+        let mut catalog = std::collections::HashMap::new();
+        catalog.insert(
+            String::from("err_fn_locked"),
+            String::from("Erreur : Tu n'as pas encore débloqué la fonction {fn_name}"),
+        );
+        crate::i18n::set_catalog(catalog);
+        let diagnostics = check_script(&engine, &avail_funcs, &[], "turn_left();").unwrap_err();
+        crate::i18n::set_catalog(std::collections::HashMap::new());
+
+        // The code stays the same no matter which language is used.
+        assert_eq!(diagnostics[0].code, "err_fn_locked");
+        assert_eq!(
+            diagnostics[0].message,
+            "Erreur : Tu n'as pas encore débloqué la fonction turn_left"
+        );
+    }
+
+    #[test]
+    fn test_check_semicolons() {
+        let source = r#"
+            // This is a comment
+            fn foo() {
+                // This is a comment
+                let a = 1;
+                let b = 2; // This is an inline comment.
+                let c = 3; /* This is a block comment */
+                let /* this is an intrusive comment */ d = 4;
+                /* 
+                    This is a multiline comment.
+                    It has more than one line.
+                 */
+            }
+        "#;
+        check_semicolons(source).unwrap();
+
+        let source = r#"
+            // This is a comment
+            fn foo() {
+                // This is a comment
+                let a = 1
+                let b = 2; // This is an inline comment.
+                let c = 3; /* This is a block comment */
+                let /* this is an intrusive comment */ d = 4;
+                /* 
+                    This is a multiline comment.
+                    It has more than one line.
+                */
+            }
+        "#;
+        assert!(check_semicolons(source).is_err());
+
+        // Should not be a missing semicolon error if a bracket is on
+        // the next line. This is a regression test for
+        // https://github.com/albrow/elara/issues/67
+        let source = r"
+            loop
+            {
+                move_forward(1);
+            }
+            if true
+            {
+                move_forward(1);
+            }
+            else
+            {
+                move_forward(1);
+            }
+            while true
+            {
+                move_forward(1);
+            }
+        ";
+        check_semicolons(source).unwrap();
+
+        let source = r#"
+            // This is a comment
+            fn foo() {
+                // This is a comment
+                let a = 1;
+                let b = 2 // This is an inline comment.
+                let c = 3; /* This is a block comment */
+                let /* this is an intrusive comment */ d = 4;
+                /*
+                    This is a multiline comment.
+                    It has more than one line.
+                */
+            }
+        "#;
+        assert!(check_semicolons(source).is_err());
+
+        let source = r#"
+            // This is a comment
+            fn foo() {
+                // This is a comment
+                let a = 1;
+                let b = 2; // This is an inline comment.
+                let c = 3 /* This is a block comment */
+                let /* this is an intrusive comment */ d = 4;
+                /*
+                    This is a multiline comment.
+                    It has more than one line.
+                */
+            }
+        "#;
+        assert!(check_semicolons(source).is_err());
+
+        let source = r#"
+        // This is a comment
+        fn foo() {
+            // This is a comment
+            let a = 1;
+            let b = 2; // This is an inline comment.
+            let c = 3; /* This is a block comment */
+            let /* this is an intrusive comment */ d = 4
+            /* 
+                This is a multiline comment.
+                It has more than one line.
+            */
+        }
+    "#;
+        assert!(check_semicolons(source).is_err());
+    }
+
+    #[test]
+    fn test_check_semicolons_fn_after_statements() {
+        // Functions defined after the top-level statements aren't part of
+        // them, so the semicolon should be checked before the function.
+        let source = r"move_forward(1);
+// Turns left.
+fn foo() {
+    turn_left();
+}
+/// Turns right.
+private fn bar() {
+    turn_right();
+}";
+        assert!(check_semicolons(source).is_ok());
+
+        // The semicolon is still required.
+        let source = r"move_forward(1)
+fn foo() {
+    turn_left();
+}";
+        assert!(check_semicolons(source).is_err());
+    }
+
+    #[test]
+    fn test_check_semicolons_multi_line() {
+        // Function arguments can be split across multiple lines.
+        let source = r#"
+            fn foo() {
+                move_forward(
+                    1
+                );
+            }
+            say(
+                "Hello"
+            );"#;
+        check_semicolons(source).unwrap();
+
+        // Strings which look like comments are not comments.
+        let source = r#"
+            say("// not a comment");
+            say("/* also not a comment */")"#;
+        let errs = check_semicolons(source).unwrap_err();
+        assert_eq!(
+            errs[0].to_string(),
+            "Syntax error: Expecting ';' at end of line (line 3, position 43)"
+        );
+
+        // Nested blocks should be checked too.
+        let source = r"
+            loop {
+                if true {
+                    turn_left()
+                } else {
+                    turn_right();
+                }
+            }";
+        let errs = check_semicolons(source).unwrap_err();
+        assert_eq!(errs[0].position(), Position::new(4, 31));
+
+        // Every missing semicolon should be reported, not just the first.
+        let source = r"
+            fn foo() {
+                turn_left()
+            }
+            if true {
+                turn_right()
+            }
+            foo()";
+        let errs = check_semicolons(source).unwrap_err();
+        assert_eq!(
+            errs.iter().map(|err| err.position()).collect::<Vec<_>>(),
+            vec![
+                Position::new(3, 27),
+                Position::new(6, 28),
+                Position::new(8, 17)
+            ]
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+// All player-facing text is looked up by a stable message ID. Messages may
+// contain placeholders like {fn_name}, which are filled in by translate. The
+// UI can provide a catalog for another language with set_catalog. Any message
+// missing from the catalog falls back to English.
+//
+// Level text uses IDs of the form level.<short_name>.<field> (e.g.
+// level.movement.objective) and falls back to the text defined by the level.
+
+lazy_static! {
+    /// The English text for each message ID.
+    static ref ENGLISH: HashMap<&'static str, &'static str> = {
+        let mut m: HashMap<&'static str, &'static str> = HashMap::new();
+
+        // Failure reasons.
+        m.insert("err_out_of_energy", "G.R.O.V.E.R. ran out of energy!");
+        m.insert("err_destroyed_by_enemy", "G.R.O.V.E.R. was attacked and disabled by a malfunctioning rover. Try again!");
+        m.insert("err_crushed_by_asteroid", "G.R.O.V.E.R. was crushed by a falling asteroid. Try again!");
+        m.insert("err_hit_by_turret", "G.R.O.V.E.R. was hit by a turret's laser beam. Try again!");
+        m.insert("err_fell_into_crater", "G.R.O.V.E.R. fell into a crater. Try again!");
+        m.insert("err_dissolved_by_acid", "G.R.O.V.E.R. drove into a pool of acid. Try again!");
+        m.insert("err_electrocuted", "G.R.O.V.E.R. was fried by an electrified floor. Try again!");
+
+        // Shown in Player.err_message.
+        m.insert("player_err_nothing_to_pick_up", "Nothing in front of me to pick up!");
+        m.insert("player_err_already_holding", "I'm already holding something!");
+        m.insert("player_err_nothing_to_drop", "I don't have anything to drop!");
+        m.insert("player_err_no_space_to_drop", "No space in front of me to drop something!");
+        m.insert("player_err_not_enough_energy", "I don't have enough energy to do that!");
+
+        // Errors while running the script.
+        m.insert("err_runtime", "Error: {message}");
+        m.insert("err_no_data_point", "read_data only works if you are next to a data point.");
+        m.insert("err_no_button", "press_button only works if you are next to a button.");
+        m.insert("err_no_rover", "There is no rover with that number.");
+        m.insert("err_index_out_of_bounds", "Error: There is no item at index {index} because the array only has {len} items. (Hint: the first item is at index 0 and the last item is at index {last}.)");
+        m.insert("err_index_out_of_bounds_empty", "Error: There is no item at index {index} because the array is empty.");
+        m.insert("err_string_index_out_of_bounds", "Error: There is no character at index {index} because the string only has {len} characters.");
+        m.insert("err_type_mismatch", "Error: Expected a {expected} but got a {actual} instead.");
+        m.insert("err_infinite_loop", "Error: Possible infinite loop detected.");
+        m.insert("type_number", "number");
+        m.insert("type_string", "string");
+        m.insert("type_boolean", "boolean (true or false)");
+        m.insert("type_empty", "empty value");
+
+        // Errors about functions.
+        m.insert("err_fn_disabled", "Error: The {fn_name} function is disabled for this level");
+        m.insert("err_fn_locked", "Error: You haven't unlocked the {fn_name} function yet");
+        m.insert("err_fn_no_inputs", "Error: The {fn_name} function should not have any inputs.");
+        m.insert("err_fn_one_input_any", "Error: The {fn_name} function should have one input of any type.");
+        m.insert("err_fn_one_input", "Error: The {fn_name} function should have one {arg_type} as an input.");
+        m.insert("err_fn_wrong_inputs", "Error: Wrong inputs for the {fn_name} function. Should have {count} inputs ({arg_types}).");
+        m.insert("err_fn_not_found", "Error: There is no function named {fn_name} (maybe you made a typo?)");
+        m.insert("err_fn_not_found_suggestion", "Error: There is no function named {fn_name}. (Hint: Did you mean {suggestion}?)");
+
+        // Errors about variables.
+        m.insert("err_var_not_found", "Error: Variable not found: {var_name}");
+        m.insert("err_var_not_found_hint", "Error: Variable not found: {var_name}. (Hint: {hint})");
+        m.insert("err_var_not_found_suggestion", "Error: Variable not found: {var_name}. (Hint: Did you mean {suggestion}?)");
+        m.insert("hint_lowercase_loop", "Did you mean loop with a lowercase 'l'?");
+        m.insert("hint_lowercase_let", "Did you mean let with a lowercase 'l'?");
+        m.insert("hint_lowercase_if", "Did you mean if with a lowercase 'i'?");
+        m.insert("hint_missing_quotes", "If you wanted this to be a string, maybe you forgot the quotation marks?");
+        m.insert("hint_missing_parentheses", "If you meant to call a function, make sure you include parentheses after the function name.");
+
+        // Syntax errors.
+        m.insert("err_missing_semicolon", "Syntax Error: Missing semicolon ';' at end of line.");
+        m.insert("err_missing_semicolon_after_statement", "Syntax Error: Missing semicolon ';' after function call or other statement.");
+        m.insert("err_extra_parentheses", "Syntax Error: Unexpected extra parentheses '()'.");
+        m.insert("err_extra_closing_parenthesis", "Syntax Error: Unexpected extra closing parentheses ')'.");
+        m.insert("err_missing_closing_parenthesis", "Syntax Error: Missing a closing parenthesis ')' for the {fn_name} function.");
+        m.insert("err_missing_closing_parenthesis_or_comma", "Syntax Error: Might be a missing closing parenthesis ')' for the {fn_name} function. Or if the function expects more than one input, you might be missing a comma ',' to separate them.");
+        m.insert("err_missing_fn_params", "Syntax Error: Missing parentheses '()' after function name '{fn_name}'.");
+        m.insert("err_unexpected_space_in_var_name", "Syntax Error: Variable names cannot contain spaces. (Hint: try using an underscore instead of a space.)");
+        m.insert("err_unexpected_space_in_func_name", "Syntax Error: Function names cannot contain spaces. (Hint: try using an underscore instead of a space.)");
+        m.insert("err_unterminated_string", "Error: String is missing a quotation mark at the end.");
+
+        // Challenges. Rule descriptions are joined into a sentence, so they
+        // are not capitalized and have no punctuation.
+        m.insert("challenge_and", "{rest} _and_ {last}");
+        m.insert("challenge_max_steps", "complete the objective in {steps} steps or fewer");
+        m.insert("challenge_max_energy", "use {energy} energy or less");
+        m.insert("challenge_max_code_len", "have a code length of {len} characters or less");
+        m.insert("challenge_max_statements", "use {statements} statements or fewer");
+        m.insert("challenge_max_tokens", "use {tokens} tokens or fewer");
+        m.insert("challenge_forbid_function", "don't use the `{fn_name}` function");
+        m.insert("challenge_define_function", "define a function named `{fn_name}`");
+        m.insert("challenge_require_loop", "use a loop");
+        m.insert("challenge_require_while_loop", "use a `while` loop");
+        m.insert("challenge_require_function_definition", "define a new function");
+        m.insert("challenge_require_conditional", "use an `if` statement");
+        m.insert("challenge_require_variable", "use a variable");
+        m.insert("challenge_require_recursion", "use recursion");
+        m.insert("challenge_forbid_loop", "don't use a loop");
+        m.insert("challenge_forbid_while_loop", "don't use any `while` loops");
+        m.insert("challenge_forbid_function_definition", "don't define any new functions");
+        m.insert("challenge_forbid_conditional", "don't use any `if` statements");
+        m.insert("challenge_forbid_variable", "don't use any variables");
+        m.insert("challenge_forbid_recursion", "don't use recursion");
+        m.insert("challenge_no_teleporting", "don't use any telepads");
+        m.insert("challenge_say_all_messages", "use the `say` function to read the messages on every data point");
+
+        // Labels for challenge progress.
+        m.insert("challenge_label_steps", "steps");
+        m.insert("challenge_label_energy", "energy");
+        m.insert("challenge_label_code_len", "code length");
+        m.insert("challenge_label_statements", "statements");
+        m.insert("challenge_label_tokens", "tokens");
+        m.insert("challenge_label_uses_of_function", "uses of `{fn_name}`");
+        m.insert("challenge_label_definitions_of_function", "definitions of `{fn_name}`");
+        m.insert("challenge_label_loops", "loops");
+        m.insert("challenge_label_while_loops", "while loops");
+        m.insert("challenge_label_function_definitions", "function definitions");
+        m.insert("challenge_label_conditionals", "if statements");
+        m.insert("challenge_label_variables", "variables");
+        m.insert("challenge_label_recursive_functions", "recursive functions");
+        m.insert("challenge_label_teleports", "teleports");
+        m.insert("challenge_label_messages", "messages");
+        m.insert("challenge_label_completed", "completed");
+
+        // Objectives.
+        m.insert("objective_reach_goal", "Reach the goal");
+        m.insert("objective_press_button", "Press the button");
+        m.insert("objective_collect_all_energy_cells", "Collect all the energy cells");
+        m.insert("objective_read_all_data_points", "Read all the data points");
+        m.insert("objective_deliver_crate", "Deliver a crate to the drop-off point");
+        m.insert("objective_deliver_crates", "Deliver a crate to each drop-off point");
+        m.insert("objective_survive_steps", "Survive for {steps} steps");
+        m.insert("objective_checkpoints", "Visit each checkpoint in order");
+        m.insert("objective_all", "Complete all of the following");
+        m.insert("objective_any", "Complete any of the following");
+        m.insert("objective_sequence", "Complete the following in order");
+
+        // Warnings.
+        m.insert("warn_shadowed_builtin", "Warning: {fn_name} is already a built-in function. Defining your own function with the same name will replace it.");
+        m.insert("warn_unused_variable", "Warning: The variable {var_name} is never used.");
+        m.insert("warn_say_empty", "Warning: say is being given an empty value, so the rover won't say anything.");
+        m.insert("warn_say_no_value", "Warning: The variable {var_name} doesn't have a value yet, so the rover won't say anything.");
+        m.insert("warn_move_zero", "Warning: Calling {fn_name}(0) won't move the rover at all.");
+        m.insert("warn_move_negative", "Warning: {fn_name} should be given a positive number. (Hint: if you want to go the other way, try using {opposite} instead.)");
+        m.insert("warn_unreachable_code", "Warning: This code will never run because the loop before it never ends. (Hint: you can use break to stop a loop.)");
+
+        m
+    };
+}
+
+thread_local! {
+    /// The catalog for the current language. Empty means English.
+    static CATALOG: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+}
+
+/// Replaces the catalog used for all messages. Passing an empty catalog
+/// switches back to English.
+pub fn set_catalog(catalog: HashMap<String, String>) {
+    CATALOG.with(|current| *current.borrow_mut() = catalog);
+}
+
+/// Returns the text for the given message ID in the current language, or
+/// default if neither the catalog nor the English messages have it.
+fn lookup(id: &str, default: &str) -> String {
+    CATALOG
+        .with(|catalog| catalog.borrow().get(id).cloned())
+        .or_else(|| ENGLISH.get(id).map(|text| text.to_string()))
+        .unwrap_or_else(|| default.to_string())
+}
+
+/// Returns the message with the given ID in the current language, with each
+/// {name} placeholder replaced by the corresponding argument.
+pub fn translate(id: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = lookup(id, id);
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    text
+}
+
+/// Like translate, but for text which is defined outside of the English
+/// messages (e.g. level text). Returns default if the current catalog doesn't
+/// have the message.
+pub fn translate_or(id: &str, default: &str) -> String {
+    lookup(id, default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate() {
+        assert_eq!(
+            translate("err_out_of_energy", &[]),
+            "G.R.O.V.E.R. ran out of energy!"
+        );
+        assert_eq!(
+            translate("err_fn_locked", &[("fn_name", &"say")]),
+            "Error: You haven't unlocked the say function yet"
+        );
+        assert_eq!(translate("not_a_message", &[]), "not_a_message");
+        assert_eq!(
+            translate_or("level.movement.name", "First Steps"),
+            "First Steps"
+        );
+
+        let mut catalog = HashMap::new();
+        catalog.insert(
+            String::from("err_fn_locked"),
+            String::from("Error: Todavía no has desbloqueado la función {fn_name}"),
+        );
+        catalog.insert(
+            String::from("level.movement.name"),
+            String::from("Primeros pasos"),
+        );
+        set_catalog(catalog);
+        assert_eq!(
+            translate("err_fn_locked", &[("fn_name", &"say")]),
+            "Error: Todavía no has desbloqueado la función say"
+        );
+        assert_eq!(
+            translate_or("level.movement.name", "First Steps"),
+            "Primeros pasos"
+        );
+        // Messages missing from the catalog fall back to English.
+        assert_eq!(
+            translate("err_out_of_energy", &[]),
+            "G.R.O.V.E.R. ran out of energy!"
+        );
+
+        set_catalog(HashMap::new());
+        assert_eq!(
+            translate("err_fn_locked", &[("fn_name", &"say")]),
+            "Error: You haven't unlocked the say function yet"
+        );
+    }
+}
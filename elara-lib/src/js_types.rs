@@ -1,20 +1,73 @@
 use js_sys::{Array, Object};
 use wasm_bindgen::prelude::*;
 
-use crate::constants::{ERR_NO_BUTTON, ERR_NO_DATA_POINT};
+use crate::i18n::translate_or;
 use crate::levels::{LevelStyle, Outcome};
 use crate::script_runner;
 use crate::simulation::{
-    AsteroidAnimState, BigEnemyAnimState, EnemyAnimState, GateVariant, ObstacleKind, Orientation,
-    OrientationWithDiagonals, PlayerAnimState, TermData,
+    AsteroidAnimState, BigEnemyAnimState, EnemyAnimState, EnemyVision, GateVariant, HazardEffect,
+    HazardKind, ObstacleKind, Orientation, OrientationWithDiagonals, PlayerAnimState, TermData,
+};
+use crate::{
+    actors, better_errors, challenges, code_metrics, diagnostics, levels, objectives, scoring,
+    simulation, versus,
 };
-use crate::{levels, simulation};
 
 #[wasm_bindgen(getter_with_clone)]
 pub struct RhaiError {
+    /// The message, line, and col of the first error.
     pub message: String,
     pub line: usize,
     pub col: usize,
+    pub diagnostics: Array, // Array<Diagnostic>
+}
+
+impl RhaiError {
+    pub fn from(diagnostics: &[diagnostics::Diagnostic]) -> Self {
+        let first = diagnostics.first();
+        Self {
+            message: first.map_or(String::new(), |d| d.message.clone()),
+            line: first.map_or(0, |d| d.span.line),
+            col: first.map_or(0, |d| d.span.col),
+            diagnostics: to_js_diagnostics(diagnostics),
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Diagnostic {
+    pub code: String, // Stable ID for the kind of problem (e.g. "err_fn_locked" or "warn_unused_variable")
+    pub message: String,
+    pub severity: String, // "error" | "warning"
+    pub line: usize,
+    pub col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+
+impl From<&diagnostics::Diagnostic> for Diagnostic {
+    fn from(diagnostic: &diagnostics::Diagnostic) -> Self {
+        Self {
+            code: String::from(diagnostic.code),
+            message: diagnostic.message.clone(),
+            severity: match diagnostic.severity {
+                diagnostics::Severity::Error => String::from("error"),
+                diagnostics::Severity::Warning => String::from("warning"),
+            },
+            line: diagnostic.span.line,
+            col: diagnostic.span.col,
+            end_line: diagnostic.span.end_line,
+            end_col: diagnostic.span.end_col,
+        }
+    }
+}
+
+fn to_js_diagnostics(diagnostics: &[diagnostics::Diagnostic]) -> Array {
+    diagnostics
+        .iter()
+        .map(|diagnostic| JsValue::from(Diagnostic::from(diagnostic)))
+        .collect()
 }
 
 /// The state and active line numbers associated with each step in
@@ -42,6 +95,42 @@ pub struct ScriptStats {
     pub energy_used: i32,
     // Amount of time (i.e. number of steps) taken to execute the script.
     pub time_taken: i32,
+    pub metrics: CodeMetrics,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct CodeMetrics {
+    pub statements: u32,
+    pub loops: u32,
+    pub conditionals: u32,
+    pub user_functions: u32,
+    pub max_nesting_depth: u32,
+    pub builtin_calls: Object, // Record<string, number>
+    pub tokens: u32,
+}
+
+impl From<&code_metrics::CodeMetrics> for CodeMetrics {
+    fn from(metrics: &code_metrics::CodeMetrics) -> Self {
+        let builtin_calls = Object::new();
+        for (name, count) in metrics.builtin_calls.iter() {
+            js_sys::Reflect::set(
+                &builtin_calls,
+                &JsValue::from(name),
+                &JsValue::from(*count as u32),
+            )
+            .unwrap();
+        }
+        Self {
+            statements: metrics.statements as u32,
+            loops: metrics.loops as u32,
+            conditionals: metrics.conditionals as u32,
+            user_functions: metrics.user_functions as u32,
+            max_nesting_depth: metrics.max_nesting_depth as u32,
+            builtin_calls,
+            tokens: metrics.tokens as u32,
+        }
+    }
 }
 
 impl From<&script_runner::ScriptStats> for ScriptStats {
@@ -50,6 +139,7 @@ impl From<&script_runner::ScriptStats> for ScriptStats {
             code_len: stats.code_len as i32,
             energy_used: stats.energy_used as i32,
             time_taken: stats.time_taken as i32,
+            metrics: CodeMetrics::from(&stats.metrics),
         }
     }
 }
@@ -59,9 +149,102 @@ impl From<&script_runner::ScriptStats> for ScriptStats {
 pub struct RunResult {
     pub states: Array,            // Array<FuzzyStateWithLines>
     pub outcome: String,          // "success" | "continue" | "other failure message"
-    pub err_type: Option<String>, // "err_no_button" | "err_no_data_point"
+    pub err_type: Option<String>, // "err_no_button" | "err_no_data_point" | ... (see RuntimeErrorKind::id)
     pub stats: ScriptStats,
     pub passes_challenge: bool,
+    pub stars: Option<u8>, // 0-3, or undefined if the level doesn't award stars
+    pub objective_progress: Array, // Array<ObjectiveProgress> (empty if the level has no objectives)
+    pub challenge_progress: Array, // Array<ChallengeProgress> (empty if the level has no challenge)
+    pub warnings: Array,           // Array<Diagnostic>
+    pub failure_reason: Option<FailureReason>, // undefined unless the outcome is a failure
+    pub runtime_error: Option<RuntimeError>,
+}
+
+/// Why the level was failed. The kind can be used to decide what to show
+/// (e.g. hints) without relying on the message.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct FailureReason {
+    pub kind: String, // "out_of_energy" | "destroyed_by_enemy" | "runtime_error" | ... (see levels::FailureReason::id)
+    pub message: String,
+}
+
+impl From<&levels::FailureReason> for FailureReason {
+    fn from(reason: &levels::FailureReason) -> Self {
+        Self {
+            kind: String::from(reason.id()),
+            message: reason.to_string(),
+        }
+    }
+}
+
+/// An error which caused the script to fail while it was running.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct RuntimeError {
+    pub kind: String, // Same as RunResult.err_type
+    pub message: String,
+    pub line: usize,
+    pub col: usize,
+}
+
+impl From<&better_errors::RuntimeError> for RuntimeError {
+    fn from(err: &better_errors::RuntimeError) -> Self {
+        Self {
+            kind: String::from(err.kind.id()),
+            message: err.message.clone(),
+            line: err.line.unwrap_or(0),
+            col: err.col.unwrap_or(0),
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct ChallengeProgress {
+    pub description: String,
+    pub label: String,
+    pub current: u32,
+    pub target: u32,
+    pub passed: bool,
+}
+
+impl From<&challenges::ChallengeProgress> for ChallengeProgress {
+    fn from(progress: &challenges::ChallengeProgress) -> Self {
+        Self {
+            description: progress.description.clone(),
+            label: progress.label.clone(),
+            current: progress.current as u32,
+            target: progress.target as u32,
+            passed: progress.passed,
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct ObjectiveProgress {
+    pub description: String,
+    pub completed: bool,
+    pub current: u32,
+    pub total: u32,
+    pub children: Array, // Array<ObjectiveProgress>
+}
+
+impl From<&objectives::ObjectiveProgress> for ObjectiveProgress {
+    fn from(progress: &objectives::ObjectiveProgress) -> Self {
+        Self {
+            description: progress.description.clone(),
+            completed: progress.completed,
+            current: progress.current as u32,
+            total: progress.total as u32,
+            children: progress
+                .children
+                .iter()
+                .map(|child| JsValue::from(ObjectiveProgress::from(child)))
+                .collect(),
+        }
+    }
 }
 
 /// Converts script_runner::ScriptResult to a format that is wasm_bindgen
@@ -81,29 +264,110 @@ pub fn to_js_run_result(result: &script_runner::ScriptResult) -> RunResult {
             }),
         );
     }
-    let err_type = match &result.outcome {
-        Outcome::Failure(message) => {
-            if message.contains(ERR_NO_BUTTON) {
-                Some(String::from("err_no_button"))
-            } else if message.contains(ERR_NO_DATA_POINT) {
-                Some(String::from("err_no_data_point"))
-            } else {
-                None
-            }
-        }
-        _ => None,
-    };
     RunResult {
         states: states_array,
         outcome: match &result.outcome {
             Outcome::Success => String::from("success"),
-            Outcome::Failure(msg) => msg.clone(),
+            Outcome::Failure(reason) => reason.to_string(),
             Outcome::Continue => String::from("continue"),
             Outcome::NoObjective => String::from("no_objective"),
         },
         stats: ScriptStats::from(&result.stats),
         passes_challenge: result.passes_challenge,
-        err_type,
+        stars: result.stars,
+        objective_progress: result
+            .objective_progress
+            .iter()
+            .map(|progress| JsValue::from(ObjectiveProgress::from(progress)))
+            .collect(),
+        challenge_progress: result
+            .challenge_progress
+            .iter()
+            .map(|progress| JsValue::from(ChallengeProgress::from(progress)))
+            .collect(),
+        warnings: to_js_diagnostics(&result.warnings),
+        failure_reason: match &result.outcome {
+            Outcome::Failure(reason) => Some(FailureReason::from(reason)),
+            _ => None,
+        },
+        err_type: result
+            .runtime_error()
+            .map(|err| String::from(err.kind.id())),
+        runtime_error: result.runtime_error().map(RuntimeError::from),
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct VersusError {
+    /// The index of the script which caused the error.
+    pub rover: usize,
+    /// The message, line, and col of the first error.
+    pub message: String,
+    pub line: usize,
+    pub col: usize,
+    pub diagnostics: Array, // Array<Diagnostic>
+}
+
+impl VersusError {
+    pub fn from(err: &versus::VersusError) -> Self {
+        let error = RhaiError::from(&err.errors);
+        Self {
+            rover: err.rover,
+            message: error.message,
+            line: error.line,
+            col: error.col,
+            diagnostics: error.diagnostics,
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone, PartialEq, Debug)]
+pub struct VersusRoverStats {
+    pub reached_goal_at: Option<u32>,
+    pub energy_cells_collected: u32,
+    pub steps_survived: u32,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct VersusResult {
+    pub states: Array, // Array<State>
+    pub rovers: Array, // Array<VersusRoverStats>
+    pub winner: Option<u32>,
+    pub decided_by: String, // "goal" | "energy_cells" | "survival" | "draw"
+    pub converged: bool,
+}
+
+impl VersusResult {
+    pub fn from(result: &versus::VersusResult) -> Self {
+        let states = Array::new_with_length(result.states.len() as u32);
+        for (i, state) in result.states.iter().enumerate() {
+            states.set(i as u32, JsValue::from(State::from(state.clone())));
+        }
+        let rovers = Array::new_with_length(result.rovers.len() as u32);
+        for (i, stats) in result.rovers.iter().enumerate() {
+            rovers.set(
+                i as u32,
+                JsValue::from(VersusRoverStats {
+                    reached_goal_at: stats.reached_goal_at.map(|step| step as u32),
+                    energy_cells_collected: stats.energy_cells_collected as u32,
+                    steps_survived: stats.steps_survived as u32,
+                }),
+            );
+        }
+        Self {
+            states,
+            rovers,
+            winner: result.winner.map(|winner| winner as u32),
+            decided_by: match result.decided_by {
+                versus::VersusDecider::Goal => String::from("goal"),
+                versus::VersusDecider::EnergyCells => String::from("energy_cells"),
+                versus::VersusDecider::Survival => String::from("survival"),
+                versus::VersusDecider::Draw => String::from("draw"),
+            },
+            converged: result.converged,
+        }
     }
 }
 
@@ -119,6 +383,57 @@ pub struct LevelData {
     pub initial_code: String,
     pub disabled_funcs: Array, // Array<String>
     pub challenge: String,
+    pub energy_costs: EnergyCosts,
+    pub par: Par,
+}
+
+/// Par values for a level. Each field is undefined if it is not scored.
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Par {
+    pub time_taken: Option<u32>,
+    pub energy_used: Option<u32>,
+    pub code_len: Option<u32>,
+}
+
+impl From<scoring::Par> for Par {
+    fn from(par: scoring::Par) -> Self {
+        Self {
+            time_taken: par.time_taken,
+            energy_used: par.energy_used,
+            code_len: par.code_len.map(|len| len as u32),
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone, PartialEq, Debug)]
+pub struct EnergyCosts {
+    pub wait: u32,
+    pub movement: u32,
+    pub movement_while_holding: u32,
+    pub turn: u32,
+    pub say: u32,
+    pub read_data: u32,
+    pub press_button: u32,
+    pub pick_up: u32,
+    pub drop: u32,
+}
+
+impl From<actors::EnergyCosts> for EnergyCosts {
+    fn from(costs: actors::EnergyCosts) -> Self {
+        Self {
+            wait: costs.wait,
+            movement: costs.movement,
+            movement_while_holding: costs.movement_while_holding,
+            turn: costs.turn,
+            say: costs.say,
+            read_data: costs.read_data,
+            press_button: costs.press_button,
+            pick_up: costs.pick_up,
+            drop: costs.drop,
+        }
+    }
 }
 
 impl LevelData {
@@ -128,19 +443,33 @@ impl LevelData {
             disabled_funcs.push(&JsValue::from(func.to_string()));
         }
         Self {
-            name: level.name().to_string(),
+            name: translate_or(&format!("level.{}.name", level.short_name()), level.name()),
             short_name: level.short_name().to_string(),
             style: match level.style() {
                 LevelStyle::Default => "default".to_string(),
                 LevelStyle::GlossyTiles => "glossy_tiles".to_string(),
                 LevelStyle::Gray => "gray".to_string(),
             },
-            camera_text: level.camera_text().to_string(),
-            objective: level.objective().to_string(),
-            initial_code: level.initial_code().to_string(),
+            camera_text: translate_or(
+                &format!("level.{}.camera_text", level.short_name()),
+                level.camera_text(),
+            ),
+            objective: translate_or(
+                &format!("level.{}.objective", level.short_name()),
+                level.objective(),
+            ),
+            initial_code: translate_or(
+                &format!("level.{}.initial_code", level.short_name()),
+                level.initial_code(),
+            ),
             initial_state: State::from(level.initial_states()[0].clone()),
             disabled_funcs,
-            challenge: level.challenge().unwrap_or_default().to_string(),
+            challenge: level
+                .challenge()
+                .map(|challenge| challenge.description())
+                .unwrap_or_default(),
+            energy_costs: EnergyCosts::from(level.energy_costs()),
+            par: Par::from(level.par().unwrap_or_default()),
         }
     }
 }
@@ -176,6 +505,17 @@ pub struct Asteroid {
     pub anim_state: String, // AsteroidAnimState
 }
 
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Turret {
+    pub pos: Pos,
+    pub facing: String, // Orientation
+    pub steps_until_fire: i32,
+    pub warm_up_steps: i32,
+    pub anim_state: String, // "idle" | "warming_up" | "firing"
+    pub beam: Array,        // Array<Pos>
+}
+
 /// Special metadata which can be used for teleportation animations in the UI.
 /// Includes:
 ///    - start_pos: The position of the rover before entering telepad.
@@ -261,6 +601,7 @@ fn get_js_big_enemy_anim_data(anim_state: &BigEnemyAnimState) -> Option<JsValue>
 #[derive(Clone, PartialEq, Debug)]
 pub struct State {
     pub player: Player,
+    pub extra_rovers: Array,      // Array<Player>
     pub energy_cells: Array,      // Array<EnergyCell>
     pub goals: Array,             // Array<Goal>
     pub enemies: Array,           // Array<Enemy>
@@ -274,6 +615,9 @@ pub struct State {
     pub crates: Array,            // Array<Crate>
     pub asteroid_warnings: Array, // Array<AsteroidWarning>
     pub asteroids: Array,         // Array<Asteroid>
+    pub turrets: Array,           // Array<Turret>
+    pub charging_pads: Array,     // Array<ChargingPad>
+    pub hazards: Array,           // Array<Hazard>
 }
 
 impl State {
@@ -321,6 +665,11 @@ impl State {
                 Orientation::Right => "right",
             };
             let anim_data = get_js_enemy_anim_data(&enemy.anim_state).unwrap_or(JsValue::UNDEFINED);
+            let (vision, vision_range) = match enemy.vision {
+                EnemyVision::Omniscient => ("omniscient", 0),
+                EnemyVision::Line(range) => ("line", range as i32),
+                EnemyVision::Cone(range) => ("cone", range as i32),
+            };
             enemies.set(
                 i as u32,
                 JsValue::from(Enemy {
@@ -331,6 +680,10 @@ impl State {
                     anim_state: anim_state.to_string(),
                     anim_data,
                     facing: facing.to_string(),
+                    vision: vision.to_string(),
+                    vision_range,
+                    spotted_player: enemy.spotted_player,
+                    destroyed: enemy.destroyed,
                 }),
             );
         }
@@ -473,6 +826,8 @@ impl State {
                         x: big_enemy.pos.x,
                         y: big_enemy.pos.y,
                     },
+                    width: big_enemy.width,
+                    height: big_enemy.height,
                     facing: match big_enemy.facing {
                         OrientationWithDiagonals::Up => "up".to_string(),
                         OrientationWithDiagonals::Down => "down".to_string(),
@@ -485,6 +840,7 @@ impl State {
                     },
                     anim_state: anim_state.to_string(),
                     anim_data,
+                    destroyed: big_enemy.destroyed,
                 }),
             );
         }
@@ -504,6 +860,7 @@ impl State {
                         simulation::CrateColor::Green => "green".to_string(),
                         simulation::CrateColor::Blue => "blue".to_string(),
                     },
+                    crushed: crt.crushed,
                 }),
             );
         }
@@ -542,8 +899,91 @@ impl State {
             );
         }
 
+        let turrets = Array::new_with_length(state.turrets.len() as u32);
+        for (i, turret) in state.turrets.iter().enumerate() {
+            let anim_state = if !turret.beam.is_empty() {
+                "firing"
+            } else if turret.is_warming_up() {
+                "warming_up"
+            } else {
+                "idle"
+            };
+            let beam = Array::new_with_length(turret.beam.len() as u32);
+            for (j, pos) in turret.beam.iter().enumerate() {
+                beam.set(j as u32, JsValue::from(Pos { x: pos.x, y: pos.y }));
+            }
+            turrets.set(
+                i as u32,
+                JsValue::from(Turret {
+                    pos: Pos {
+                        x: turret.pos.x,
+                        y: turret.pos.y,
+                    },
+                    facing: match turret.facing {
+                        Orientation::Up => "up".to_string(),
+                        Orientation::Down => "down".to_string(),
+                        Orientation::Left => "left".to_string(),
+                        Orientation::Right => "right".to_string(),
+                    },
+                    steps_until_fire: turret.steps_until_fire as i32,
+                    warm_up_steps: turret.warm_up_steps as i32,
+                    anim_state: anim_state.to_string(),
+                    beam,
+                }),
+            );
+        }
+
+        let extra_rovers = Array::new_with_length(state.extra_rovers.len() as u32);
+        for (i, rover) in state.extra_rovers.into_iter().enumerate() {
+            extra_rovers.set(i as u32, JsValue::from(Player::from(rover)));
+        }
+
+        let charging_pads = Array::new_with_length(state.charging_pads.len() as u32);
+        for (i, pad) in state.charging_pads.iter().enumerate() {
+            charging_pads.set(
+                i as u32,
+                JsValue::from(ChargingPad {
+                    pos: Pos {
+                        x: pad.pos.x,
+                        y: pad.pos.y,
+                    },
+                    rate: pad.rate,
+                    cooldown: pad.cooldown,
+                    cooldown_remaining: pad.cooldown_remaining,
+                    charging: pad.charging,
+                }),
+            );
+        }
+
+        let hazards = Array::new_with_length(state.hazards.len() as u32);
+        for (i, hazard) in state.hazards.iter().enumerate() {
+            let (effect, drain_amount) = match hazard.effect {
+                HazardEffect::Fail => ("fail", 0),
+                HazardEffect::Drain(amount) => ("drain", amount),
+            };
+            hazards.set(
+                i as u32,
+                JsValue::from(Hazard {
+                    pos: Pos {
+                        x: hazard.pos.x,
+                        y: hazard.pos.y,
+                    },
+                    kind: match hazard.kind {
+                        HazardKind::Crater => "crater".to_string(),
+                        HazardKind::Acid => "acid".to_string(),
+                        HazardKind::ElectrifiedFloor => "electrified_floor".to_string(),
+                    },
+                    effect: effect.to_string(),
+                    drain_amount,
+                    bridgeable: hazard.bridgeable,
+                    bridged: !hazard.is_active(),
+                }),
+            );
+        }
+
         State {
             player: Player::from(state.player),
+            extra_rovers,
             energy_cells,
             goals,
             enemies,
@@ -557,6 +997,9 @@ impl State {
             crates,
             asteroid_warnings,
             asteroids,
+            turrets,
+            charging_pads,
+            hazards,
         }
     }
 }
@@ -633,6 +1076,29 @@ pub struct EnergyCell {
     pub collected: bool,
 }
 
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Hazard {
+    pub pos: Pos,
+    pub kind: String,   // "crater" | "acid" | "electrified_floor"
+    pub effect: String, // "fail" | "drain"
+    /// The amount of energy drained (only if effect is "drain").
+    pub drain_amount: u32,
+    pub bridgeable: bool,
+    /// Whether a crate has been dropped into the hazard, making it safe.
+    pub bridged: bool,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ChargingPad {
+    pub pos: Pos,
+    pub rate: u32,
+    pub cooldown: u32,
+    pub cooldown_remaining: u32,
+    pub charging: bool,
+}
+
 #[wasm_bindgen(getter_with_clone)]
 #[derive(Clone, PartialEq, Debug)]
 pub struct Enemy {
@@ -640,15 +1106,22 @@ pub struct Enemy {
     pub anim_state: String, // EnemyAnimState
     pub anim_data: JsValue, // TeleAnimData | BumpAnimData | (other animation data types) | undefined
     pub facing: String,     // Orientation
+    pub vision: String,     // EnemyVision
+    pub vision_range: i32,  // Number of spaces the enemy can see (0 for omniscient enemies).
+    pub spotted_player: bool,
+    pub destroyed: bool,
 }
 
 #[wasm_bindgen(getter_with_clone)]
 #[derive(Clone, PartialEq, Debug)]
 pub struct BigEnemy {
     pub pos: Pos,
+    pub width: u32,         // Width when facing up or down
+    pub height: u32,        // Height when facing up or down
     pub anim_state: String, // EnemyAnimState
     pub anim_data: JsValue, // TeleAnimData | BumpAnimData | (other animation data types) | undefined
     pub facing: String,     // Orientation
+    pub destroyed: bool,
 }
 
 #[wasm_bindgen(getter_with_clone)]
@@ -711,4 +1184,5 @@ pub struct Crate {
     pub pos: Pos,
     pub held: bool,
     pub color: String, // CrateColor
+    pub crushed: bool,
 }
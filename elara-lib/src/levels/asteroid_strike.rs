@@ -1,8 +1,7 @@
-use rhai::Engine;
-
 use super::{std_check_win, Level, Outcome};
 use crate::actors::AsteroidActor;
-use crate::script_runner::ScriptStats;
+use crate::challenges::{Challenge, ChallengeRule};
+use crate::scoring::Par;
 use crate::simulation::{Actor, AsteroidWarning, DataPoint, Orientation};
 use crate::simulation::{Goal, Obstacle, Player, State};
 use crate::state_maker::StateMaker;
@@ -115,16 +114,17 @@ if safe_direction == "right" {
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Reach the goal without using the `read_data` function.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(9),
+            energy_used: Some(6),
+            code_len: Some(221),
+        })
     }
-    fn check_challenge(&self, _states: &[State], script: &str, _stats: &ScriptStats) -> bool {
-        // Strip the comments first, then check if the script contains "read_data".
-        if let Ok(script) = Engine::new().compact_script(script) {
-            return !script.contains("read_data");
-        }
-        // Otherwise, challenge is considered not passed.
-        false
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![ChallengeRule::ForbidFunction(
+            "read_data",
+        )]))
     }
 }
 
@@ -168,6 +168,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
 
         // Hard-coding the movement direction should always result in failure.
         // In this specific case, it should be Outcome::Continue because we didn't
@@ -274,5 +275,18 @@ mod tests {
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
         assert!(result.passes_challenge);
+
+        // So should a variable with a similar name.
+        let script = r"
+            let read_data_count = 3;
+            move_forward(read_data_count);
+            turn_right();
+            move_forward(read_data_count);
+            move_backward(read_data_count);";
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(result.outcome, Outcome::Success);
+        assert!(result.passes_challenge);
     }
 }
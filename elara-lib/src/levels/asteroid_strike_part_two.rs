@@ -1,5 +1,6 @@
 use super::{std_check_win, Level, Outcome};
 use crate::actors::AsteroidActor;
+use crate::scoring::Par;
 use crate::simulation::{Actor, AsteroidWarning, DataPoint, Orientation};
 use crate::simulation::{Goal, Obstacle, Player, State};
 use crate::state_maker::StateMaker;
@@ -122,6 +123,13 @@ impl Level for AsteroidStrikePartTwo {
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(8),
+            energy_used: Some(6),
+            code_len: Some(143),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -158,6 +166,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
 
         // Hard-coding the movement direction should always result in failure.
         // In this specific case, it should be Outcome::Continue because we didn't
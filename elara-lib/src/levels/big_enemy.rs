@@ -1,11 +1,11 @@
 use super::{make_all_initial_states_for_telepads, std_check_win, Level, Outcome};
+use crate::challenges::{Challenge, ChallengeRule};
+use crate::scoring::Par;
 use crate::{
     actors::{BigEnemyActor, Bounds},
-    script_runner::ScriptStats,
     simulation::{
         Actor, BigEnemy, Button, ButtonConnection, DataPoint, EnergyCell, Gate, GateVariant, Goal,
-        Obstacle, Orientation, OrientationWithDiagonals, PasswordGate, Player, PlayerAnimState,
-        State, Telepad,
+        Obstacle, Orientation, OrientationWithDiagonals, PasswordGate, Player, State, Telepad,
     },
     state_maker::StateMaker,
 };
@@ -122,16 +122,15 @@ fn face_direction(direction) {
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Reach the goal without using any telepads.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(46),
+            energy_used: Some(33),
+            code_len: Some(373),
+        })
     }
-    fn check_challenge(&self, states: &[State], _script: &str, _stats: &ScriptStats) -> bool {
-        for state in states {
-            if let PlayerAnimState::Teleporting(_) = state.player.anim_state {
-                return false;
-            }
-        }
-        true
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![ChallengeRule::NoTeleporting]))
     }
 }
 
@@ -183,6 +182,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
     }
 
     #[test]
@@ -1,4 +1,5 @@
 use super::{std_check_win, Level, Outcome};
+use crate::scoring::Par;
 use crate::simulation::{
     Actor, Button, ButtonConnection, Gate, GateVariant, Goal, Obstacle, Orientation, Player, State,
 };
@@ -63,6 +64,13 @@ move_forward(4);
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(15),
+            energy_used: Some(10),
+            code_len: Some(113),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -97,6 +105,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
 
         // Running this code should also result in Outcome::Success. It just
         // uses fewer steps by moving backward.
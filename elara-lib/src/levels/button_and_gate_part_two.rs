@@ -1,4 +1,5 @@
 use super::{std_check_win, Level, Outcome};
+use crate::scoring::Par;
 use crate::simulation::{
     Actor, Button, ButtonConnection, Gate, GateVariant, Goal, Obstacle, Orientation, Player, State,
 };
@@ -90,6 +91,13 @@ impl Level for ButtonAndGatePartTwo {
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(16),
+            energy_used: Some(12),
+            code_len: Some(137),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -125,5 +133,6 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
     }
 }
@@ -1,8 +1,6 @@
-use super::{Level, Outcome};
-use crate::{
-    constants::ERR_OUT_OF_ENERGY,
-    simulation::{Actor, Button, ButtonConnection, Orientation, Player, State},
-};
+use super::{FailureReason, Level, Outcome};
+use crate::scoring::Par;
+use crate::simulation::{Actor, Button, ButtonConnection, Orientation, Player, State};
 
 #[derive(Copy, Clone)]
 pub struct ButtonsPartOne {}
@@ -41,13 +39,20 @@ impl Level for ButtonsPartOne {
         // Note that this level uses a different check_win function. There is not
         // goal to reach. Instead you beat the level by pressing the button.
         if state.player.energy == 0 {
-            Outcome::Failure(ERR_OUT_OF_ENERGY.to_string())
+            Outcome::Failure(FailureReason::OutOfEnergy)
         } else if state.buttons[0].currently_pressed {
             Outcome::Success
         } else {
             Outcome::Continue
         }
     }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(3),
+            energy_used: Some(2),
+            code_len: Some(31),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -76,5 +81,6 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
     }
 }
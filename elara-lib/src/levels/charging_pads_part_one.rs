@@ -0,0 +1,96 @@
+use super::{std_check_win, Level, Outcome};
+use crate::scoring::Par;
+use crate::simulation::{Actor, ChargingPad, Goal, Obstacle, Orientation, Player, State};
+use crate::state_maker::StateMaker;
+
+#[derive(Copy, Clone)]
+pub struct ChargingPadsPartOne {}
+
+impl Level for ChargingPadsPartOne {
+    fn name(&self) -> &'static str {
+        "Solar Power"
+    }
+    fn short_name(&self) -> &'static str {
+        "charging_pads_part_one"
+    }
+    fn objective(&self) -> &'static str {
+        "Recharge on the solar pad, then move the rover ({robot}) to the goal ({goal})."
+    }
+    fn initial_code(&self) -> &'static str {
+        r#"// G.R.O.V.E.R. is parked on a solar charging pad, but it doesn't
+// have enough energy to reach the goal yet. The pad restores a
+// little bit of energy whenever the rover waits on it (but it
+// needs to rest for a step in between).
+//
+// Try using a while loop with get_energy() and wait() to charge
+// until the rover has enough energy.
+//
+// CHANGE THE CODE BELOW:
+move_forward(10);
+"#
+    }
+    fn initial_states(&self) -> Vec<State> {
+        vec![StateMaker::new()
+            .with_player(Player::new(1, 3, 2, Orientation::Right))
+            .with_charging_pads(vec![ChargingPad::new_with_cooldown(1, 3, 2, 1)])
+            .with_goals(vec![Goal::new(11, 3)])
+            .with_obstacles(vec![
+                Obstacle::new(0, 2),
+                Obstacle::new(1, 2),
+                Obstacle::new(2, 2),
+                Obstacle::new(0, 4),
+                Obstacle::new(1, 4),
+                Obstacle::new(2, 4),
+            ])
+            .build()]
+    }
+    fn actors(&self) -> Vec<Box<dyn Actor>> {
+        vec![]
+    }
+    fn check_win(&self, state: &State) -> Outcome {
+        std_check_win(state)
+    }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(17),
+            energy_used: Some(10),
+            code_len: Some(48),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::{FailureReason, Outcome};
+
+    #[test]
+    fn level() {
+        let mut game = crate::Game::new();
+        const LEVEL: &'static dyn Level = &ChargingPadsPartOne {};
+
+        // Running the initial code should result in running out of energy.
+        let script = LEVEL.initial_code();
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(result.outcome, Outcome::Failure(FailureReason::OutOfEnergy));
+
+        // Charging until there is enough energy should result in Outcome::Success.
+        let script = r"
+            while get_energy() < 10 {
+                wait(1);
+            }
+            move_forward(10);
+        ";
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
+        // The pad charges every other step, so it takes 7 steps to go from 2
+        // to 10 energy.
+        assert_eq!(result.states.len(), 1 + 7 + 10);
+        assert_eq!(result.trace.len(), result.states.len());
+    }
+}
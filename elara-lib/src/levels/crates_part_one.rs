@@ -1,4 +1,5 @@
 use super::{std_check_win, Level, Outcome};
+use crate::scoring::Par;
 use crate::simulation::{Actor, Crate, CrateColor, Goal, Obstacle, Orientation, Player, State};
 
 #[derive(Copy, Clone)]
@@ -44,6 +45,13 @@ impl Level for CratesPartOne {
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(5),
+            energy_used: Some(4),
+            code_len: Some(42),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -72,6 +80,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
 
         // This should work too.
         let script = r#"
@@ -1,4 +1,6 @@
 use super::{std_check_win, Level, Outcome};
+use crate::challenges::{Challenge, ChallengeRule};
+use crate::scoring::Par;
 use crate::simulation::{
     Actor, Crate, CrateColor, EnergyCell, Goal, Obstacle, Orientation, Player, State,
 };
@@ -76,16 +78,18 @@ drop();
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Use 12 energy or less _and_ have a code length of 190 chars or less.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(44),
+            energy_used: Some(22),
+            code_len: Some(426),
+        })
     }
-    fn check_challenge(
-        &self,
-        _states: &[State],
-        _script: &str,
-        stats: &crate::script_runner::ScriptStats,
-    ) -> bool {
-        stats.code_len <= 190 && stats.energy_used <= 12
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![
+            ChallengeRule::MaxEnergy(12),
+            ChallengeRule::MaxCodeLen(190),
+        ]))
     }
 }
 
@@ -147,6 +151,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
     }
 
     #[test]
@@ -180,6 +185,13 @@ mod tests {
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
         assert!(!result.passes_challenge);
+        // The code uses little enough energy, but is too long.
+        assert!(result.challenge_progress[0].passed);
+        let code_len = &result.challenge_progress[1];
+        assert_eq!(code_len.label, "code length");
+        assert_eq!(code_len.target, 190);
+        assert!(code_len.current > 190);
+        assert!(!code_len.passed);
 
         // This code satisfies the challenge conditions.
         let script = r#"
@@ -1,4 +1,6 @@
 use super::{std_check_win, Level, Outcome};
+use crate::challenges::{Challenge, ChallengeRule};
+use crate::scoring::Par;
 use crate::simulation::{
     Actor, Crate, CrateColor, EnergyCell, Goal, Obstacle, Orientation, Player, State,
 };
@@ -73,16 +75,15 @@ drop();
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Reach the goal in 17 steps or fewer.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(20),
+            energy_used: Some(13),
+            code_len: Some(173),
+        })
     }
-    fn check_challenge(
-        &self,
-        _states: &[State],
-        _script: &str,
-        stats: &crate::script_runner::ScriptStats,
-    ) -> bool {
-        stats.time_taken <= 17
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![ChallengeRule::MaxSteps(17)]))
     }
 }
 
@@ -124,6 +125,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
     }
 
     #[test]
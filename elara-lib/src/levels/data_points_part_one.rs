@@ -1,8 +1,6 @@
-use super::{Level, Outcome};
-use crate::{
-    constants::ERR_OUT_OF_ENERGY,
-    simulation::{Actor, DataPoint, Obstacle, Orientation, Player, State},
-};
+use super::{FailureReason, Level, Outcome};
+use crate::scoring::Par;
+use crate::simulation::{Actor, DataPoint, Obstacle, Orientation, Player, State};
 
 const HUMMUS_RECIPE: &str = r"{markdown}
 **Ingredients**:
@@ -73,19 +71,26 @@ impl Level for DataPointsPartOne {
         // Note that this level uses a different check_win function. There is not
         // goal to reach. Instead you beat the level by saying the correct message.
         if state.player.energy == 0 {
-            Outcome::Failure(ERR_OUT_OF_ENERGY.to_string())
+            Outcome::Failure(FailureReason::OutOfEnergy)
         } else if state.player.message == HUMMUS_RECIPE {
             Outcome::Success
         } else {
             Outcome::Continue
         }
     }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(7),
+            energy_used: Some(5),
+            code_len: Some(33),
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::levels::{Outcome, ERR_OUT_OF_ENERGY};
+    use crate::levels::Outcome;
 
     #[test]
     fn level() {
@@ -107,6 +112,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
 
         // Saying something else should result in Outcome::Continue.
         let script = r#"
@@ -128,9 +134,6 @@ mod tests {
         let result = game
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
-        assert_eq!(
-            result.outcome,
-            Outcome::Failure(String::from(ERR_OUT_OF_ENERGY))
-        );
+        assert_eq!(result.outcome, Outcome::Failure(FailureReason::OutOfEnergy));
     }
 }
@@ -1,6 +1,7 @@
 use super::{std_check_win, Level, Outcome};
 use crate::actors::{AsteroidActor, Bounds, EvilRoverActor};
-use crate::script_runner::ScriptStats;
+use crate::challenges::{Challenge, ChallengeRule};
+use crate::scoring::Par;
 use crate::simulation::{Actor, AsteroidWarning, DataPoint, Enemy, EnergyCell, Orientation};
 use crate::simulation::{Goal, Obstacle, Player, State};
 use crate::state_maker::StateMaker;
@@ -127,18 +128,22 @@ impl Level for EnemiesAndAsteroids {
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Complete the objective in 21 or fewer steps.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(26),
+            energy_used: Some(22),
+            code_len: Some(289),
+        })
     }
-    fn check_challenge(&self, _states: &[State], _script: &str, stats: &ScriptStats) -> bool {
-        stats.time_taken <= 21
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![ChallengeRule::MaxSteps(21)]))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{constants::ERR_DESTROYED_BY_ENEMY, levels::Outcome};
+    use crate::levels::{FailureReason, Outcome};
 
     #[test]
     fn level() {
@@ -180,6 +185,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
 
         // This is the "short way". Trying to take the shortest
         // path should result in failure.
@@ -207,7 +213,7 @@ mod tests {
             .unwrap();
         assert_eq!(
             result.outcome,
-            Outcome::Failure(ERR_DESTROYED_BY_ENEMY.into())
+            Outcome::Failure(FailureReason::DestroyedByEnemy)
         );
 
         // This is the "wrong way". Trying to go in the opposite of
@@ -239,7 +245,7 @@ mod tests {
             .unwrap();
         assert_eq!(
             result.outcome,
-            Outcome::Failure(ERR_DESTROYED_BY_ENEMY.into())
+            Outcome::Failure(FailureReason::DestroyedByEnemy)
         );
     }
 
@@ -1,5 +1,7 @@
 use super::{std_check_win, Level, Outcome};
 use crate::actors::{Bounds, EvilRoverActor};
+use crate::challenges::{Challenge, ChallengeRule};
+use crate::scoring::Par;
 use crate::simulation::{Actor, Enemy, Goal, Obstacle, Orientation, Player, State};
 
 #[derive(Copy, Clone)]
@@ -49,24 +51,22 @@ move_forward(3);
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Reach the goal in 17 or fewer steps.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(18),
+            energy_used: Some(15),
+            code_len: Some(85),
+        })
     }
-    fn check_challenge(
-        &self,
-        _states: &[State],
-        _script: &str,
-        stats: &crate::script_runner::ScriptStats,
-    ) -> bool {
-        stats.time_taken <= 17
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![ChallengeRule::MaxSteps(17)]))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::constants::ERR_DESTROYED_BY_ENEMY;
-    use crate::levels::Outcome;
+    use crate::levels::{FailureReason, Outcome};
 
     #[test]
     fn level() {
@@ -81,7 +81,7 @@ mod tests {
             .unwrap();
         assert_eq!(
             result.outcome,
-            Outcome::Failure(String::from(ERR_DESTROYED_BY_ENEMY))
+            Outcome::Failure(FailureReason::DestroyedByEnemy)
         );
 
         // Running this code should result in Outcome::Success.
@@ -97,6 +97,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
     }
 
     #[test]
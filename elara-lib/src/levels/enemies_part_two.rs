@@ -1,5 +1,7 @@
 use super::{std_check_win, Level, Outcome};
 use crate::actors::{Bounds, EvilRoverActor};
+use crate::challenges::{Challenge, ChallengeRule};
+use crate::scoring::Par;
 use crate::simulation::{Actor, Enemy, EnergyCell, Goal, Obstacle, Orientation, Player, State};
 
 #[derive(Copy, Clone)]
@@ -77,24 +79,22 @@ impl Level for EnemiesPartTwo {
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Reach the goal using 7 energy or less.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(20),
+            energy_used: Some(15),
+            code_len: Some(158),
+        })
     }
-    fn check_challenge(
-        &self,
-        _states: &[State],
-        _script: &str,
-        stats: &crate::script_runner::ScriptStats,
-    ) -> bool {
-        stats.energy_used <= 7
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![ChallengeRule::MaxEnergy(7)]))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::constants::ERR_DESTROYED_BY_ENEMY;
-    use crate::levels::Outcome;
+    use crate::levels::{FailureReason, Outcome};
 
     #[test]
     fn level() {
@@ -122,7 +122,7 @@ mod tests {
             .unwrap();
         assert_eq!(
             result.outcome,
-            Outcome::Failure(ERR_DESTROYED_BY_ENEMY.into())
+            Outcome::Failure(FailureReason::DestroyedByEnemy)
         );
 
         // Running this code should result in Outcome::Success.
@@ -143,6 +143,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
     }
 
     #[test]
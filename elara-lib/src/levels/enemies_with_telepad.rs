@@ -1,6 +1,8 @@
 use super::{make_all_initial_states_for_telepads, std_check_win, Level, Outcome};
 use crate::actors::{Bounds, EvilRoverActor};
+use crate::challenges::{Challenge, ChallengeRule};
 use crate::constants::{HEIGHT, WIDTH};
+use crate::scoring::Par;
 use crate::simulation::{
     Actor, Enemy, EnergyCell, Goal, Obstacle, Orientation, Player, State, Telepad,
 };
@@ -81,8 +83,18 @@ impl Level for EnemiesWithTelepad {
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Reach the goal without picking up any energy cells.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(21),
+            energy_used: Some(16),
+            code_len: Some(259),
+        })
+    }
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![ChallengeRule::Custom {
+            id: "level.enemies_with_telepad.challenge",
+            description: "reach the goal without picking up any energy cells",
+        }]))
     }
     fn check_challenge(
         &self,
@@ -97,8 +109,7 @@ impl Level for EnemiesWithTelepad {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::constants::ERR_DESTROYED_BY_ENEMY;
-    use crate::levels::Outcome;
+    use crate::levels::{FailureReason, Outcome};
 
     #[test]
     fn level() {
@@ -137,6 +148,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
 
         // Trying to go straight for the goal should result in the rover
         // being destroyed.
@@ -148,7 +160,7 @@ mod tests {
             .unwrap();
         assert_eq!(
             result.outcome,
-            Outcome::Failure(ERR_DESTROYED_BY_ENEMY.into())
+            Outcome::Failure(FailureReason::DestroyedByEnemy)
         );
     }
 
@@ -1,8 +1,7 @@
 use super::{std_check_win, Level, Outcome};
-use crate::{
-    script_runner::ScriptStats,
-    simulation::{Actor, EnergyCell, Goal, Obstacle, Orientation, Player, Pos, State},
-};
+use crate::challenges::{Challenge, ChallengeRule};
+use crate::scoring::Par;
+use crate::simulation::{Actor, EnergyCell, Goal, Obstacle, Orientation, Player, Pos, State};
 
 #[derive(Copy, Clone)]
 pub struct EnergyPartOne {}
@@ -67,18 +66,22 @@ move_forward(4);
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Complete the objective in 11 or fewer steps.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(13),
+            energy_used: Some(10),
+            code_len: Some(85),
+        })
     }
-    fn check_challenge(&self, _states: &[State], _script: &str, stats: &ScriptStats) -> bool {
-        stats.time_taken <= 11
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![ChallengeRule::MaxSteps(11)]))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::levels::{Outcome, ERR_OUT_OF_ENERGY};
+    use crate::levels::{FailureReason, Outcome};
 
     #[test]
     fn level() {
@@ -91,10 +94,7 @@ mod tests {
         let result = game
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
-        assert_eq!(
-            result.outcome,
-            Outcome::Failure(String::from(ERR_OUT_OF_ENERGY))
-        );
+        assert_eq!(result.outcome, Outcome::Failure(FailureReason::OutOfEnergy));
 
         // Running this code should result in Outcome::Success.
         let script = r"
@@ -108,6 +108,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
 
         // Player should not be able to move past the obstacles for this level.
         let script = r"
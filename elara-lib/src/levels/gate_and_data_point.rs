@@ -1,4 +1,5 @@
 use super::{std_check_win, Level, Outcome};
+use crate::scoring::Par;
 use crate::simulation::{
     Actor, DataPoint, GateVariant, Goal, Obstacle, Orientation, PasswordGate, Player, Pos, State,
 };
@@ -65,13 +66,21 @@ let password = read_data();
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(7),
+            energy_used: Some(5),
+            code_len: Some(87),
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::constants::ERR_NO_DATA_POINT;
-    use crate::levels::Outcome;
+    use crate::better_errors::RuntimeErrorKind;
+    use crate::i18n::translate;
+    use crate::levels::{FailureReason, Outcome};
 
     #[test]
     fn level() {
@@ -97,6 +106,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
         // Regression check for a bug where read_data was not correctly
         // adding a position to result.positions, resulting in a length
         // mismatch.
@@ -110,15 +120,17 @@ mod tests {
         let result = game
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
-        // result.outcome should be Outcome::Failure and the message
-        // should contain ERR_NO_DATA_POINT.
-        match result.outcome {
-            Outcome::Failure(msg) => {
-                assert!(msg.to_string().contains(ERR_NO_DATA_POINT));
-            }
+        // result.outcome should be Outcome::Failure because of a runtime
+        // error which says there is no data point.
+        let err = match result.outcome {
+            Outcome::Failure(FailureReason::RuntimeError(err)) => err,
             _ => {
                 panic!("Expected Outcome::Failure, got {:?}", result.outcome);
             }
-        }
+        };
+        assert_eq!(err.kind, RuntimeErrorKind::NoDataPoint);
+        assert!(err.message.contains(&translate("err_no_data_point", &[])));
+        // The error should point to the call to read_data.
+        assert_eq!((err.line, err.col), (Some(2), Some(28)));
     }
 }
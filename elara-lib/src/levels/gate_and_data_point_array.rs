@@ -1,4 +1,5 @@
 use super::{std_check_win, Level, Outcome};
+use crate::scoring::Par;
 use crate::simulation::{
     Actor, DataPoint, GateVariant, Goal, Obstacle, Orientation, PasswordGate, Player, State,
 };
@@ -71,6 +72,13 @@ let array = read_data();
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(7),
+            energy_used: Some(5),
+            code_len: Some(68),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -101,5 +109,6 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
     }
 }
@@ -1,7 +1,8 @@
 use super::{std_check_win, Level, Outcome};
-use crate::{
-    script_runner::ScriptStats,
-    simulation::{Actor, GateVariant, Goal, Obstacle, Orientation, PasswordGate, Player, State},
+use crate::challenges::{Challenge, ChallengeRule};
+use crate::scoring::Par;
+use crate::simulation::{
+    Actor, GateVariant, Goal, Obstacle, Orientation, PasswordGate, Player, State,
 };
 
 #[derive(Copy, Clone)]
@@ -55,19 +56,22 @@ move_forward(2);
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Code length must be 40 characters or less.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(8),
+            energy_used: Some(7),
+            code_len: Some(48),
+        })
     }
-    fn check_challenge(&self, _states: &[State], _script: &str, stats: &ScriptStats) -> bool {
-        stats.code_len <= 40
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![ChallengeRule::MaxCodeLen(40)]))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::constants::ERR_OUT_OF_ENERGY;
-    use crate::levels::Outcome;
+    use crate::levels::{FailureReason, Outcome};
 
     #[test]
     fn level() {
@@ -87,6 +91,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
 
         // Attempting to reach the goal without saying the password should
         // result in running out of energy.
@@ -94,10 +99,7 @@ mod tests {
         let result = game
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
-        assert_eq!(
-            result.outcome,
-            Outcome::Failure(ERR_OUT_OF_ENERGY.to_string())
-        );
+        assert_eq!(result.outcome, Outcome::Failure(FailureReason::OutOfEnergy));
 
         // Saying the wrong password should not open the gate.
         let script = r#"move_forward(2); say("wrong password"); move_forward(5);"#;
@@ -0,0 +1,103 @@
+use super::{std_check_win, Level, Outcome};
+use crate::scoring::Par;
+use crate::simulation::{
+    Actor, Crate, CrateColor, Goal, Hazard, HazardKind, Obstacle, Orientation, Player, State,
+};
+use crate::state_maker::StateMaker;
+
+#[derive(Copy, Clone)]
+pub struct HazardsPartOne {}
+
+impl Level for HazardsPartOne {
+    fn name(&self) -> &'static str {
+        "Mind the Gap"
+    }
+    fn short_name(&self) -> &'static str {
+        "hazards_part_one"
+    }
+    fn objective(&self) -> &'static str {
+        "Move the rover ({robot}) to the goal ({goal}) without falling into the crater."
+    }
+    fn initial_code(&self) -> &'static str {
+        r#"// There's a crater blocking the way to the goal! If you drop a
+// crate into the crater, it will fill it in and the rover can
+// drive safely over it.
+//
+// CHANGE THE CODE BELOW:
+move_forward(11);
+"#
+    }
+    fn initial_states(&self) -> Vec<State> {
+        vec![StateMaker::new()
+            .with_player(Player::new(0, 3, 20, Orientation::Right))
+            .with_goals(vec![Goal::new(11, 3)])
+            .with_crates(vec![Crate::new(2, 2, CrateColor::Blue)])
+            .with_hazards(vec![
+                Hazard::new(5, 3, HazardKind::Crater),
+                Hazard::new(5, 2, HazardKind::Crater),
+                Hazard::new(5, 4, HazardKind::Crater),
+            ])
+            .with_obstacles(vec![
+                Obstacle::new(4, 1),
+                Obstacle::new(5, 1),
+                Obstacle::new(6, 1),
+                Obstacle::new(4, 5),
+                Obstacle::new(5, 5),
+                Obstacle::new(6, 5),
+            ])
+            .build()]
+    }
+    fn actors(&self) -> Vec<Box<dyn Actor>> {
+        vec![]
+    }
+    fn check_win(&self, state: &State) -> Outcome {
+        std_check_win(state)
+    }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(15),
+            energy_used: Some(11),
+            code_len: Some(90),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::{FailureReason, Outcome};
+
+    #[test]
+    fn level() {
+        let mut game = crate::Game::new();
+        const LEVEL: &'static dyn Level = &HazardsPartOne {};
+
+        // Running the initial code should result in falling into the crater.
+        let script = LEVEL.initial_code();
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(
+            result.outcome,
+            Outcome::Failure(FailureReason::Hazard(HazardKind::Crater))
+        );
+
+        // Filling in the crater with the crate should result in Outcome::Success.
+        let script = r"
+            move_forward(2);
+            turn_left();
+            pick_up();
+            turn_right();
+            move_forward(2);
+            drop();
+            move_forward(7);
+        ";
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
+        let last_state = result.states.last().unwrap();
+        assert_eq!(last_state.hazards[0].bridged_by, Some(0));
+    }
+}
@@ -1,4 +1,5 @@
 use super::{std_check_win, Level, Outcome};
+use crate::scoring::Par;
 use crate::simulation::{Actor, EnergyCell, Goal, Obstacle, Orientation, Player, State};
 
 #[derive(Copy, Clone)]
@@ -67,13 +68,19 @@ impl Level for LoopsPartOne {
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(29),
+            energy_used: Some(15),
+            code_len: Some(63),
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::constants::ERR_OUT_OF_ENERGY;
-    use crate::levels::Outcome;
+    use crate::levels::{FailureReason, Outcome};
 
     #[test]
     fn level() {
@@ -86,10 +93,7 @@ mod tests {
         let result = game
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
-        assert_eq!(
-            result.outcome,
-            Outcome::Failure(String::from(ERR_OUT_OF_ENERGY))
-        );
+        assert_eq!(result.outcome, Outcome::Failure(FailureReason::OutOfEnergy));
 
         // Running this code should result in Outcome::Success.
         let script = r"loop {
@@ -102,5 +106,6 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
     }
 }
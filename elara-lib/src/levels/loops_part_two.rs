@@ -1,10 +1,9 @@
-use rhai::Engine;
-
 use super::{std_check_win, Level, Outcome};
+use crate::challenges::{Challenge, ChallengeRule, Construct};
+use crate::scoring::Par;
 use crate::{
     actors::{Bounds, EvilRoverActor},
     constants::{HEIGHT, WIDTH},
-    script_runner::ScriptStats,
     simulation::{Actor, Enemy, EnergyCell, Goal, Obstacle, Orientation, Player, Pos, State},
 };
 
@@ -85,21 +84,18 @@ impl Level for LoopsPartTwo {
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Have a code length of 84 characters or less and don't use a loop.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(24),
+            energy_used: Some(15),
+            code_len: Some(63),
+        })
     }
-    fn check_challenge(&self, _states: &[State], script: &str, stats: &ScriptStats) -> bool {
-        if stats.code_len > 84 {
-            return false;
-        }
-        // Script cannot contain the key words "loop", "for", "while", or "do".
-        // Strip the comments first, then use a regex to check for keywords.
-        if let Ok(script) = Engine::new().compact_script(script) {
-            let loop_regex = regex::Regex::new(r"\b(loop|for|while|do)\b").unwrap();
-            return !loop_regex.is_match(script.as_str());
-        }
-        // Otherwise, challenge is considered not passed.
-        false
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![
+            ChallengeRule::MaxCodeLen(84),
+            ChallengeRule::ForbidConstruct(Construct::Loop),
+        ]))
     }
 }
 
@@ -133,6 +129,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
     }
 
     #[test]
@@ -0,0 +1,98 @@
+use super::{make_asteroid_shower_states, std_check_win, AsteroidShower, Level, Outcome};
+use crate::actors::{AsteroidActor, Bounds};
+use crate::scoring::Par;
+use crate::simulation::{Actor, Goal, Orientation, Player, Pos, State};
+use crate::state_maker::StateMaker;
+
+#[derive(Copy, Clone)]
+pub struct MeteorShower {}
+
+impl Level for MeteorShower {
+    fn name(&self) -> &'static str {
+        "Meteor Shower"
+    }
+    fn short_name(&self) -> &'static str {
+        "meteor_shower"
+    }
+    fn objective(&self) -> &'static str {
+        "Move the rover ({robot}) to the goal ({goal})."
+    }
+    fn initial_code(&self) -> &'static str {
+        r"// Asteroids are falling all over the place! They land in
+// different spots every time, but there is always a safe path
+// without any warnings. Stick to it and you'll be fine.
+move_forward(6);
+"
+    }
+    fn initial_states(&self) -> Vec<State> {
+        let base_state = StateMaker::new()
+            .with_player(Player::new(1, 7, 20, Orientation::Up))
+            .with_goals(vec![Goal::new(10, 1)])
+            .build();
+        let mut safe_path = vec![];
+        for y in 1..7 {
+            safe_path.push(Pos::new(1, y));
+        }
+        for x in 2..10 {
+            safe_path.push(Pos::new(x, 1));
+        }
+        make_asteroid_shower_states(
+            vec![base_state],
+            &AsteroidShower {
+                region: Bounds::default(),
+                min_hits: 6,
+                max_hits: 10,
+                min_steps: 2,
+                max_steps: 10,
+                safe_path,
+                variants: 4,
+                seed: 42,
+            },
+        )
+    }
+    fn actors(&self) -> Vec<Box<dyn Actor>> {
+        vec![Box::new(AsteroidActor::new())]
+    }
+    fn check_win(&self, state: &State) -> Outcome {
+        std_check_win(state)
+    }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(16),
+            energy_used: Some(15),
+            code_len: Some(45),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::Outcome;
+
+    #[test]
+    fn level() {
+        let mut game = crate::Game::new();
+        const LEVEL: &'static dyn Level = &MeteorShower {};
+
+        // Running the initial code should result in Outcome::Continue.
+        let script = LEVEL.initial_code();
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(result.outcome, Outcome::Continue);
+
+        // Following the safe path should result in Outcome::Success no matter
+        // where the asteroids land.
+        let script = r"
+            move_forward(6);
+            turn_right();
+            move_forward(9);
+        ";
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
+    }
+}
@@ -4,6 +4,7 @@ mod big_enemy;
 mod button_and_gate;
 mod button_and_gate_part_two;
 mod buttons_part_one;
+mod charging_pads_part_one;
 mod crates_part_one;
 mod crates_part_three;
 mod crates_part_two;
@@ -19,27 +20,50 @@ mod gate_and_data_point_array;
 mod gate_and_data_point_three;
 mod gate_and_data_point_two;
 mod gates;
+mod hazards_part_one;
 mod loops_part_one;
 mod loops_part_two;
+mod meteor_shower;
 mod movement;
 mod movement_part_two;
+mod multiple_rovers_part_one;
 mod partly_disabled_movement;
+mod practice;
 mod reimplement_turn_right;
 mod sandbox;
 mod sandbox_with_data_point;
 mod server_room;
+mod stealth_part_one;
 mod telepad_and_button_gate;
 mod telepad_part_one;
 mod telepad_part_two;
 mod telepads_and_while_loop;
+mod turrets_part_one;
 mod variables_intro;
+mod versus_race;
 
-use crate::actors::{AsteroidActor, BigEnemyActor, Bounds, EvilRoverActor, BIG_ENEMY_SIZE};
-use crate::constants::{ERR_DESTROYED_BY_ENEMY, ERR_OUT_OF_ENERGY, HEIGHT, WIDTH};
+pub use practice::{
+    generate_practice_level, DifficultyProfile, PracticeLevel, PRACTICE_LEVEL_SHORT_NAME,
+};
+
+use crate::actors::{
+    AsteroidActor, BigEnemyActor, Bounds, EnergyCosts, EvilRoverActor, TurretActor,
+};
+use crate::better_errors::RuntimeError;
+use crate::challenges::Challenge;
+use crate::constants::{HEIGHT, WIDTH};
+use crate::i18n::translate;
+use crate::objectives::Objective;
+use crate::scoring::Par;
 use crate::script_runner::ScriptStats;
 use crate::simulation::State;
-use crate::simulation::{Actor, Orientation, Pos};
+use crate::simulation::{
+    Actor, AsteroidAnimState, AsteroidWarning, HazardEffect, HazardKind, Orientation, Player, Pos,
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
 use std::collections::HashMap;
+use std::ops::Deref;
+use std::rc::Rc;
 
 #[derive(PartialEq, Clone, Debug)]
 pub enum Outcome {
@@ -48,11 +72,67 @@ pub enum Outcome {
     // The win condition was met.
     Success,
     // Some failure condition was reached.
-    Failure(String),
+    Failure(FailureReason),
     // Used for levels without any set objective.
     NoObjective,
 }
 
+/// The reason why a level was failed. This is only turned into a message for
+/// the player at the very end (i.e. when the result is sent to the UI), so
+/// everything else can check the reason without comparing strings.
+#[derive(PartialEq, Clone, Debug)]
+pub enum FailureReason {
+    // All the rovers ran out of energy.
+    OutOfEnergy,
+    // A rover was destroyed by an enemy.
+    DestroyedByEnemy,
+    // A rover was crushed by an asteroid.
+    CrushedByAsteroid,
+    // A rover was hit by a turret's laser beam.
+    HitByTurret,
+    // A rover drove into a hazard which causes a failure.
+    Hazard(HazardKind),
+    // The script stopped running because of an error.
+    RuntimeError(RuntimeError),
+    // A failure condition specific to one level, along with the message.
+    Custom(String),
+}
+
+impl FailureReason {
+    /// Returns a stable ID for the failure reason which the UI can use to
+    /// decide what to show.
+    pub fn id(&self) -> &'static str {
+        match self {
+            FailureReason::OutOfEnergy => "out_of_energy",
+            FailureReason::DestroyedByEnemy => "destroyed_by_enemy",
+            FailureReason::CrushedByAsteroid => "crushed_by_asteroid",
+            FailureReason::HitByTurret => "hit_by_turret",
+            FailureReason::Hazard(HazardKind::Crater) => "fell_into_crater",
+            FailureReason::Hazard(HazardKind::Acid) => "dissolved_by_acid",
+            FailureReason::Hazard(HazardKind::ElectrifiedFloor) => "electrocuted",
+            FailureReason::RuntimeError(_) => "runtime_error",
+            FailureReason::Custom(_) => "custom",
+        }
+    }
+}
+
+impl std::fmt::Display for FailureReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message_id = match self {
+            FailureReason::RuntimeError(err) => return write!(f, "{}", err.message),
+            FailureReason::Custom(msg) => return write!(f, "{}", msg),
+            FailureReason::OutOfEnergy => "err_out_of_energy",
+            FailureReason::DestroyedByEnemy => "err_destroyed_by_enemy",
+            FailureReason::CrushedByAsteroid => "err_crushed_by_asteroid",
+            FailureReason::HitByTurret => "err_hit_by_turret",
+            FailureReason::Hazard(HazardKind::Crater) => "err_fell_into_crater",
+            FailureReason::Hazard(HazardKind::Acid) => "err_dissolved_by_acid",
+            FailureReason::Hazard(HazardKind::ElectrifiedFloor) => "err_electrocuted",
+        };
+        write!(f, "{}", translate(message_id, &[]))
+    }
+}
+
 pub enum LevelStyle {
     // Default style used for most levels. Lunar landscape background.
     Default,
@@ -91,14 +171,57 @@ pub trait Level {
     fn disabled_functions(&self) -> &'static Vec<&'static str> {
         &EMPTY_VEC
     }
-    fn challenge(&self) -> Option<&'static str> {
+    fn energy_costs(&self) -> EnergyCosts {
+        EnergyCosts::default()
+    }
+    /// Levels can optionally describe their win condition declaratively. If
+    /// this returns Some, the level is won as soon as the objective is
+    /// complete and check_win is only used for failure conditions (see
+    /// failure_check_win and objective_check_win).
+    fn objectives(&self) -> Option<Objective> {
+        None
+    }
+    /// Par values used to award stars (see scoring::Par). Levels without par
+    /// values don't award any stars.
+    fn par(&self) -> Option<Par> {
+        None
+    }
+    fn challenge(&self) -> Option<Challenge> {
         None
     }
+    /// Evaluates any ChallengeRule::Custom rules in the level's challenge.
+    /// Other rules are evaluated automatically.
     fn check_challenge(&self, _states: &[State], _script: &str, _stats: &ScriptStats) -> bool {
         false
     }
 }
 
+/// A level which can be loaded into a Simulation. Most levels are built in
+/// and live for the whole program, but generated levels (e.g. practice levels)
+/// are owned by the Game and shared with the simulation.
+#[derive(Clone)]
+pub enum LevelRef<'a> {
+    Borrowed(&'a dyn Level),
+    Shared(Rc<dyn Level>),
+}
+
+impl<'a> Deref for LevelRef<'a> {
+    type Target = dyn Level + 'a;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            LevelRef::Borrowed(level) => *level,
+            LevelRef::Shared(level) => level.as_ref(),
+        }
+    }
+}
+
+impl<'a> From<&'a dyn Level> for LevelRef<'a> {
+    fn from(level: &'a dyn Level) -> Self {
+        LevelRef::Borrowed(level)
+    }
+}
+
 /// Checks that the level is valid.
 fn validate_level(level: &dyn Level) {
     // Check that the level has a name.
@@ -166,6 +289,14 @@ fn validate_level(level: &dyn Level) {
         "BigEnemies",
         "BigEnemyActors",
     );
+
+    // If the level has Turrets, it must also have TurretActors.
+    check_entities_and_actors::<TurretActor>(
+        level,
+        |state| !state.turrets.is_empty(),
+        "Turrets",
+        "TurretActors",
+    );
 }
 
 // Special constants for sandbox levels. Used in some tests.
@@ -222,6 +353,12 @@ lazy_static! {
         m.insert(crates_part_one::CratesPartOne{}.short_name(), Box::new(crates_part_one::CratesPartOne{}));
         m.insert(crates_part_two::CratesPartTwo{}.short_name(), Box::new(crates_part_two::CratesPartTwo{}));
         m.insert(crates_part_three::CratesPartThree{}.short_name(), Box::new(crates_part_three::CratesPartThree{}));
+        m.insert(stealth_part_one::StealthPartOne{}.short_name(), Box::new(stealth_part_one::StealthPartOne{}));
+        m.insert(turrets_part_one::TurretsPartOne{}.short_name(), Box::new(turrets_part_one::TurretsPartOne{}));
+        m.insert(meteor_shower::MeteorShower{}.short_name(), Box::new(meteor_shower::MeteorShower{}));
+        m.insert(multiple_rovers_part_one::MultipleRoversPartOne{}.short_name(), Box::new(multiple_rovers_part_one::MultipleRoversPartOne{}));
+        m.insert(charging_pads_part_one::ChargingPadsPartOne{}.short_name(), Box::new(charging_pads_part_one::ChargingPadsPartOne{}));
+        m.insert(hazards_part_one::HazardsPartOne{}.short_name(), Box::new(hazards_part_one::HazardsPartOne{}));
 
 
         // Validate all the levels.
@@ -231,50 +368,169 @@ lazy_static! {
 
         m
     };
+
+    /// Levels which are designed for versus matches between two scripts. These
+    /// are kept separate from LEVELS since they are not part of the regular
+    /// progression.
+    pub static ref VERSUS_LEVELS: HashMap<&'static str, Box<dyn Level + Sync>> = {
+        let mut m: HashMap<&'static str, Box<dyn Level + Sync>> = HashMap::new();
+        m.insert(versus_race::VersusRace{}.short_name(), Box::new(versus_race::VersusRace{}));
+
+        for (_, level) in m.iter() {
+            validate_level(level.as_ref());
+            // Each script in a versus match needs its own rover to control.
+            for state in level.initial_states() {
+                assert!(state.num_rovers() >= crate::versus::NUM_VERSUS_ROVERS);
+            }
+        }
+
+        m
+    };
 }
 
-fn is_destroyed_by_enemy(state: &State) -> bool {
+fn is_enemy_at(state: &State, pos: &Pos) -> bool {
     // First check for regular sized enemies.
-    if state
+    state
         .enemies
         .iter()
-        .any(|enemy| enemy.pos == state.player.pos)
-    {
-        return true;
-    }
+        .any(|enemy| !enemy.destroyed && enemy.pos == *pos)
+        // Then check for big enemies (which can take up more than one space).
+        || state
+            .big_enemies
+            .iter()
+            .any(|big_enemy| !big_enemy.destroyed && big_enemy.occupies(pos))
+}
 
-    // Then check for big enemies (which are size 3x3).
-    for big_enemy in &state.big_enemies {
-        for x in big_enemy.pos.x..big_enemy.pos.x + BIG_ENEMY_SIZE {
-            for y in big_enemy.pos.y..big_enemy.pos.y + BIG_ENEMY_SIZE {
-                if state.player.pos == (Pos { x, y }) {
-                    return true;
-                }
-            }
-        }
+fn is_asteroid_impact_at(state: &State, pos: &Pos) -> bool {
+    state.asteroids.iter().any(|asteroid| {
+        asteroid.anim_state == AsteroidAnimState::RecentlyHitGround && asteroid.pos == *pos
+    })
+}
+
+fn is_beam_hit_at(state: &State, pos: &Pos) -> bool {
+    state
+        .turrets
+        .iter()
+        .any(|turret| turret.hit_player && turret.beam.last() == Some(pos))
+}
+
+/// Returns the kind of hazard at the given position, if there is an active
+/// hazard there which causes a failure.
+fn hazard_failure_at(state: &State, pos: &Pos) -> Option<HazardKind> {
+    state
+        .hazards
+        .iter()
+        .find(|hazard| {
+            hazard.pos == *pos && hazard.is_active() && hazard.effect == HazardEffect::Fail
+        })
+        .map(|hazard| hazard.kind)
+}
+
+/// Returns the kind of hazard if any of the rovers drove into a deadly hazard.
+fn hazard_failure(state: &State) -> Option<HazardKind> {
+    state
+        .rovers()
+        .find_map(|rover| hazard_failure_at(state, &rover.pos))
+}
+
+fn is_destroyed_by_enemy(state: &State) -> bool {
+    state.rovers().any(|rover| is_enemy_at(state, &rover.pos))
+}
+
+fn is_crushed_by_asteroid(state: &State) -> bool {
+    state
+        .rovers()
+        .any(|rover| is_asteroid_impact_at(state, &rover.pos))
+}
+
+fn is_hit_by_turret(state: &State) -> bool {
+    state.turrets.iter().any(|turret| turret.hit_player)
+}
+
+/// Returns the reason why the given rover can no longer continue, if any.
+/// Unlike std_check_win, this only considers a single rover, which is useful
+/// when rovers are competing against each other (e.g. in versus mode).
+pub fn rover_failure(state: &State, rover: &Player) -> Option<FailureReason> {
+    if is_enemy_at(state, &rover.pos) {
+        Some(FailureReason::DestroyedByEnemy)
+    } else if is_asteroid_impact_at(state, &rover.pos) {
+        Some(FailureReason::CrushedByAsteroid)
+    } else if is_beam_hit_at(state, &rover.pos) {
+        Some(FailureReason::HitByTurret)
+    } else if let Some(kind) = hazard_failure_at(state, &rover.pos) {
+        Some(FailureReason::Hazard(kind))
+    } else if rover.energy == 0 {
+        Some(FailureReason::OutOfEnergy)
+    } else {
+        None
     }
-    false
 }
 
+/// Returns true if the given rover is on one of the goals.
+pub fn is_on_goal(state: &State, rover: &Player) -> bool {
+    state.goals.iter().any(|goal| goal.pos == rover.pos)
+}
+
+/// Returns true if any of the rovers has reached a goal.
 fn did_reach_goal(state: &State) -> bool {
-    for goal in state.goals.iter() {
-        if state.player.pos == goal.pos {
-            return true;
-        }
+    state.rovers().any(|rover| is_on_goal(state, rover))
+}
+
+/// Returns true if every rover is out of energy. In levels with more than one
+/// rover, the remaining rovers may still be able to complete the objective
+/// after one of them runs out.
+fn is_out_of_energy(state: &State) -> bool {
+    state.rovers().all(|rover| rover.energy == 0)
+}
+
+/// An implementation of Level::check_win which only checks for failure
+/// conditions which end the level immediately (e.g. being destroyed by an
+/// enemy). Used by levels with objectives, which determine success instead.
+pub fn failure_check_win(state: &State) -> Outcome {
+    if is_destroyed_by_enemy(state) {
+        Outcome::Failure(FailureReason::DestroyedByEnemy)
+    } else if is_crushed_by_asteroid(state) {
+        Outcome::Failure(FailureReason::CrushedByAsteroid)
+    } else if is_hit_by_turret(state) {
+        Outcome::Failure(FailureReason::HitByTurret)
+    } else if let Some(kind) = hazard_failure(state) {
+        Outcome::Failure(FailureReason::Hazard(kind))
+    } else {
+        Outcome::Continue
     }
-    false
 }
 
 /// An implementation of Level::check_win which covers some common
 /// success and failure cases. Some levels may need to implement
 /// their own logic on top of this.
 pub fn std_check_win(state: &State) -> Outcome {
-    if is_destroyed_by_enemy(state) {
-        Outcome::Failure(ERR_DESTROYED_BY_ENEMY.to_string())
-    } else if did_reach_goal(state) {
+    match failure_check_win(state) {
+        Outcome::Continue => {}
+        outcome => return outcome,
+    }
+    if did_reach_goal(state) {
+        Outcome::Success
+    } else if is_out_of_energy(state) {
+        Outcome::Failure(FailureReason::OutOfEnergy)
+    } else {
+        Outcome::Continue
+    }
+}
+
+/// Checks for win or lose conditions in levels with objectives. states is the
+/// full history so far, including the latest state. Failures reported by the
+/// level's check_win take priority, then the objective, and finally running
+/// out of energy.
+pub fn objective_check_win(level: &dyn Level, objective: &Objective, states: &[State]) -> Outcome {
+    let state = states.last().unwrap();
+    match level.check_win(state) {
+        Outcome::Continue => {}
+        outcome => return outcome,
+    }
+    if objective.is_complete(states) {
         Outcome::Success
-    } else if state.player.energy == 0 {
-        Outcome::Failure(ERR_OUT_OF_ENERGY.to_string())
+    } else if is_out_of_energy(state) {
+        Outcome::Failure(FailureReason::OutOfEnergy)
     } else {
         Outcome::Continue
     }
@@ -284,13 +540,179 @@ pub fn std_check_win(state: &State) -> Outcome {
 /// explicit objective. Some levels may need to implement
 /// their own logic on top of this.
 pub fn no_objective_check_win(state: &State) -> Outcome {
-    if state.player.energy == 0 {
-        Outcome::Failure(ERR_OUT_OF_ENERGY.to_string())
+    if is_crushed_by_asteroid(state) {
+        Outcome::Failure(FailureReason::CrushedByAsteroid)
+    } else if let Some(kind) = hazard_failure(state) {
+        Outcome::Failure(FailureReason::Hazard(kind))
+    } else if is_out_of_energy(state) {
+        Outcome::Failure(FailureReason::OutOfEnergy)
     } else {
         Outcome::NoObjective
     }
 }
 
+/// Configuration for a procedurally generated asteroid shower. See
+/// make_asteroid_shower_states.
+pub struct AsteroidShower {
+    /// The area in which asteroids may fall (inclusive).
+    pub region: Bounds,
+    /// The minimum and maximum number of asteroids that will hit in each
+    /// variant (inclusive).
+    pub min_hits: usize,
+    pub max_hits: usize,
+    /// The minimum and maximum number of steps until each asteroid hits
+    /// (inclusive). Must be at least 2.
+    pub min_steps: u32,
+    pub max_steps: u32,
+    /// Positions which are guaranteed never to be hit. They must form a
+    /// connected path (in any order) from the rover to a goal.
+    pub safe_path: Vec<Pos>,
+    /// The number of variants to generate for each base state.
+    pub variants: usize,
+    /// Seed for the random number generator. The same seed always results in
+    /// the same states, which is important since Level::initial_states may be
+    /// called many times.
+    pub seed: u64,
+}
+
+/// Returns true if a and b are next to each other (not diagonally).
+fn is_adjacent(a: &Pos, b: &Pos) -> bool {
+    a.x.abs_diff(b.x) + a.y.abs_diff(b.y) == 1
+}
+
+/// Returns true if the positions in the given safe path are all connected
+/// and lead from the rover's starting position to one of the goals. The
+/// positions can be in any order and don't need to include the starting
+/// position or the goal itself.
+fn is_valid_safe_path(state: &State, safe_path: &[Pos]) -> bool {
+    // Flood fill from the rover through the safe path.
+    let mut reached = vec![state.player.pos.clone()];
+    let mut i = 0;
+    while i < reached.len() {
+        for pos in safe_path.iter() {
+            if is_adjacent(pos, &reached[i]) && !reached.contains(pos) {
+                reached.push(pos.clone());
+            }
+        }
+        i += 1;
+    }
+    let reaches_goal = state.goals.iter().any(|goal| {
+        reached
+            .iter()
+            .any(|pos| *pos == goal.pos || is_adjacent(pos, &goal.pos))
+    });
+    !safe_path.is_empty() && safe_path.iter().all(|pos| reached.contains(pos)) && reaches_goal
+}
+
+/// Returns true if there is something at the given position which an asteroid
+/// should not land on (e.g. the rover, a goal, or an energy cell).
+fn is_occupied(state: &State, pos: &Pos) -> bool {
+    state.rovers().any(|rover| rover.pos == *pos)
+        || state.goals.iter().any(|goal| goal.pos == *pos)
+        || state.obstacles.iter().any(|obstacle| obstacle.pos == *pos)
+        || state.energy_cells.iter().any(|cell| cell.pos == *pos)
+        || state.buttons.iter().any(|button| button.pos == *pos)
+        || state.gates.iter().any(|gate| gate.pos == *pos)
+        || state.password_gates.iter().any(|gate| gate.pos == *pos)
+        || state
+            .data_points
+            .iter()
+            .any(|data_point| data_point.pos == *pos)
+        || state
+            .telepads
+            .iter()
+            .any(|telepad| telepad.start_pos == *pos || telepad.end_pos == *pos)
+        || state.crates.iter().any(|crt| crt.pos == *pos)
+        || state.enemies.iter().any(|enemy| enemy.pos == *pos)
+        || state
+            .big_enemies
+            .iter()
+            .any(|big_enemy| big_enemy.occupies(pos))
+        || state.turrets.iter().any(|turret| turret.pos == *pos)
+        || state.charging_pads.iter().any(|pad| pad.pos == *pos)
+        || state.hazards.iter().any(|hazard| hazard.pos == *pos)
+}
+
+/// Generates asteroid warnings for each of the given states according to the
+/// given AsteroidShower. Returns shower.variants new states for each of the
+/// given states. In every variant, there is a warning at each position that
+/// is hit in *any* variant (with will_hit set accordingly), so the rover can't
+/// tell the variants apart just by looking at the warnings.
+///
+/// Panics if the safe path is not a connected path from the rover to a goal.
+pub fn make_asteroid_shower_states(states: Vec<State>, shower: &AsteroidShower) -> Vec<State> {
+    assert!(
+        shower.min_hits <= shower.max_hits,
+        "min_hits must be less than or equal to max_hits"
+    );
+    assert!(
+        shower.min_steps >= 2 && shower.min_steps <= shower.max_steps,
+        "min_steps must be at least 2 and less than or equal to max_steps"
+    );
+    let mut rng = StdRng::seed_from_u64(shower.seed);
+    let mut new_states = vec![];
+    for state in states.iter() {
+        assert!(
+            is_valid_safe_path(state, &shower.safe_path),
+            "safe_path must be a connected path from the rover to a goal"
+        );
+
+        // Asteroids can fall anywhere in the region except on the safe path
+        // or on top of anything else (e.g. the rover, goals, or obstacles).
+        let mut candidates = vec![];
+        for y in shower.region.min_y..=shower.region.max_y {
+            for x in shower.region.min_x..=shower.region.max_x {
+                let pos = Pos::new(x, y);
+                if shower.safe_path.contains(&pos) || is_occupied(state, &pos) {
+                    continue;
+                }
+                candidates.push(pos);
+            }
+        }
+        assert!(
+            candidates.len() >= shower.max_hits,
+            "Not enough room in the region for {} asteroids",
+            shower.max_hits
+        );
+
+        // Each position gets the same timing in every variant.
+        let timings: Vec<u32> = candidates
+            .iter()
+            .map(|_| rng.gen_range(shower.min_steps..=shower.max_steps))
+            .collect();
+
+        let variant_hits: Vec<Vec<usize>> = (0..shower.variants)
+            .map(|_| {
+                let count = rng.gen_range(shower.min_hits..=shower.max_hits);
+                let mut hits: Vec<usize> = (0..candidates.len())
+                    .collect::<Vec<_>>()
+                    .choose_multiple(&mut rng, count)
+                    .cloned()
+                    .collect();
+                hits.sort_unstable();
+                hits
+            })
+            .collect();
+        let mut all_hits: Vec<usize> = variant_hits.iter().flatten().cloned().collect();
+        all_hits.sort_unstable();
+        all_hits.dedup();
+
+        for hits in variant_hits.iter() {
+            let mut new_state = state.clone();
+            for i in all_hits.iter() {
+                new_state.asteroid_warnings.push(AsteroidWarning::new(
+                    candidates[*i].x as u32,
+                    candidates[*i].y as u32,
+                    timings[*i],
+                    hits.contains(i),
+                ));
+            }
+            new_states.push(new_state);
+        }
+    }
+    new_states
+}
+
 /// Expects an array of initial states for a level where each state
 /// has only one possible orientation for each telepad. Expands the
 /// possible states to include all possible orientations for each
@@ -363,7 +785,10 @@ pub fn make_all_initial_states_for_telepads(states: Vec<State>) -> Vec<State> {
 mod tests {
     use super::*;
     use crate::{
-        simulation::{Orientation, Telepad},
+        simulation::{
+            Asteroid, BigEnemy, Button, ButtonConnection, Crate, CrateColor, Enemy, EnergyCell,
+            Goal, Hazard, HazardKind, Orientation, OrientationWithDiagonals, Player, Pos, Telepad,
+        },
         state_maker::StateMaker,
     };
 
@@ -481,4 +906,239 @@ mod tests {
         assert_eq!(full_initial_states.len(), expected.len());
         assert_eq!(full_initial_states, expected);
     }
+
+    #[test]
+    fn test_is_destroyed_by_big_enemy() {
+        // A 2x4 enemy facing up takes up x in 3..5 and y in 2..6.
+        let mut state = StateMaker::new()
+            .with_player(Player::new(4, 5, 10, Orientation::Up))
+            .with_big_enemies(vec![BigEnemy::new_with_size(
+                3,
+                2,
+                OrientationWithDiagonals::Up,
+                2,
+                4,
+            )])
+            .build();
+        assert!(is_destroyed_by_enemy(&state));
+        state.player.pos = Pos::new(5, 5);
+        assert!(!is_destroyed_by_enemy(&state));
+
+        // When facing right, the width and height are swapped, so it takes up
+        // x in 3..7 and y in 2..4.
+        state.big_enemies[0].facing = OrientationWithDiagonals::Right;
+        assert!(!is_destroyed_by_enemy(&state));
+        state.player.pos = Pos::new(6, 3);
+        assert!(is_destroyed_by_enemy(&state));
+    }
+
+    #[test]
+    fn test_std_check_win_crushed_by_asteroid() {
+        let mut state = StateMaker::new()
+            .with_player(Player::new(2, 2, 10, Orientation::Up))
+            .with_asteroids(vec![Asteroid::new(2, 2, AsteroidAnimState::Falling)])
+            .build();
+
+        // Standing under a falling asteroid is fine as long as the rover moves
+        // away before it lands.
+        assert_eq!(std_check_win(&state), Outcome::Continue);
+
+        state.asteroids[0].anim_state = AsteroidAnimState::RecentlyHitGround;
+        assert_eq!(
+            std_check_win(&state),
+            Outcome::Failure(FailureReason::CrushedByAsteroid)
+        );
+        assert_eq!(
+            no_objective_check_win(&state),
+            Outcome::Failure(FailureReason::CrushedByAsteroid)
+        );
+    }
+
+    #[test]
+    fn test_std_check_win_hazards() {
+        let mut state = StateMaker::new()
+            .with_player(Player::new(2, 2, 10, Orientation::Up))
+            .with_hazards(vec![
+                Hazard::new(2, 2, HazardKind::Acid),
+                Hazard::new(3, 2, HazardKind::ElectrifiedFloor),
+            ])
+            .build();
+        assert_eq!(
+            std_check_win(&state),
+            Outcome::Failure(FailureReason::Hazard(HazardKind::Acid))
+        );
+        assert_eq!(
+            no_objective_check_win(&state),
+            Outcome::Failure(FailureReason::Hazard(HazardKind::Acid))
+        );
+
+        // Hazards which only drain energy don't cause a failure by themselves.
+        state.player.pos = Pos::new(3, 2);
+        assert_eq!(std_check_win(&state), Outcome::Continue);
+
+        // Neither do hazards which have been bridged.
+        state.player.pos = Pos::new(2, 2);
+        state.hazards[0].bridged_by = Some(0);
+        assert_eq!(std_check_win(&state), Outcome::Continue);
+    }
+
+    #[test]
+    fn test_levels_have_par() {
+        // Sandboxes and demos don't have an objective, so they can't award
+        // any stars.
+        let no_par = ["sandbox", "sandbox_with_data_point", "data_point_demo"];
+        for (name, level) in LEVELS.iter() {
+            assert_eq!(level.par().is_some(), !no_par.contains(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_failure_reason_message() {
+        assert_eq!(FailureReason::OutOfEnergy.id(), "out_of_energy");
+        assert_eq!(
+            FailureReason::OutOfEnergy.to_string(),
+            "G.R.O.V.E.R. ran out of energy!"
+        );
+        let reason = FailureReason::Hazard(HazardKind::ElectrifiedFloor);
+        assert_eq!(reason.id(), "electrocuted");
+        assert_eq!(
+            reason.to_string(),
+            "G.R.O.V.E.R. was fried by an electrified floor. Try again!"
+        );
+        let reason = FailureReason::Custom(String::from("The gate closed."));
+        assert_eq!(reason.id(), "custom");
+        assert_eq!(reason.to_string(), "The gate closed.");
+    }
+
+    #[test]
+    fn test_make_asteroid_shower_states() {
+        let base_state = StateMaker::new()
+            .with_player(Player::new(0, 0, 10, Orientation::Right))
+            .with_goals(vec![Goal::new(4, 0)])
+            .build();
+        let safe_path = vec![Pos::new(1, 0), Pos::new(2, 0), Pos::new(3, 0)];
+        let shower = AsteroidShower {
+            region: Bounds::new(0, 4, 0, 4),
+            min_hits: 3,
+            max_hits: 5,
+            min_steps: 2,
+            max_steps: 6,
+            safe_path: safe_path.clone(),
+            variants: 5,
+            seed: 1,
+        };
+        let states = make_asteroid_shower_states(vec![base_state.clone()], &shower);
+        assert_eq!(states.len(), 5);
+
+        // The same seed should always result in the same states.
+        assert_eq!(
+            states,
+            make_asteroid_shower_states(vec![base_state], &shower)
+        );
+
+        for state in states.iter() {
+            // Every variant should have warnings in the same positions with
+            // the same timing. Only will_hit should be different.
+            assert_eq!(
+                state.asteroid_warnings.len(),
+                states[0].asteroid_warnings.len()
+            );
+            for (warning, first_warning) in state
+                .asteroid_warnings
+                .iter()
+                .zip(states[0].asteroid_warnings.iter())
+            {
+                assert_eq!(warning.pos, first_warning.pos);
+                assert_eq!(warning.steps_until_impact, first_warning.steps_until_impact);
+                assert!(warning.steps_until_impact >= 2 && warning.steps_until_impact <= 6);
+                assert!(!safe_path.contains(&warning.pos));
+                assert_ne!(warning.pos, Pos::new(0, 0));
+                assert_ne!(warning.pos, Pos::new(4, 0));
+            }
+            let hits = state
+                .asteroid_warnings
+                .iter()
+                .filter(|warning| warning.will_hit)
+                .count();
+            assert!((3..=5).contains(&hits));
+        }
+    }
+
+    #[test]
+    fn test_make_asteroid_shower_states_occupied() {
+        // Asteroids should never land on top of anything.
+        let base_state = StateMaker::new()
+            .with_player(Player::new(0, 0, 10, Orientation::Right))
+            .with_goals(vec![Goal::new(2, 0)])
+            .with_energy_cells(vec![EnergyCell::new(0, 1)])
+            .with_buttons(vec![Button::new(1, 1, ButtonConnection::None)])
+            .with_telepads(vec![Telepad::new((2, 1), (0, 2), Orientation::Up)])
+            .with_crates(vec![Crate::new(1, 2, CrateColor::Red)])
+            .with_enemies(vec![Enemy::new(2, 2, Orientation::Up)])
+            .build();
+        let shower = AsteroidShower {
+            region: Bounds::new(0, 2, 0, 3),
+            min_hits: 3,
+            max_hits: 3,
+            min_steps: 2,
+            max_steps: 2,
+            safe_path: vec![Pos::new(1, 0)],
+            variants: 1,
+            seed: 1,
+        };
+        let states = make_asteroid_shower_states(vec![base_state], &shower);
+        let mut positions: Vec<Pos> = states[0]
+            .asteroid_warnings
+            .iter()
+            .map(|warning| warning.pos.clone())
+            .collect();
+        positions.sort_by_key(|pos| pos.x);
+        assert_eq!(
+            positions,
+            vec![Pos::new(0, 3), Pos::new(1, 3), Pos::new(2, 3)]
+        );
+    }
+
+    #[test]
+    fn test_is_valid_safe_path() {
+        let state = StateMaker::new()
+            .with_player(Player::new(0, 0, 10, Orientation::Right))
+            .with_goals(vec![Goal::new(2, 1)])
+            .build();
+        assert!(is_valid_safe_path(
+            &state,
+            &[Pos::new(1, 0), Pos::new(1, 1)]
+        ));
+        assert!(is_valid_safe_path(
+            &state,
+            &[
+                Pos::new(0, 0),
+                Pos::new(1, 0),
+                Pos::new(2, 0),
+                Pos::new(2, 1)
+            ]
+        ));
+        // Empty paths don't lead anywhere.
+        assert!(!is_valid_safe_path(&state, &[]));
+        // Paths with gaps are not connected.
+        assert!(!is_valid_safe_path(
+            &state,
+            &[Pos::new(1, 0), Pos::new(3, 0), Pos::new(2, 1)]
+        ));
+        // The order of the positions doesn't matter.
+        assert!(is_valid_safe_path(
+            &state,
+            &[Pos::new(1, 1), Pos::new(1, 0)]
+        ));
+        // Paths must start next to the rover.
+        assert!(!is_valid_safe_path(
+            &state,
+            &[Pos::new(2, 0), Pos::new(3, 0), Pos::new(3, 1)]
+        ));
+        // Paths must end next to a goal.
+        assert!(!is_valid_safe_path(
+            &state,
+            &[Pos::new(0, 1), Pos::new(0, 2), Pos::new(0, 3)]
+        ));
+    }
 }
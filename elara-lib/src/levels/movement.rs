@@ -1,4 +1,5 @@
 use super::{std_check_win, Level, Outcome};
+use crate::scoring::Par;
 use crate::simulation::{Actor, Goal, Obstacle, Orientation, Player, State};
 
 #[derive(Copy, Clone)]
@@ -48,13 +49,19 @@ move_forward(1);
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(7),
+            energy_used: Some(6),
+            code_len: Some(45),
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::constants::ERR_OUT_OF_ENERGY;
-    use crate::levels::Outcome;
+    use crate::levels::{FailureReason, Outcome};
 
     #[test]
     fn level() {
@@ -67,6 +74,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Continue);
+        assert_eq!(result.stars, Some(0));
 
         // Running this code should result in Outcome::Success.
         let script = "move_forward(3); turn_right(); move_forward(3);";
@@ -74,6 +82,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
 
         // Running this code should result in Outcome::Failure due to running out
         // of energy.
@@ -87,10 +96,7 @@ mod tests {
         let result = game
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
-        assert_eq!(
-            result.outcome,
-            Outcome::Failure(String::from(ERR_OUT_OF_ENERGY))
-        );
+        assert_eq!(result.outcome, Outcome::Failure(FailureReason::OutOfEnergy));
 
         // Player should not be able to move past the obstacles for this level.
         // First try moving too far right. This should still be a success because
@@ -100,6 +106,8 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        // Bumping into the wall takes extra steps and energy.
+        assert_eq!(result.stars, Some(2));
 
         // Now try moving too far down.
         let script = "turn_right(); move_forward(5); turn_left(); move_forward(3);";
@@ -124,7 +132,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
-        // In this case, we don't reach the objective so we expect ERR_OUT_OF_ENERGY.
+        // In this case, we don't reach the objective so we expect FailureReason::OutOfEnergy.
         let script = r"while (true) {
                 move_forward(1);
                 move_backward(1);
@@ -135,9 +143,6 @@ mod tests {
         let result = game
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
-        assert_eq!(
-            result.outcome,
-            Outcome::Failure(String::from(ERR_OUT_OF_ENERGY))
-        );
+        assert_eq!(result.outcome, Outcome::Failure(FailureReason::OutOfEnergy));
     }
 }
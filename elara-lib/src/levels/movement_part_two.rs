@@ -1,8 +1,7 @@
 use super::{std_check_win, Level, Outcome};
-use crate::{
-    script_runner::ScriptStats,
-    simulation::{Actor, Goal, Obstacle, Orientation, Player, State},
-};
+use crate::challenges::{Challenge, ChallengeRule};
+use crate::scoring::Par;
+use crate::simulation::{Actor, Goal, Obstacle, Orientation, Player, State};
 
 #[derive(Copy, Clone)]
 pub struct MovementPartTwo {}
@@ -52,11 +51,15 @@ impl Level for MovementPartTwo {
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Code length must be 40 characters or less.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(11),
+            energy_used: Some(9),
+            code_len: Some(72),
+        })
     }
-    fn check_challenge(&self, _states: &[State], _script: &str, stats: &ScriptStats) -> bool {
-        stats.code_len <= 40
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![ChallengeRule::MaxCodeLen(40)]))
     }
 }
 
@@ -0,0 +1,143 @@
+use super::{std_check_win, Level, Outcome};
+use crate::scoring::Par;
+use crate::simulation::{
+    Actor, Button, ButtonConnection, Gate, GateVariant, Goal, Obstacle, Orientation, Player, State,
+};
+use crate::state_maker::StateMaker;
+
+#[derive(Copy, Clone)]
+pub struct MultipleRoversPartOne {}
+
+impl Level for MultipleRoversPartOne {
+    fn name(&self) -> &'static str {
+        "Teamwork"
+    }
+    fn short_name(&self) -> &'static str {
+        "multiple_rovers_part_one"
+    }
+    fn objective(&self) -> &'static str {
+        "Move either rover ({robot}) to the goal ({goal})."
+    }
+    fn initial_code(&self) -> &'static str {
+        r#"// There are two rovers in this level. G.R.O.V.E.R. is rover 0
+// and its helper is rover 1. The helper can't reach the goal,
+// but it is right next to the button which unlocks the gate.
+//
+// You can control a specific rover like this:
+//
+//     rover(1).move_forward(1);
+//
+// CHANGE THE CODE BELOW:
+move_forward(6);
+"#
+    }
+    fn initial_states(&self) -> Vec<State> {
+        let mut obstacles: Vec<Obstacle> = (0..12).map(|x| Obstacle::new(x, 6)).collect();
+        obstacles.push(Obstacle::new(7, 7));
+        vec![StateMaker::new()
+            .with_player(Player::new(0, 7, 10, Orientation::Right))
+            .with_extra_rovers(vec![Player::new(1, 5, 10, Orientation::Right)])
+            .with_goals(vec![Goal::new(6, 7)])
+            .with_buttons(vec![Button::new_with_info(
+                3,
+                5,
+                ButtonConnection::Gate(0),
+                "Press this button to unlock the gate.".into(),
+            )])
+            .with_gates(vec![Gate::new_with_info(
+                4,
+                7,
+                false,
+                GateVariant::NESW,
+                "This gate can be unlocked by pressing the button above.".into(),
+            )])
+            .with_obstacles(obstacles)
+            .build()]
+    }
+    fn actors(&self) -> Vec<Box<dyn Actor>> {
+        vec![]
+    }
+    fn check_win(&self, state: &State) -> Outcome {
+        std_check_win(state)
+    }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(8),
+            energy_used: Some(6),
+            code_len: Some(65),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::translate;
+    use crate::levels::{FailureReason, Outcome};
+
+    #[test]
+    fn level() {
+        let mut game = crate::Game::new();
+        const LEVEL: &'static dyn Level = &MultipleRoversPartOne {};
+
+        // Running the initial code should result in Outcome::Continue.
+        let script = LEVEL.initial_code();
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(result.outcome, Outcome::Continue);
+
+        // Using the helper rover to press the button should result in
+        // Outcome::Success.
+        let script = r"
+            rover(1).move_forward(1);
+            rover(1).press_button();
+            move_forward(6);
+        ";
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
+        let last_state = result.states.last().unwrap();
+        assert_eq!(last_state.extra_rovers[0].pos.x, 2);
+        assert_eq!(last_state.extra_rovers[0].energy, 9);
+        assert_eq!(last_state.player.energy, 4);
+        // Method calls on a rover should still be traced one line per step.
+        assert_eq!(result.trace.len(), result.states.len());
+        assert_eq!(result.trace[1], vec![2]);
+        assert_eq!(result.trace[2], vec![3]);
+
+        // The same thing can be done by selecting the active rover.
+        let script = r"
+            select_rover(1);
+            move_forward(1);
+            press_button();
+            select_rover(0);
+            move_forward(6);
+        ";
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(result.outcome, Outcome::Success);
+    }
+
+    #[test]
+    fn no_such_rover() {
+        let mut game = crate::Game::new();
+        const LEVEL: &'static dyn Level = &MultipleRoversPartOne {};
+
+        let script = r"
+            rover(2).move_forward(1);
+        ";
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        match result.outcome {
+            Outcome::Failure(FailureReason::RuntimeError(err)) => {
+                assert!(err.message.contains(&translate("err_no_rover", &[])))
+            }
+            outcome => panic!("unexpected outcome: {:?}", outcome),
+        }
+    }
+}
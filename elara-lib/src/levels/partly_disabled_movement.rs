@@ -1,4 +1,5 @@
 use super::{std_check_win, Level, Outcome};
+use crate::scoring::Par;
 use crate::simulation::{Actor, Goal, Obstacle, Orientation, Player, State};
 
 #[derive(Copy, Clone)]
@@ -51,6 +52,13 @@ impl Level for PartlyDisabledMovement {
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(9),
+            energy_used: Some(6),
+            code_len: Some(70),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -82,6 +90,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
 
         // Trying to use move_forward or turn_right should result in
         // an error.
@@ -92,11 +101,13 @@ mod tests {
         ";
         let result = game.run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string());
         assert!(result.is_err());
-        assert!(result.err().unwrap().to_string().contains("move_forward"));
+        assert!(result.err().unwrap()[0]
+            .to_string()
+            .contains("move_forward"));
 
         let script = r"turn_right();";
         let result = game.run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string());
         assert!(result.is_err());
-        assert!(result.err().unwrap().to_string().contains("turn_right"));
+        assert!(result.err().unwrap()[0].to_string().contains("turn_right"));
     }
 }
@@ -0,0 +1,560 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+use std::sync::mpsc;
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use super::{std_check_win, Level, Outcome};
+use crate::actors::{
+    Action, Bounds, ChaseMode, EvilRoverActor, MoveDirection, PlayerChannelActor, TurnDirection,
+};
+use crate::constants::{HEIGHT, WIDTH};
+use crate::scoring::Par;
+use crate::simulation::{
+    get_adjacent_button, Actor, Button, ButtonConnection, Enemy, EnemyVision, EnergyCell, Gate,
+    GateVariant, Goal, Obstacle, Orientation, Player, Pos, Simulation, State,
+};
+use crate::state_maker::StateMaker;
+
+/// The short name shared by all generated practice levels.
+pub const PRACTICE_LEVEL_SHORT_NAME: &str = "practice";
+
+/// The maximum number of random layouts to try before giving up.
+const MAX_ATTEMPTS: u64 = 500;
+
+/// Describes how difficult a generated practice level should be.
+#[derive(Clone, Debug)]
+pub struct DifficultyProfile {
+    /// The size of the area the rover can move around in. Must fit within
+    /// WIDTH x HEIGHT. The rest of the grid is filled with obstacles.
+    pub width: u32,
+    pub height: u32,
+    pub num_obstacles: usize,
+    pub num_energy_cells: usize,
+    /// The number of closed gates. Each gate comes with a button that opens it.
+    pub num_gates: usize,
+    /// The number of stationary guards. They will chase the rover if they
+    /// spot it.
+    pub num_enemies: usize,
+    pub starting_energy: u32,
+}
+
+impl DifficultyProfile {
+    pub fn easy() -> DifficultyProfile {
+        DifficultyProfile {
+            width: 6,
+            height: 4,
+            num_obstacles: 3,
+            num_energy_cells: 0,
+            num_gates: 0,
+            num_enemies: 0,
+            starting_energy: 20,
+        }
+    }
+
+    pub fn medium() -> DifficultyProfile {
+        DifficultyProfile {
+            width: 9,
+            height: 6,
+            num_obstacles: 8,
+            num_energy_cells: 1,
+            num_gates: 1,
+            num_enemies: 0,
+            starting_energy: 15,
+        }
+    }
+
+    pub fn hard() -> DifficultyProfile {
+        DifficultyProfile {
+            width: WIDTH,
+            height: HEIGHT,
+            num_obstacles: 14,
+            num_energy_cells: 2,
+            num_gates: 2,
+            num_enemies: 1,
+            starting_energy: 15,
+        }
+    }
+
+    /// Returns the profile with the given name ("easy", "medium", or "hard").
+    pub fn from_name(name: &str) -> Option<DifficultyProfile> {
+        match name {
+            "easy" => Some(DifficultyProfile::easy()),
+            "medium" => Some(DifficultyProfile::medium()),
+            "hard" => Some(DifficultyProfile::hard()),
+            _ => None,
+        }
+    }
+}
+
+/// A single step in the solution to a practice level.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SolutionStep {
+    MoveForward,
+    TurnLeft,
+    TurnRight,
+    PressButton,
+}
+
+impl SolutionStep {
+    fn to_action(self) -> Action {
+        match self {
+            SolutionStep::MoveForward => Action::Move(MoveDirection::Forward),
+            SolutionStep::TurnLeft => Action::Turn(TurnDirection::Left),
+            SolutionStep::TurnRight => Action::Turn(TurnDirection::Right),
+            SolutionStep::PressButton => Action::PressButton,
+        }
+    }
+
+    fn func_name(self) -> &'static str {
+        match self {
+            SolutionStep::MoveForward => "move_forward",
+            SolutionStep::TurnLeft => "turn_left",
+            SolutionStep::TurnRight => "turn_right",
+            SolutionStep::PressButton => "press_button",
+        }
+    }
+}
+
+/// A randomly generated level which is guaranteed to be solvable. Used for
+/// endless practice after finishing the main levels.
+#[derive(Clone, Debug)]
+pub struct PracticeLevel {
+    state: State,
+    solution: Vec<SolutionStep>,
+}
+
+impl PracticeLevel {
+    /// Returns a script which solves the level.
+    pub fn solution_script(&self) -> String {
+        let mut script = String::new();
+        let mut i = 0;
+        while i < self.solution.len() {
+            let step = self.solution[i];
+            if step == SolutionStep::MoveForward {
+                let mut spaces = 0;
+                while i < self.solution.len() && self.solution[i] == SolutionStep::MoveForward {
+                    spaces += 1;
+                    i += 1;
+                }
+                script.push_str(&format!("move_forward({});\n", spaces));
+            } else {
+                script.push_str(&format!("{}();\n", step.func_name()));
+                i += 1;
+            }
+        }
+        script
+    }
+
+    /// Returns the names of the functions which are needed to solve the level.
+    pub fn required_functions(&self) -> Vec<&'static str> {
+        let mut funcs: Vec<&'static str> = vec![];
+        for step in self.solution.iter() {
+            if !funcs.contains(&step.func_name()) {
+                funcs.push(step.func_name());
+            }
+        }
+        funcs
+    }
+
+    /// Runs the solution in the simulation and returns true if it reaches the
+    /// goal. This catches anything the solver doesn't account for, like
+    /// running out of energy or being caught by an enemy.
+    fn check_solvable(&self) -> bool {
+        let (tx, rx) = mpsc::channel();
+        let player_actor = PlayerChannelActor::new(Rc::new(RefCell::new(rx)), self.bounds());
+        let mut simulation = Simulation::new(player_actor);
+        simulation.load_level(self as &dyn Level, 0);
+        for step in self.solution.iter() {
+            tx.send(step.to_action()).unwrap();
+            if simulation.step_forward() != Outcome::Continue {
+                break;
+            }
+        }
+        simulation.last_outcome() == Outcome::Success
+    }
+}
+
+impl Level for PracticeLevel {
+    fn name(&self) -> &'static str {
+        "Practice Puzzle"
+    }
+    fn short_name(&self) -> &'static str {
+        PRACTICE_LEVEL_SHORT_NAME
+    }
+    fn objective(&self) -> &'static str {
+        "Move the rover ({robot}) to the goal ({goal})."
+    }
+    fn initial_code(&self) -> &'static str {
+        "// Write some code to move the rover to the goal.\n"
+    }
+    fn initial_states(&self) -> Vec<State> {
+        vec![self.state.clone()]
+    }
+    fn actors(&self) -> Vec<Box<dyn Actor>> {
+        let mut actors: Vec<Box<dyn Actor>> = vec![];
+        for i in 0..self.state.enemies.len() {
+            actors.push(Box::new(EvilRoverActor::new_with_chase_mode(
+                i,
+                Bounds::default(),
+                ChaseMode::ShortestPath,
+            )));
+        }
+        actors
+    }
+    fn check_win(&self, state: &State) -> Outcome {
+        std_check_win(state)
+    }
+    fn par(&self) -> Option<Par> {
+        // The solver always finds the shortest solution, so matching it is
+        // worth full marks.
+        let num_moves = self
+            .solution
+            .iter()
+            .filter(|&&step| step == SolutionStep::MoveForward)
+            .count();
+        Some(Par {
+            time_taken: Some(self.solution.len() as u32),
+            energy_used: Some(num_moves as u32),
+            code_len: Some(self.solution_script().replace('\n', "").len()),
+        })
+    }
+}
+
+/// Generates a random practice level matching the given profile. The same
+/// profile and seed always result in the same level. Returns None if no
+/// solvable level could be found (e.g. because the profile asks for more
+/// things than can fit in the grid).
+pub fn generate_practice_level(profile: &DifficultyProfile, seed: u64) -> Option<PracticeLevel> {
+    assert!(
+        profile.width >= 2
+            && profile.width <= WIDTH
+            && profile.height >= 2
+            && profile.height <= HEIGHT,
+        "Practice level size must be between 2x2 and {}x{}",
+        WIDTH,
+        HEIGHT
+    );
+    for attempt in 0..MAX_ATTEMPTS {
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_mul(MAX_ATTEMPTS).wrapping_add(attempt));
+        let state = match random_state(profile, &mut rng) {
+            Some(state) => state,
+            None => continue,
+        };
+        let solution = match solve(&state) {
+            Some(solution) => solution,
+            None => continue,
+        };
+        let level = PracticeLevel { state, solution };
+        if level.check_solvable() {
+            return Some(level);
+        }
+    }
+    None
+}
+
+/// Returns a random initial state matching the profile. The state might not
+/// be solvable. Returns None if there is not enough room to fit everything.
+fn random_state(profile: &DifficultyProfile, rng: &mut StdRng) -> Option<State> {
+    // Center the playable area in the grid and fill in the rest with obstacles.
+    let min_x = (WIDTH - profile.width) / 2;
+    let min_y = (HEIGHT - profile.height) / 2;
+    let max_x = min_x + profile.width - 1;
+    let max_y = min_y + profile.height - 1;
+    let mut obstacles = vec![];
+    let mut free = vec![];
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            if x < min_x || x > max_x || y < min_y || y > max_y {
+                obstacles.push(Obstacle::new(x, y));
+            } else {
+                free.push((x, y));
+            }
+        }
+    }
+    free.shuffle(rng);
+
+    // Pick the player and goal positions first, making sure they are not too
+    // close together.
+    let (player_x, player_y) = free.pop()?;
+    let min_dist = (profile.width + profile.height) / 2;
+    let goal_index = free
+        .iter()
+        .position(|(x, y)| x.abs_diff(player_x) + y.abs_diff(player_y) >= min_dist)?;
+    let (goal_x, goal_y) = free.remove(goal_index);
+
+    // Keep enemies away from the player's starting position so the rover
+    // isn't spotted right away.
+    let mut enemies = vec![];
+    for _ in 0..profile.num_enemies {
+        let index = free
+            .iter()
+            .position(|(x, y)| x.abs_diff(player_x) + y.abs_diff(player_y) >= 4)?;
+        let (x, y) = free.remove(index);
+        let facing = *[
+            Orientation::Up,
+            Orientation::Down,
+            Orientation::Left,
+            Orientation::Right,
+        ]
+        .choose(rng)
+        .unwrap();
+        enemies.push(Enemy::new_with_vision(
+            x,
+            y,
+            facing,
+            EnemyVision::Cone(2),
+            vec![],
+        ));
+    }
+
+    let needed = profile.num_obstacles + profile.num_energy_cells + profile.num_gates * 2;
+    if free.len() < needed {
+        return None;
+    }
+    for _ in 0..profile.num_obstacles {
+        let (x, y) = free.pop()?;
+        obstacles.push(Obstacle::new(x, y));
+    }
+    let mut energy_cells = vec![];
+    for _ in 0..profile.num_energy_cells {
+        let (x, y) = free.pop()?;
+        energy_cells.push(EnergyCell::new(x, y));
+    }
+    let mut gates = vec![];
+    let mut buttons = vec![];
+    for i in 0..profile.num_gates {
+        let (x, y) = free.pop()?;
+        let variant = if rng.gen_bool(0.5) {
+            GateVariant::NWSE
+        } else {
+            GateVariant::NESW
+        };
+        gates.push(Gate::new(x, y, false, variant));
+        let (x, y) = free.pop()?;
+        buttons.push(Button::new(x, y, ButtonConnection::Gate(i)));
+    }
+
+    Some(
+        StateMaker::new()
+            .with_player(Player::new(
+                player_x,
+                player_y,
+                profile.starting_energy,
+                Orientation::Right,
+            ))
+            .with_goals(vec![Goal::new(goal_x, goal_y)])
+            .with_obstacles(obstacles)
+            .with_energy_cells(energy_cells)
+            .with_gates(gates)
+            .with_buttons(buttons)
+            .with_enemies(enemies)
+            .build(),
+    )
+}
+
+/// The parts of the state which the solver keeps track of.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct SolverNode {
+    pos: Pos,
+    facing: Orientation,
+    /// Bit i is set if gate i is open.
+    open_gates: u32,
+}
+
+/// Returns true if the rover could stand at the given position. To keep things
+/// simple, the solver stays away from enemies entirely.
+fn solver_can_move_to(state: &State, pos: &Pos, open_gates: u32) -> bool {
+    let bounds = Bounds::default();
+    if pos.x < bounds.min_x || pos.x > bounds.max_x || pos.y < bounds.min_y || pos.y > bounds.max_y
+    {
+        return false;
+    }
+    if state.obstacles.iter().any(|o| o.pos == *pos) || state.buttons.iter().any(|b| b.pos == *pos)
+    {
+        return false;
+    }
+    for (i, gate) in state.gates.iter().enumerate() {
+        if gate.pos == *pos && open_gates & (1 << i) == 0 {
+            return false;
+        }
+    }
+    !state
+        .enemies
+        .iter()
+        .any(|enemy| enemy.pos.x.abs_diff(pos.x) + enemy.pos.y.abs_diff(pos.y) <= 1)
+}
+
+/// Uses breadth-first search to find the shortest sequence of steps which
+/// gets the rover to the goal, or None if there is no such sequence.
+fn solve(state: &State) -> Option<Vec<SolutionStep>> {
+    let goal = &state.goals[0].pos;
+    let start = SolverNode {
+        pos: state.player.pos.clone(),
+        facing: state.player.facing,
+        open_gates: 0,
+    };
+    let mut parents: HashMap<SolverNode, Option<(SolverNode, SolutionStep)>> = HashMap::new();
+    parents.insert(start.clone(), None);
+    let mut queue = VecDeque::new();
+    queue.push_back(start);
+
+    while let Some(node) = queue.pop_front() {
+        if node.pos == *goal {
+            // Walk back through the parents to reconstruct the solution.
+            let mut solution = vec![];
+            let mut curr = node;
+            while let Some(Some((parent, step))) = parents.get(&curr) {
+                solution.push(*step);
+                curr = parent.clone();
+            }
+            solution.reverse();
+            return Some(solution);
+        }
+
+        let mut next_nodes = vec![];
+        let forward = match node.facing {
+            Orientation::Up => Pos::new(node.pos.x, node.pos.y - 1),
+            Orientation::Down => Pos::new(node.pos.x, node.pos.y + 1),
+            Orientation::Left => Pos::new(node.pos.x - 1, node.pos.y),
+            Orientation::Right => Pos::new(node.pos.x + 1, node.pos.y),
+        };
+        if solver_can_move_to(state, &forward, node.open_gates) {
+            next_nodes.push((
+                SolverNode {
+                    pos: forward,
+                    ..node.clone()
+                },
+                SolutionStep::MoveForward,
+            ));
+        }
+        next_nodes.push((
+            SolverNode {
+                facing: node.facing.rotate_counter_clockwise(),
+                ..node.clone()
+            },
+            SolutionStep::TurnLeft,
+        ));
+        next_nodes.push((
+            SolverNode {
+                facing: node.facing.rotate_clockwise(),
+                ..node.clone()
+            },
+            SolutionStep::TurnRight,
+        ));
+        if let Some(button_index) = get_adjacent_button(state, &node.pos) {
+            if let ButtonConnection::Gate(gate_index) = state.buttons[button_index].connection {
+                next_nodes.push((
+                    SolverNode {
+                        open_gates: node.open_gates ^ (1 << gate_index),
+                        ..node.clone()
+                    },
+                    SolutionStep::PressButton,
+                ));
+            }
+        }
+
+        for (next, step) in next_nodes {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some((node.clone(), step)));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::LevelRef;
+
+    #[test]
+    fn generate() {
+        for profile in [
+            DifficultyProfile::easy(),
+            DifficultyProfile::medium(),
+            DifficultyProfile::hard(),
+        ] {
+            for seed in 0..5 {
+                let level = generate_practice_level(&profile, seed).unwrap();
+                let state = &level.initial_states()[0];
+                assert_eq!(state.gates.len(), profile.num_gates);
+                assert_eq!(state.buttons.len(), profile.num_gates);
+                assert_eq!(state.enemies.len(), profile.num_enemies);
+                assert_eq!(state.energy_cells.len(), profile.num_energy_cells);
+                assert!(level.required_functions().contains(&"move_forward"));
+
+                // The same seed should always result in the same level.
+                let same_level = generate_practice_level(&profile, seed).unwrap();
+                assert_eq!(same_level.initial_states(), level.initial_states());
+            }
+        }
+    }
+
+    #[test]
+    fn solution_script_reaches_goal() {
+        let mut game = crate::Game::new();
+        let level = generate_practice_level(&DifficultyProfile::medium(), 7).unwrap();
+        let script = level.solution_script();
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LevelRef::Shared(Rc::new(level)), script)
+            .unwrap();
+        assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
+    }
+
+    #[test]
+    fn solution_script() {
+        let level = PracticeLevel {
+            state: State::new(),
+            solution: vec![
+                SolutionStep::MoveForward,
+                SolutionStep::MoveForward,
+                SolutionStep::TurnLeft,
+                SolutionStep::PressButton,
+                SolutionStep::MoveForward,
+            ],
+        };
+        assert_eq!(
+            level.solution_script(),
+            "move_forward(2);\nturn_left();\npress_button();\nmove_forward(1);\n"
+        );
+        assert_eq!(
+            level.required_functions(),
+            vec!["move_forward", "turn_left", "press_button"]
+        );
+    }
+
+    #[test]
+    fn solve_with_gate() {
+        // The only way to the goal is through a gate, so the button needs to be
+        // pressed first.
+        let wall = (0..HEIGHT)
+            .filter(|y| *y != 1)
+            .map(|y| Obstacle::new(1, y))
+            .collect();
+        let state = StateMaker::new()
+            .with_player(Player::new(0, 1, 10, Orientation::Right))
+            .with_goals(vec![Goal::new(2, 1)])
+            .with_obstacles(wall)
+            .with_gates(vec![Gate::new(1, 1, false, GateVariant::NWSE)])
+            .with_buttons(vec![Button::new(0, 0, ButtonConnection::Gate(0))])
+            .build();
+        let solution = solve(&state).unwrap();
+        assert_eq!(
+            solution,
+            vec![
+                SolutionStep::PressButton,
+                SolutionStep::MoveForward,
+                SolutionStep::MoveForward,
+            ]
+        );
+
+        // Without the button, there is no solution.
+        let mut state = state;
+        state.buttons = vec![];
+        assert_eq!(solve(&state), None);
+    }
+}
@@ -1,8 +1,7 @@
 use super::{std_check_win, Level, Outcome};
-use crate::{
-    script_runner::ScriptStats,
-    simulation::{Actor, EnergyCell, Goal, Obstacle, Orientation, Player, State},
-};
+use crate::challenges::{Challenge, ChallengeRule};
+use crate::scoring::Par;
+use crate::simulation::{Actor, EnergyCell, Goal, Obstacle, Orientation, Player, State};
 
 #[derive(Copy, Clone)]
 pub struct ReimplementTurnRight {}
@@ -81,18 +80,22 @@ move_backward(1);
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Code length must be 55 or less.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(26),
+            energy_used: Some(14),
+            code_len: Some(195),
+        })
     }
-    fn check_challenge(&self, _states: &[State], _script: &str, stats: &ScriptStats) -> bool {
-        stats.code_len <= 55
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![ChallengeRule::MaxCodeLen(55)]))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{constants::ERR_OUT_OF_ENERGY, levels::Outcome};
+    use crate::levels::{FailureReason, Outcome};
 
     #[test]
     fn level() {
@@ -104,10 +107,7 @@ mod tests {
         let result = game
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
-        assert_eq!(
-            result.outcome,
-            Outcome::Failure(ERR_OUT_OF_ENERGY.to_string())
-        );
+        assert_eq!(result.outcome, Outcome::Failure(FailureReason::OutOfEnergy));
 
         // This is an example solution that should result in Outcome::Success.
         let script = r"
@@ -131,6 +131,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
     }
 
     #[test]
@@ -50,7 +50,7 @@ impl Level for Sandbox {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{constants::ERR_OUT_OF_ENERGY, levels::Outcome};
+    use crate::levels::{FailureReason, Outcome};
 
     #[test]
     fn level() {
@@ -76,40 +76,28 @@ mod tests {
         let result = game
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
-        assert_eq!(
-            result.outcome,
-            Outcome::Failure(ERR_OUT_OF_ENERGY.to_string())
-        );
+        assert_eq!(result.outcome, Outcome::Failure(FailureReason::OutOfEnergy));
         assert_eq!(result.states.last().unwrap().player.pos.y, -50);
 
         let script = "turn_right(); move_forward(50);";
         let result = game
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
-        assert_eq!(
-            result.outcome,
-            Outcome::Failure(ERR_OUT_OF_ENERGY.to_string())
-        );
+        assert_eq!(result.outcome, Outcome::Failure(FailureReason::OutOfEnergy));
         assert_eq!(result.states.last().unwrap().player.pos.y, 50);
 
         let script = "turn_left(); turn_left(); move_forward(50);";
         let result = game
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
-        assert_eq!(
-            result.outcome,
-            Outcome::Failure(ERR_OUT_OF_ENERGY.to_string())
-        );
+        assert_eq!(result.outcome, Outcome::Failure(FailureReason::OutOfEnergy));
         assert_eq!(result.states.last().unwrap().player.pos.x, -50);
 
         let script = "move_forward(50);";
         let result = game
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
-        assert_eq!(
-            result.outcome,
-            Outcome::Failure(ERR_OUT_OF_ENERGY.to_string())
-        );
+        assert_eq!(result.outcome, Outcome::Failure(FailureReason::OutOfEnergy));
         assert_eq!(result.states.last().unwrap().player.pos.x, 50);
     }
 }
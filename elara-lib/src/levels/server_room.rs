@@ -1,8 +1,8 @@
-use std::collections::HashSet;
-
-use super::{Level, LevelStyle, Outcome};
+use super::{failure_check_win, Level, LevelStyle, Outcome};
+use crate::challenges::{Challenge, ChallengeRule};
+use crate::scoring::Par;
 use crate::{
-    constants::ERR_OUT_OF_ENERGY,
+    objectives::Objective,
     simulation::{
         Actor, Button, ButtonConnection, DataPoint, EnergyCell, Obstacle, ObstacleKind,
         Orientation, Player, State,
@@ -198,41 +198,30 @@ impl Level for ServerRoom {
     fn actors(&self) -> Vec<Box<dyn Actor>> {
         vec![]
     }
+    fn objectives(&self) -> Option<Objective> {
+        // There is no goal to reach. Instead you beat the level by pressing
+        // the button.
+        Some(Objective::PressButton(0))
+    }
     fn check_win(&self, state: &State) -> Outcome {
-        // Note that this level uses a different check_win function. There is not
-        // goal to reach. Instead you beat the level by pressing the button.
-        if state.player.energy == 0 {
-            Outcome::Failure(ERR_OUT_OF_ENERGY.to_string())
-        } else if state.buttons[0].currently_pressed {
-            Outcome::Success
-        } else {
-            Outcome::Continue
-        }
+        failure_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Use the `say` function to read the messages on every data point.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(7),
+            energy_used: Some(6),
+            code_len: Some(31),
+        })
     }
-    fn check_challenge(
-        &self,
-        states: &[State],
-        _script: &str,
-        _stats: &crate::script_runner::ScriptStats,
-    ) -> bool {
-        let mut remaining_messages: HashSet<String> =
-            MESSAGES.iter().map(|s| s.to_string()).collect();
-        for state in states {
-            if remaining_messages.contains(&state.player.message) {
-                remaining_messages.remove(&state.player.message);
-            }
-        }
-        remaining_messages.is_empty()
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![ChallengeRule::SayAllMessages]))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::levels::Outcome;
+    use crate::levels::{FailureReason, Outcome};
 
     #[test]
     fn level() {
@@ -245,6 +234,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Continue);
+        assert!(!result.objective_progress.unwrap().completed);
 
         // Running this code should result in Outcome::Success.
         let script = r"
@@ -255,6 +245,23 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
+        assert!(result.objective_progress.unwrap().completed);
+
+        // Running out of energy before pressing the button should result in
+        // a failure.
+        let script = r"
+            for i in 0..3 {
+                move_forward(2);
+                move_backward(2);
+            }
+            move_forward(6);
+            press_button();
+        ";
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(result.outcome, Outcome::Failure(FailureReason::OutOfEnergy));
     }
 
     #[test]
@@ -0,0 +1,212 @@
+use super::{make_all_initial_states_for_telepads, std_check_win, Level, Outcome};
+use crate::actors::{Bounds, ChaseMode, EvilRoverActor};
+use crate::challenges::{Challenge, ChallengeRule};
+use crate::scoring::Par;
+use crate::simulation::{
+    Actor, Enemy, EnemyVision, Goal, Obstacle, Orientation, Player, State, Telepad,
+};
+use crate::state_maker::StateMaker;
+
+#[derive(Copy, Clone)]
+pub struct StealthPartOne {}
+
+impl Level for StealthPartOne {
+    fn name(&self) -> &'static str {
+        "Sneaking Around"
+    }
+    fn short_name(&self) -> &'static str {
+        "stealth_part_one"
+    }
+    fn objective(&self) -> &'static str {
+        "Move the rover ({robot}) to the goal ({goal})."
+    }
+    fn initial_code(&self) -> &'static str {
+        r"// These malfunctioning rovers can only see what is in front of
+// them. They won't chase G.R.O.V.E.R. until they spot it, so try
+// to find a path that stays out of sight.
+
+// CHANGE THE CODE BELOW
+move_forward(7);
+"
+    }
+    fn initial_states(&self) -> Vec<State> {
+        let base_state = StateMaker::new()
+            .with_player(Player::new(6, 7, 25, Orientation::Up))
+            .with_goals(vec![Goal::new(6, 0)])
+            .with_enemies(vec![
+                // A guard which stands still next to the goal and watches
+                // everything in front of it.
+                Enemy::new_with_vision(6, 2, Orientation::Down, EnemyVision::Cone(4), vec![]),
+                // A guard which patrols back and forth on the right side.
+                Enemy::new_with_vision(
+                    10,
+                    1,
+                    Orientation::Down,
+                    EnemyVision::Line(3),
+                    vec![(10, 6), (10, 1)],
+                ),
+            ])
+            .with_obstacles(vec![
+                Obstacle::new(2, 2),
+                Obstacle::new(2, 3),
+                Obstacle::new(4, 5),
+                Obstacle::new(8, 3),
+                Obstacle::new(8, 4),
+            ])
+            // The guard next to the goal can use this telepad to cut off the
+            // rover if it tries to escape to the left.
+            .with_telepads(vec![Telepad::new((7, 2), (0, 6), Orientation::Right)])
+            .build();
+        make_all_initial_states_for_telepads(vec![base_state])
+    }
+    fn actors(&self) -> Vec<Box<dyn Actor>> {
+        // Once they spot the rover, the guards will find their way around any
+        // obstacles (or take a shortcut through the telepad) in order to catch
+        // it.
+        vec![
+            Box::new(EvilRoverActor::new_with_chase_mode(
+                0,
+                Bounds::default(),
+                ChaseMode::ShortestPathWithTelepads,
+            )),
+            Box::new(EvilRoverActor::new_with_chase_mode(
+                1,
+                Bounds::default(),
+                ChaseMode::ShortestPathWithTelepads,
+            )),
+        ]
+    }
+    fn check_win(&self, state: &State) -> Outcome {
+        std_check_win(state)
+    }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(22),
+            energy_used: Some(19),
+            code_len: Some(86),
+        })
+    }
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![ChallengeRule::Custom {
+            id: "level.stealth_part_one.challenge",
+            description: "reach the goal without any of the rovers spotting you",
+        }]))
+    }
+    fn check_challenge(
+        &self,
+        states: &[State],
+        _script: &str,
+        _stats: &crate::script_runner::ScriptStats,
+    ) -> bool {
+        !states
+            .iter()
+            .any(|state| state.enemies.iter().any(|enemy| enemy.spotted_player))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::{FailureReason, Outcome};
+    use crate::simulation::EnemyAnimState;
+
+    #[test]
+    fn level() {
+        let mut game = crate::Game::new();
+        const LEVEL: &'static dyn Level = &StealthPartOne {};
+
+        // Running the initial code should result in Outcome::Failure due to
+        // being spotted and then destroyed by the guard.
+        let script = LEVEL.initial_code();
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(
+            result.outcome,
+            Outcome::Failure(FailureReason::DestroyedByEnemy)
+        );
+
+        // Sneaking around the edge of the guard's vision should result in
+        // Outcome::Success.
+        let script = r"
+            turn_left();
+            move_forward(6);
+            turn_right();
+            move_forward(7);
+            turn_right();
+            move_forward(6);
+        ";
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
+        assert!(result.passes_challenge);
+        // The patrolling guard should have moved even though it never spotted
+        // the rover.
+        let last_state = result.states.last().unwrap();
+        assert_ne!(last_state.enemies[1].pos, result.states[0].enemies[1].pos);
+    }
+
+    #[test]
+    fn chase_after_spotted() {
+        let mut game = crate::Game::new();
+        const LEVEL: &'static dyn Level = &StealthPartOne {};
+
+        // Waiting out of sight should not cause the guard to move.
+        let script = r"
+            say(1);
+            say(2);
+        ";
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(result.outcome, Outcome::Continue);
+        let last_state = result.states.last().unwrap();
+        assert!(!last_state.enemies[0].spotted_player);
+        assert_eq!(last_state.enemies[0].pos, result.states[0].enemies[0].pos);
+
+        // Stepping into view should cause the guard to start chasing.
+        let script = r"
+            move_forward(1);
+            say(1);
+        ";
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        let last_state = result.states.last().unwrap();
+        assert!(last_state.enemies[0].spotted_player);
+        assert_ne!(last_state.enemies[0].pos, result.states[0].enemies[0].pos);
+    }
+
+    #[test]
+    fn guard_uses_telepad() {
+        let mut game = crate::Game::new();
+        const LEVEL: &'static dyn Level = &StealthPartOne {};
+
+        // After spotting the rover, the guard should take the telepad to cut
+        // it off instead of going the long way around.
+        let script = r"
+            turn_left();
+            move_forward(4);
+            turn_right();
+            move_forward(1);
+            say(1);
+            say(2);
+            say(3);
+            say(4);
+            say(5);
+        ";
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(
+            result.outcome,
+            Outcome::Failure(FailureReason::DestroyedByEnemy)
+        );
+        assert!(result
+            .states
+            .iter()
+            .any(|state| matches!(state.enemies[0].anim_state, EnemyAnimState::Teleporting(_))));
+    }
+}
@@ -1,4 +1,5 @@
 use super::{make_all_initial_states_for_telepads, std_check_win, Level, Outcome};
+use crate::scoring::Par;
 use crate::{
     simulation::{
         Actor, Button, ButtonConnection, EnergyCell, Gate, GateVariant, Goal, Obstacle,
@@ -96,6 +97,13 @@ move_forward(3);
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(22),
+            energy_used: Some(13),
+            code_len: Some(213),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -136,5 +144,6 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
     }
 }
@@ -1,6 +1,6 @@
-use rhai::Engine;
-
 use super::{std_check_win, Level, Outcome};
+use crate::challenges::{Challenge, ChallengeRule, Construct};
+use crate::scoring::Par;
 use crate::simulation::{Actor, Goal, Obstacle, Orientation, Player, State, Telepad};
 use crate::state_maker::StateMaker;
 
@@ -89,25 +89,18 @@ if get_orientation() == "up" {
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Have a code length of 165 or less and do not define any new functions.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(9),
+            energy_used: Some(6),
+            code_len: Some(288),
+        })
     }
-    fn check_challenge(
-        &self,
-        _states: &[State],
-        script: &str,
-        stats: &crate::script_runner::ScriptStats,
-    ) -> bool {
-        if stats.code_len > 165 {
-            return false;
-        }
-        // Script cannot contain the key word "fn".
-        if let Ok(script) = Engine::new().compact_script(script) {
-            let fn_regex = regex::Regex::new(r"\b(fn)\b").unwrap();
-            return !fn_regex.is_match(script.as_str());
-        }
-        // Otherwise, challenge is considered not passed.
-        false
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![
+            ChallengeRule::MaxCodeLen(165),
+            ChallengeRule::ForbidConstruct(Construct::FunctionDefinition),
+        ]))
     }
 }
 
@@ -161,6 +154,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
     }
 
     #[test]
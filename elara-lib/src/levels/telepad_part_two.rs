@@ -1,4 +1,5 @@
 use super::{make_all_initial_states_for_telepads, std_check_win, Level, Outcome};
+use crate::scoring::Par;
 use crate::{
     simulation::{Actor, Goal, Obstacle, Orientation, Player, State, Telepad},
     state_maker::StateMaker,
@@ -66,6 +67,13 @@ impl Level for TelepadPartTwo {
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(12),
+            energy_used: Some(6),
+            code_len: Some(148),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -106,5 +114,6 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
     }
 }
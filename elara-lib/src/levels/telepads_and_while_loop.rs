@@ -1,6 +1,7 @@
 use super::{make_all_initial_states_for_telepads, std_check_win, Level, Outcome};
+use crate::challenges::{Challenge, ChallengeRule};
+use crate::scoring::Par;
 use crate::{
-    script_runner::ScriptStats,
     simulation::{Actor, EnergyCell, Goal, Obstacle, Orientation, Player, State, Telepad},
     state_maker::StateMaker,
 };
@@ -83,11 +84,15 @@ move_forward(3);
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Complete the objective in 17 or fewer steps.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(21),
+            energy_used: Some(14),
+            code_len: Some(151),
+        })
     }
-    fn check_challenge(&self, _states: &[State], _script: &str, stats: &ScriptStats) -> bool {
-        stats.time_taken <= 17
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![ChallengeRule::MaxSteps(17)]))
     }
 }
 
@@ -128,6 +133,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
     }
 }
 
@@ -0,0 +1,87 @@
+use super::{std_check_win, Level, Outcome};
+use crate::actors::{Bounds, TurretActor};
+use crate::scoring::Par;
+use crate::simulation::{Actor, Goal, Obstacle, Orientation, Player, State, Turret};
+use crate::state_maker::StateMaker;
+
+#[derive(Copy, Clone)]
+pub struct TurretsPartOne {}
+
+impl Level for TurretsPartOne {
+    fn name(&self) -> &'static str {
+        "Laser Fence"
+    }
+    fn short_name(&self) -> &'static str {
+        "turrets_part_one"
+    }
+    fn objective(&self) -> &'static str {
+        "Move the rover ({robot}) to the goal ({goal})."
+    }
+    fn initial_code(&self) -> &'static str {
+        r"// The turret at the top of the screen fires a laser beam every
+// 3 steps. It glows right before it fires. Try to time your
+// approach so that you don't get hit.
+
+// CHANGE THE CODE BELOW
+move_forward(8);
+"
+    }
+    fn initial_states(&self) -> Vec<State> {
+        vec![StateMaker::new()
+            .with_player(Player::new(2, 3, 20, Orientation::Right))
+            .with_goals(vec![Goal::new(10, 3)])
+            .with_turrets(vec![Turret::new(5, 0, Orientation::Down, 3, 3, 1)])
+            .with_obstacles(vec![
+                Obstacle::new(4, 2),
+                Obstacle::new(6, 2),
+                Obstacle::new(4, 4),
+                Obstacle::new(6, 4),
+            ])
+            .build()]
+    }
+    fn actors(&self) -> Vec<Box<dyn Actor>> {
+        vec![Box::new(TurretActor::new(0, Bounds::default()))]
+    }
+    fn check_win(&self, state: &State) -> Outcome {
+        std_check_win(state)
+    }
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(9),
+            energy_used: Some(8),
+            code_len: Some(64),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::{FailureReason, Outcome};
+
+    #[test]
+    fn level() {
+        let mut game = crate::Game::new();
+        const LEVEL: &'static dyn Level = &TurretsPartOne {};
+
+        // Running the initial code should result in Outcome::Failure because
+        // the rover drives right into the beam.
+        let script = LEVEL.initial_code();
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(result.outcome, Outcome::Failure(FailureReason::HitByTurret));
+
+        // Waiting for the turret to fire first should result in Outcome::Success.
+        let script = r#"
+            move_forward(2);
+            say("Waiting for the laser...");
+            move_forward(6);
+        "#;
+        let result = game
+            .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
+            .unwrap();
+        assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
+    }
+}
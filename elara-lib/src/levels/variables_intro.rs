@@ -1,4 +1,6 @@
 use super::{std_check_win, Level, Outcome};
+use crate::challenges::{Challenge, ChallengeRule};
+use crate::scoring::Par;
 use crate::simulation::{
     Actor, EnergyCell, GateVariant, Goal, Obstacle, Orientation, PasswordGate, Player, State,
 };
@@ -107,24 +109,22 @@ let password = "supercalifragilisticexpialidocious";
     fn check_win(&self, state: &State) -> Outcome {
         std_check_win(state)
     }
-    fn challenge(&self) -> Option<&'static str> {
-        Some("Reach the goal using 12 energy or less.")
+    fn par(&self) -> Option<Par> {
+        Some(Par {
+            time_taken: Some(16),
+            energy_used: Some(12),
+            code_len: Some(169),
+        })
     }
-    fn check_challenge(
-        &self,
-        _states: &[State],
-        _script: &str,
-        stats: &crate::script_runner::ScriptStats,
-    ) -> bool {
-        stats.energy_used <= 12
+    fn challenge(&self) -> Option<Challenge> {
+        Some(Challenge::new(vec![ChallengeRule::MaxEnergy(12)]))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::constants::ERR_OUT_OF_ENERGY;
-    use crate::levels::Outcome;
+    use crate::levels::{FailureReason, Outcome};
 
     #[test]
     fn level() {
@@ -154,6 +154,7 @@ mod tests {
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
         assert_eq!(result.outcome, Outcome::Success);
+        assert_eq!(result.stars, Some(3));
 
         // An alternative solution.
         let script = r#"
@@ -204,10 +205,7 @@ mod tests {
         let result = game
             .run_player_script_with_all_funcs_unlocked(LEVEL, script.to_string())
             .unwrap();
-        assert_eq!(
-            result.outcome,
-            Outcome::Failure(ERR_OUT_OF_ENERGY.to_string())
-        );
+        assert_eq!(result.outcome, Outcome::Failure(FailureReason::OutOfEnergy));
     }
 
     #[test]
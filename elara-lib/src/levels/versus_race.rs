@@ -0,0 +1,48 @@
+use super::{std_check_win, Level, Outcome};
+use crate::simulation::{Actor, EnergyCell, Goal, Obstacle, Orientation, Player, State};
+use crate::state_maker::StateMaker;
+
+/// A symmetrical board for versus matches. This level is not part of the
+/// regular list of levels.
+#[derive(Copy, Clone)]
+pub struct VersusRace {}
+
+impl Level for VersusRace {
+    fn name(&self) -> &'static str {
+        "Race"
+    }
+    fn short_name(&self) -> &'static str {
+        "versus_race"
+    }
+    fn objective(&self) -> &'static str {
+        "Be the first rover ({robot}) to reach a goal ({goal})."
+    }
+    fn initial_code(&self) -> &'static str {
+        r#"// Your rover starts in one corner and your opponent's rover
+// starts in the opposite corner. Reach a goal before they do!
+//
+// CHANGE THE CODE BELOW:
+move_forward(5);
+"#
+    }
+    fn initial_states(&self) -> Vec<State> {
+        vec![StateMaker::new()
+            .with_player(Player::new(0, 0, 20, Orientation::Right))
+            .with_extra_rovers(vec![Player::new(11, 7, 20, Orientation::Left)])
+            .with_goals(vec![Goal::new(5, 4), Goal::new(6, 3)])
+            .with_energy_cells(vec![EnergyCell::new(2, 5), EnergyCell::new(9, 2)])
+            .with_obstacles(vec![
+                Obstacle::new(3, 1),
+                Obstacle::new(3, 2),
+                Obstacle::new(8, 5),
+                Obstacle::new(8, 6),
+            ])
+            .build()]
+    }
+    fn actors(&self) -> Vec<Box<dyn Actor>> {
+        vec![]
+    }
+    fn check_win(&self, state: &State) -> Outcome {
+        std_check_win(state)
+    }
+}
@@ -8,24 +8,42 @@ extern crate lazy_static;
 
 mod actors;
 mod better_errors;
+mod challenges;
+mod code_metrics;
 mod constants;
+mod diagnostics;
+mod i18n;
 mod js_types;
 mod levels;
+mod lints;
+mod objectives;
+mod scoring;
+mod script_query;
 mod script_runner;
 mod simulation;
 mod state_maker;
+mod suggestions;
+mod versus;
 
 use actors::{Action, Bounds};
-use better_errors::BetterError;
 use constants::{HEIGHT, WIDTH};
-use levels::{Level, Outcome, LEVELS};
+use diagnostics::Diagnostic;
+use levels::{
+    generate_practice_level, DifficultyProfile, Level, LevelRef, Outcome, PracticeLevel, LEVELS,
+    PRACTICE_LEVEL_SHORT_NAME, VERSUS_LEVELS,
+};
 use rand::seq::SliceRandom;
+use rand::Rng;
 use script_runner::{ScriptResult, ScriptRunner};
 use simulation::Simulation;
 use std::cell::RefCell;
 use std::collections::HashSet;
 use std::rc::Rc;
 use std::sync::mpsc;
+use versus::{
+    VersusError, VersusResult, MAX_VERSUS_ROUNDS, MAX_VERSUS_STEPS, NUM_VERSUS_ROVERS,
+    VERSUS_DISABLED_FUNCS,
+};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -33,10 +51,12 @@ use wasm_bindgen::prelude::*;
 /// managing state, running user scripts, and gluing all the pieces
 /// together.
 pub struct Game {
-    simulation: Rc<RefCell<Simulation>>,
+    simulation: Rc<RefCell<Simulation<'static>>>,
     script_runner: ScriptRunner,
     player_action_rx: Rc<RefCell<mpsc::Receiver<Action>>>,
     // player_action_tx: Rc<RefCell<mpsc::Sender<Action>>>,
+    /// The most recently generated practice level (if any).
+    practice_level: Option<Rc<PracticeLevel>>,
 }
 
 #[wasm_bindgen]
@@ -79,6 +99,7 @@ impl Game {
             script_runner,
             player_action_rx,
             // player_action_tx,
+            practice_level: None,
         }
     }
 
@@ -89,21 +110,97 @@ impl Game {
         script: String,
     ) -> Result<js_types::RunResult, JsValue> {
         // Run the script and convert the results to the corresponding JS Types.
-        let level = LEVELS.get(level_name).unwrap();
+        let level = match LEVELS.get(level_name) {
+            Some(level) => LevelRef::Borrowed(level.as_ref()),
+            None if level_name == PRACTICE_LEVEL_SHORT_NAME => match &self.practice_level {
+                Some(level) => LevelRef::Shared(level.clone()),
+                None => {
+                    return Err(JsValue::from(
+                        "new_practice_level must be called before running a practice level",
+                    ))
+                }
+            },
+            None => panic!("Unknown level: {}", level_name),
+        };
         let unlocked_funcs = unlocked_funcs
             .iter()
             .map(|x| x.as_string().unwrap())
             .collect();
-        let result = self.run_player_script_internal(level.as_ref(), &unlocked_funcs, script);
+        let result = self.run_player_script_internal(level, &unlocked_funcs, script);
         match result {
             Ok(result) => Ok(js_types::to_js_run_result(&result)),
-            Err(err) => {
-                let message = err.message;
-                let line = err.line.unwrap_or(0);
-                let col = err.col.unwrap_or(0);
-                Err(JsValue::from(js_types::RhaiError { message, line, col }))
+            Err(errs) => Err(JsValue::from(js_types::RhaiError::from(&errs))),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl Game {
+    /// Generates a new random practice level with the given difficulty ("easy",
+    /// "medium", or "hard"). Afterwards, the level can be run by passing
+    /// "practice" as the level name to run_player_script.
+    pub fn new_practice_level(
+        &mut self,
+        difficulty: &str,
+        seed: u32,
+    ) -> Result<js_types::LevelData, JsValue> {
+        let profile = DifficultyProfile::from_name(difficulty)
+            .ok_or_else(|| JsValue::from(format!("Unknown difficulty: {}", difficulty)))?;
+        let level = generate_practice_level(&profile, seed as u64)
+            .ok_or_else(|| JsValue::from("Could not generate a practice level"))?;
+        let data = js_types::LevelData::from(&level);
+        self.practice_level = Some(Rc::new(level));
+        Ok(data)
+    }
+
+    /// Returns the names of the functions needed to solve the current practice
+    /// level.
+    pub fn practice_level_required_funcs(&self) -> js_sys::Array {
+        let funcs = js_sys::Array::new();
+        if let Some(level) = &self.practice_level {
+            for func in level.required_functions() {
+                funcs.push(&JsValue::from(func));
             }
         }
+        funcs
+    }
+
+    /// Runs two scripts against each other on the given versus level. The first
+    /// script controls rover 0 and the second script controls rover 1. Both
+    /// rovers act in lockstep, one action per step.
+    pub fn run_versus(
+        &mut self,
+        level_name: &str,
+        unlocked_funcs: js_sys::Array,
+        script_a: String,
+        script_b: String,
+    ) -> Result<js_types::VersusResult, JsValue> {
+        let level: &'static dyn Level = match VERSUS_LEVELS.get(level_name) {
+            Some(level) => level.as_ref(),
+            None => {
+                return Err(JsValue::from(format!(
+                    "Unknown versus level: {}",
+                    level_name
+                )))
+            }
+        };
+        let unlocked_funcs = unlocked_funcs
+            .iter()
+            .map(|x| x.as_string().unwrap())
+            .collect();
+        let seed = rand::thread_rng().gen_range(0..level.initial_states().len());
+        let result = self.run_versus_internal(level, &unlocked_funcs, [&script_a, &script_b], seed);
+        match result {
+            Ok(result) => Ok(js_types::VersusResult::from(&result)),
+            Err(err) => Err(JsValue::from(js_types::VersusError::from(&err))),
+        }
+    }
+
+    /// Returns an example solution for the current practice level.
+    pub fn practice_level_solution(&self) -> Option<String> {
+        self.practice_level
+            .as_ref()
+            .map(|level| level.solution_script())
     }
 }
 
@@ -113,7 +210,7 @@ impl Default for Game {
     }
 }
 
-fn get_avail_funcs(level: &'static dyn Level, unlocked_funcs: &Vec<String>) -> Vec<String> {
+fn get_avail_funcs(level: &dyn Level, unlocked_funcs: &Vec<String>) -> Vec<String> {
     // Store avail_funcs in a set
     let mut avail_funcs = HashSet::new();
     for func in unlocked_funcs {
@@ -130,10 +227,11 @@ impl Game {
     /// instead of JS types. This is not expected to be called from Wasm-land.
     fn run_player_script_internal(
         &mut self,
-        level: &'static dyn Level,
+        level: impl Into<LevelRef<'static>>,
         unlocked_funcs: &Vec<String>,
         script: String,
-    ) -> Result<ScriptResult, BetterError> {
+    ) -> Result<ScriptResult, Vec<Diagnostic>> {
+        let level = level.into();
         // Run the simulation multiple times, once for each possible initial
         // state. Return the first result that fails (if any), otherwise return
         // a random successful result.
@@ -145,11 +243,11 @@ impl Game {
         let mut rng = rand::thread_rng();
         seeds.shuffle(&mut rng);
 
-        let avail_funcs = get_avail_funcs(level, unlocked_funcs);
+        let avail_funcs = get_avail_funcs(&*level, unlocked_funcs);
 
         for i in seeds {
             // Reset the simulation and load the level.
-            self.simulation.borrow_mut().load_level(level, i);
+            self.simulation.borrow_mut().load_level(level.clone(), i);
             // Drain the channel.
             while self.player_action_rx.clone().borrow().try_recv().is_ok() {}
             // Run the script.
@@ -165,9 +263,11 @@ impl Game {
                         return Ok(result);
                     }
                 }
-                Err(err) => {
-                    log!("{}", err.message);
-                    return Err(err);
+                Err(errs) => {
+                    for err in errs.iter() {
+                        log!("{}", err.message);
+                    }
+                    return Err(errs);
                 }
             }
         }
@@ -189,16 +289,100 @@ impl Game {
         Ok(successes.first().unwrap().clone())
     }
 
+    /// An internal implementation of run_versus which works with Rust types
+    /// instead of JS types. seed determines which initial state to use.
+    ///
+    /// Rhai scripts can't be paused, so each script is run on its own while the
+    /// other rover replays the actions recorded from the previous run of the
+    /// other script. This is repeated until the recorded actions for both scripts
+    /// stop changing, at which point each script has seen exactly what it would
+    /// have seen if both scripts were running at the same time. For the same
+    /// reason, scripts can only control their own rover (see
+    /// VERSUS_DISABLED_FUNCS).
+    fn run_versus_internal(
+        &mut self,
+        level: &'static dyn Level,
+        unlocked_funcs: &Vec<String>,
+        scripts: [&str; NUM_VERSUS_ROVERS],
+        seed: usize,
+    ) -> Result<VersusResult, VersusError> {
+        let disabled_funcs: Vec<&str> = level
+            .disabled_functions()
+            .iter()
+            .chain(VERSUS_DISABLED_FUNCS)
+            .copied()
+            .collect();
+        let avail_funcs: Vec<String> = get_avail_funcs(level, unlocked_funcs)
+            .into_iter()
+            .filter(|func| !disabled_funcs.contains(&func.as_str()))
+            .collect();
+        let mut actions: Vec<Vec<Action>> = vec![vec![]; NUM_VERSUS_ROVERS];
+        let mut converged = false;
+        let mut total_steps = 0;
+
+        for _ in 0..MAX_VERSUS_ROUNDS {
+            if total_steps > MAX_VERSUS_STEPS {
+                break;
+            }
+            let mut changed = false;
+            for (rover, script) in scripts.iter().enumerate() {
+                {
+                    let mut sim = self.simulation.borrow_mut();
+                    sim.load_level(level, seed);
+                    sim.set_active_rover(rover);
+                    for (other, other_actions) in actions.iter().enumerate() {
+                        if other != rover {
+                            sim.set_replay(other, other_actions.clone());
+                        }
+                    }
+                }
+                // Drain the channel.
+                while self.player_action_rx.clone().borrow().try_recv().is_ok() {}
+                self.script_runner
+                    .run(&avail_funcs, &disabled_funcs, script)
+                    .map_err(|errors| VersusError { rover, errors })?;
+
+                let received = self.simulation.borrow().received_actions();
+                total_steps += received.len();
+                if received != actions[rover] {
+                    actions[rover] = received;
+                    changed = true;
+                }
+            }
+            if !changed {
+                converged = true;
+                break;
+            }
+        }
+
+        // Replay the final actions for both rovers to get the states for the
+        // whole match.
+        let mut sim = self.simulation.borrow_mut();
+        sim.load_level(level, seed);
+        let num_steps = actions.iter().map(|a| a.len()).max().unwrap_or(0);
+        for (rover, rover_actions) in actions.into_iter().enumerate() {
+            sim.set_replay(rover, rover_actions);
+        }
+        for _ in 0..num_steps {
+            let outcome = sim.step_forward();
+            if outcome != Outcome::Continue && outcome != Outcome::NoObjective {
+                break;
+            }
+        }
+        Ok(versus::score_versus(sim.get_history(), converged))
+    }
+
     #[allow(dead_code)]
     /// Like run_player_script_internal but considers all functions to be unlocked.
     /// This is mainly used as a convenience for testing.
     fn run_player_script_with_all_funcs_unlocked(
         &mut self,
-        level: &'static dyn Level,
+        level: impl Into<LevelRef<'static>>,
         script: String,
-    ) -> Result<ScriptResult, BetterError> {
+    ) -> Result<ScriptResult, Vec<Diagnostic>> {
+        let level = level.into();
         let avail_funcs = get_avail_funcs(
-            level,
+            &*level,
             &vec![
                 "move_forward".to_string(),
                 "move_backward".to_string(),
@@ -210,6 +394,10 @@ impl Game {
                 "read_data".to_string(),
                 "pick_up".to_string(),
                 "drop".to_string(),
+                "rover".to_string(),
+                "select_rover".to_string(),
+                "wait".to_string(),
+                "get_energy".to_string(),
             ],
         );
         self.run_player_script_internal(level, &avail_funcs, script)
@@ -230,6 +418,24 @@ pub fn get_level_data() -> js_sys::Object {
     js_types::to_level_data_obj(LEVELS)
 }
 
+#[wasm_bindgen]
+/// Sets the catalog used for player-facing text (e.g. error messages and
+/// level text). The catalog maps message IDs to text, using the same {name}
+/// placeholders as the English messages in i18n.rs. Any message missing from
+/// the catalog falls back to English, so an empty object switches back to
+/// English. Level text is only translated by get_level_data, so it should be
+/// called again afterwards.
+pub fn set_locale_catalog(catalog: js_sys::Object) {
+    let entries = js_sys::Object::entries(&catalog)
+        .iter()
+        .filter_map(|entry| {
+            let entry = js_sys::Array::from(&entry);
+            Some((entry.get(0).as_string()?, entry.get(1).as_string()?))
+        })
+        .collect();
+    i18n::set_catalog(entries);
+}
+
 #[wasm_bindgen]
 pub fn new_pos() -> js_types::Pos {
     js_types::Pos { x: 0, y: 0 }
@@ -0,0 +1,304 @@
+use rhai::{ASTFlags, ASTNode, Engine, Expr, Position, Stmt, Token, AST};
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use crate::better_errors::BetterError;
+use crate::constants::BUILTIN_FUNCTIONS;
+use crate::diagnostics::{raw_engine, tokenize, Diagnostic};
+
+/// Checks the script for common beginner mistakes. Unlike errors, warnings
+/// don't stop the script from running. Returns the warnings ordered by
+/// position, or no warnings if the script can't be compiled.
+pub fn lint(script: &str) -> Vec<Diagnostic> {
+    let engine = raw_engine();
+    let ast = match engine.compile(script) {
+        Ok(ast) => ast,
+        Err(_) => return vec![],
+    };
+
+    let mut warnings = vec![];
+    check_shadowed_builtins(&engine, script, &ast, &mut warnings);
+    // Functions can't access variables from outside of the function, so the
+    // top-level statements and each function body are checked separately.
+    check_statements(ast.statements(), &[], &mut warnings);
+    for func in ast.iter_fn_def() {
+        check_statements(func.body.statements(), &func.params, &mut warnings);
+    }
+
+    let mut diagnostics: Vec<Diagnostic> = warnings
+        .into_iter()
+        .map(|warning| Diagnostic::warning(script, warning))
+        .collect();
+    diagnostics.sort_by_key(|diagnostic| (diagnostic.span.line, diagnostic.span.col));
+    diagnostics
+}
+
+fn warning(code: &'static str, args: &[(&str, &dyn Display)], pos: Position) -> BetterError {
+    BetterError::new(code, args, pos.line(), pos.position())
+}
+
+/// Warns about user-defined functions with the same name as a built-in
+/// function (which replaces the built-in function).
+fn check_shadowed_builtins(
+    engine: &Engine,
+    script: &str,
+    ast: &AST,
+    warnings: &mut Vec<BetterError>,
+) {
+    // Function definitions don't have a position, so we find the name of the
+    // function after the fn keyword instead.
+    let tokens = tokenize(engine, script);
+    for func in ast.iter_fn_def() {
+        if !BUILTIN_FUNCTIONS.contains_key(func.name.as_str()) {
+            continue;
+        }
+        let pos = tokens
+            .windows(2)
+            .find(|pair| {
+                pair[0].0 == Token::Fn
+                    && matches!(&pair[1].0, Token::Identifier(name) if name.as_str() == func.name.as_str())
+            })
+            .map_or(Position::NONE, |pair| pair[1].1);
+        warnings.push(warning(
+            "warn_shadowed_builtin",
+            &[("fn_name", &func.name)],
+            pos,
+        ));
+    }
+}
+
+/// Warns about mistakes in the given statements (including any nested
+/// statements). params are the names of the function parameters, if any.
+fn check_statements(
+    stmts: &[Stmt],
+    params: &[rhai::ImmutableString],
+    warnings: &mut Vec<BetterError>,
+) {
+    check_unreachable(stmts, warnings);
+
+    // Variables declared with let, along with whether they were given a value.
+    let mut declared: Vec<(String, Position, bool)> = vec![];
+    // Variables which are read somewhere (not including assigning to them).
+    let mut used: HashSet<String> = params.iter().map(|param| param.to_string()).collect();
+    // Variables which are assigned to after being declared.
+    let mut assigned: HashSet<String> = HashSet::new();
+    // Variables passed to say (name and position).
+    let mut said: Vec<(String, Position)> = vec![];
+
+    for stmt in stmts {
+        stmt.walk(&mut vec![], &mut |path| {
+            match path.last().unwrap() {
+                ASTNode::Stmt(Stmt::Var(var, ..)) => {
+                    let has_value = !matches!(var.1, Expr::Unit(_));
+                    declared.push((var.0.name.to_string(), var.0.pos, has_value));
+                }
+                ASTNode::Expr(expr @ Expr::Variable(var, ..)) => {
+                    if is_assignment_target(path, expr) {
+                        assigned.insert(var.3.to_string());
+                    } else {
+                        used.insert(var.3.to_string());
+                    }
+                }
+                ASTNode::Stmt(Stmt::FnCall(call, pos))
+                | ASTNode::Expr(Expr::FnCall(call, pos))
+                | ASTNode::Expr(Expr::MethodCall(call, pos)) => {
+                    let name = call.name.as_str();
+                    if name == "move_forward" || name == "move_backward" {
+                        if let Some(steps) = call.args.first().and_then(int_value) {
+                            check_move_steps(name, steps, *pos, warnings);
+                        }
+                    }
+                    if name == "say" {
+                        match call.args.first() {
+                            Some(Expr::Variable(var, _, pos)) => {
+                                said.push((var.3.to_string(), *pos));
+                            }
+                            Some(Expr::Unit(pos)) => {
+                                warnings.push(warning("warn_say_empty", &[], *pos))
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+            true
+        });
+    }
+
+    for (name, pos, has_value) in declared.iter() {
+        if !name.starts_with('_') && !used.contains(name) {
+            warnings.push(warning("warn_unused_variable", &[("var_name", name)], *pos));
+        }
+        if !has_value && !assigned.contains(name) {
+            for (_, say_pos) in said.iter().filter(|(said_name, _)| said_name == name) {
+                warnings.push(warning(
+                    "warn_say_no_value",
+                    &[("var_name", name)],
+                    *say_pos,
+                ));
+            }
+        }
+    }
+}
+
+/// Returns true if expr is the variable being assigned to in an assignment
+/// statement (e.g. x in `x = 5;`).
+fn is_assignment_target(path: &[ASTNode], expr: &Expr) -> bool {
+    match path.len().checked_sub(2).map(|i| &path[i]) {
+        Some(ASTNode::Stmt(Stmt::Assignment(assignment))) => std::ptr::eq(&assignment.1.lhs, expr),
+        _ => false,
+    }
+}
+
+/// Returns the value of the expression if it is an integer literal (including
+/// negative integers).
+fn int_value(expr: &Expr) -> Option<i64> {
+    match expr {
+        Expr::IntegerConstant(value, _) => Some(*value),
+        Expr::FnCall(call, _) if call.name == "-" && call.args.len() == 1 => {
+            int_value(&call.args[0]).map(|value| -value)
+        }
+        _ => None,
+    }
+}
+
+fn check_move_steps(name: &str, steps: i64, pos: Position, warnings: &mut Vec<BetterError>) {
+    let opposite = if name == "move_forward" {
+        "move_backward"
+    } else {
+        "move_forward"
+    };
+    if steps == 0 {
+        warnings.push(warning("warn_move_zero", &[("fn_name", &name)], pos));
+    } else if steps < 0 {
+        warnings.push(warning(
+            "warn_move_negative",
+            &[("fn_name", &name), ("opposite", &opposite)],
+            pos,
+        ));
+    }
+}
+
+/// Warns about statements which come after a loop that never ends (i.e. a
+/// loop without a condition or a break statement).
+fn check_unreachable(stmts: &[Stmt], warnings: &mut Vec<BetterError>) {
+    let mut reported = false;
+    for (i, stmt) in stmts.iter().enumerate() {
+        for block in nested_blocks(stmt) {
+            check_unreachable(block, warnings);
+        }
+        let is_endless = match stmt {
+            Stmt::While(flow, _) => {
+                matches!(flow.expr, Expr::Unit(_)) && !contains_break(flow.body.statements())
+            }
+            _ => false,
+        };
+        if !is_endless || reported {
+            continue;
+        }
+        if let Some(next) = stmts[i + 1..]
+            .iter()
+            .find(|next| !matches!(next, Stmt::Noop(..)))
+        {
+            warnings.push(warning("warn_unreachable_code", &[], next.position()));
+            reported = true;
+        }
+    }
+}
+
+/// Returns the blocks of statements directly inside the given statement.
+fn nested_blocks(stmt: &Stmt) -> Vec<&[Stmt]> {
+    match stmt {
+        Stmt::If(flow, _) | Stmt::While(flow, _) | Stmt::Do(flow, ..) => {
+            vec![flow.body.statements(), flow.branch.statements()]
+        }
+        Stmt::For(for_loop, _) => vec![for_loop.2.body.statements()],
+        Stmt::Block(block) => vec![block.statements()],
+        _ => vec![],
+    }
+}
+
+/// Returns true if the statements contain a break which would exit the
+/// enclosing loop (i.e. not a break inside a nested loop).
+fn contains_break(stmts: &[Stmt]) -> bool {
+    stmts.iter().any(|stmt| match stmt {
+        Stmt::BreakLoop(_, flags, _) => flags.contains(ASTFlags::BREAK),
+        Stmt::If(flow, _) => {
+            contains_break(flow.body.statements()) || contains_break(flow.branch.statements())
+        }
+        Stmt::Block(block) => contains_break(block.statements()),
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(script: &str) -> Vec<(usize, usize, String)> {
+        lint(script)
+            .into_iter()
+            .map(|d| (d.span.line, d.span.col, d.message))
+            .collect()
+    }
+
+    #[test]
+    fn no_warnings() {
+        let script = r#"
+            fn turn_around(times) {
+                turn_left();
+                turn_left();
+            }
+            let steps = 2;
+            let message;
+            message = "hello";
+            say(message);
+            loop {
+                move_forward(steps);
+                if steps > 1 {
+                    break;
+                }
+            }
+            turn_around(1);
+        "#;
+        assert_eq!(messages(script), vec![]);
+    }
+
+    #[test]
+    fn warnings() {
+        let script = r"
+            fn turn_right() {
+                turn_left();
+            }
+            let unused = 1;
+            let nothing;
+            say(nothing);
+            move_forward(0);
+            move_backward(-2);
+            loop {
+                move_forward(1);
+                for i in 0..3 {
+                    break;
+                }
+            }
+            turn_right();
+        ";
+        assert_eq!(
+            messages(script),
+            vec![
+                (2, 16, String::from("Warning: turn_right is already a built-in function. Defining your own function with the same name will replace it.")),
+                (5, 17, String::from("Warning: The variable unused is never used.")),
+                (7, 17, String::from("Warning: The variable nothing doesn't have a value yet, so the rover won't say anything.")),
+                (8, 13, String::from("Warning: Calling move_forward(0) won't move the rover at all.")),
+                (9, 13, String::from("Warning: move_backward should be given a positive number. (Hint: if you want to go the other way, try using move_forward instead.)")),
+                (16, 13, String::from("Warning: This code will never run because the loop before it never ends. (Hint: you can use break to stop a loop.)")),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_script() {
+        assert_eq!(messages("move_forward(0"), vec![]);
+    }
+}
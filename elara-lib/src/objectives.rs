@@ -0,0 +1,383 @@
+use crate::i18n::translate;
+use crate::levels::is_on_goal;
+use crate::simulation::{Pos, State};
+
+/// A declarative description of what the player needs to do to beat a level.
+/// Objectives are evaluated against the whole history of states (rather than
+/// just the latest one) so that they can express things like "survive for N
+/// steps" or "visit the checkpoints in order". Objectives can be combined with
+/// All, Any and Sequence.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Objective {
+    /// Any of the rovers reaches one of the goals.
+    ReachGoal,
+    /// The button with the given index is pressed.
+    PressButton(usize),
+    /// Every energy cell has been collected.
+    CollectAllEnergyCells,
+    /// Every data point has been read at least once.
+    ReadAllDataPoints,
+    /// There is a crate on each of the given positions (and none of them are
+    /// being held).
+    DeliverCrates(Vec<Pos>),
+    /// The rovers last for the given number of steps without failing.
+    SurviveSteps(usize),
+    /// The rovers visit each of the given positions in order.
+    Checkpoints(Vec<Pos>),
+    /// Every one of the given objectives is completed (in any order).
+    All(Vec<Objective>),
+    /// At least one of the given objectives is completed.
+    Any(Vec<Objective>),
+    /// Each of the given objectives is completed in order. Progress towards an
+    /// objective is only counted after the previous one is complete.
+    Sequence(Vec<Objective>),
+}
+
+/// The progress towards an objective, intended to be displayed in the UI.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectiveProgress {
+    pub description: String,
+    pub completed: bool,
+    /// The number of parts of the objective which are complete so far (e.g.
+    /// the number of energy cells collected or the number of steps survived).
+    pub current: usize,
+    /// The number of parts needed to complete the objective.
+    pub total: usize,
+    /// Progress for each of the sub-objectives (only used for All, Any and
+    /// Sequence).
+    pub children: Vec<ObjectiveProgress>,
+}
+
+impl Objective {
+    /// Returns true if the objective has been completed at any point in the
+    /// given history of states.
+    pub fn is_complete(&self, states: &[State]) -> bool {
+        self.completed_at(states).is_some()
+    }
+
+    /// Returns the index of the first state at which the objective was
+    /// complete, or None if it has not been completed yet.
+    pub fn completed_at(&self, states: &[State]) -> Option<usize> {
+        match self {
+            Objective::ReachGoal => states
+                .iter()
+                .position(|state| state.rovers().any(|rover| is_on_goal(state, rover))),
+            Objective::PressButton(index) => states.iter().position(|state| {
+                state
+                    .buttons
+                    .get(*index)
+                    .is_some_and(|button| button.currently_pressed)
+            }),
+            Objective::CollectAllEnergyCells => states
+                .iter()
+                .position(|state| state.energy_cells.iter().all(|cell| cell.collected)),
+            Objective::ReadAllDataPoints => {
+                // Data points are only "reading" for a single step, so we need to
+                // find the first step at which each data point was read.
+                let num_data_points = states.first()?.data_points.len();
+                (0..num_data_points).try_fold(0, |latest, i| {
+                    let read_at = first_read_at(states, i)?;
+                    Some(latest.max(read_at))
+                })
+            }
+            Objective::DeliverCrates(targets) => states
+                .iter()
+                .position(|state| num_delivered(state, targets) == targets.len()),
+            Objective::SurviveSteps(steps) => {
+                // The initial state doesn't count as a step.
+                if states.len() > *steps {
+                    Some(*steps)
+                } else {
+                    None
+                }
+            }
+            Objective::Checkpoints(checkpoints) => {
+                if checkpoints.is_empty() {
+                    return if states.is_empty() { None } else { Some(0) };
+                }
+                let (visited, last_visit) = visit_checkpoints(states, checkpoints);
+                if visited == checkpoints.len() {
+                    last_visit
+                } else {
+                    None
+                }
+            }
+            Objective::All(objectives) => objectives
+                .iter()
+                .try_fold(0, |latest, objective| {
+                    Some(latest.max(objective.completed_at(states)?))
+                })
+                .filter(|_| !states.is_empty()),
+            Objective::Any(objectives) => objectives
+                .iter()
+                .filter_map(|objective| objective.completed_at(states))
+                .min(),
+            Objective::Sequence(objectives) => {
+                let mut offset = 0;
+                for objective in objectives {
+                    offset += objective.completed_at(&states[offset..])?;
+                }
+                if states.is_empty() {
+                    None
+                } else {
+                    Some(offset)
+                }
+            }
+        }
+    }
+
+    /// Returns a short, human-readable description of the objective.
+    pub fn description(&self) -> String {
+        match self {
+            Objective::ReachGoal => translate("objective_reach_goal", &[]),
+            Objective::PressButton(_) => translate("objective_press_button", &[]),
+            Objective::CollectAllEnergyCells => {
+                translate("objective_collect_all_energy_cells", &[])
+            }
+            Objective::ReadAllDataPoints => translate("objective_read_all_data_points", &[]),
+            Objective::DeliverCrates(targets) if targets.len() == 1 => {
+                translate("objective_deliver_crate", &[])
+            }
+            Objective::DeliverCrates(_) => translate("objective_deliver_crates", &[]),
+            Objective::SurviveSteps(steps) => {
+                translate("objective_survive_steps", &[("steps", steps)])
+            }
+            Objective::Checkpoints(_) => translate("objective_checkpoints", &[]),
+            Objective::All(_) => translate("objective_all", &[]),
+            Objective::Any(_) => translate("objective_any", &[]),
+            Objective::Sequence(_) => translate("objective_sequence", &[]),
+        }
+    }
+
+    /// Returns the progress towards the objective (and any sub-objectives)
+    /// based on the given history of states.
+    pub fn progress(&self, states: &[State]) -> ObjectiveProgress {
+        let completed = self.is_complete(states);
+        let last_state = states.last();
+        let mut children = vec![];
+        let (current, total) = match self {
+            Objective::ReachGoal | Objective::PressButton(_) => (0, 1),
+            Objective::CollectAllEnergyCells => {
+                let cells = last_state.map_or(&[][..], |state| &state.energy_cells[..]);
+                (
+                    cells.iter().filter(|cell| cell.collected).count(),
+                    cells.len(),
+                )
+            }
+            Objective::ReadAllDataPoints => {
+                let num_data_points = states.first().map_or(0, |state| state.data_points.len());
+                (
+                    (0..num_data_points)
+                        .filter(|&i| first_read_at(states, i).is_some())
+                        .count(),
+                    num_data_points,
+                )
+            }
+            Objective::DeliverCrates(targets) => (
+                last_state.map_or(0, |state| num_delivered(state, targets)),
+                targets.len(),
+            ),
+            Objective::SurviveSteps(steps) => (states.len().saturating_sub(1).min(*steps), *steps),
+            Objective::Checkpoints(checkpoints) => {
+                (visit_checkpoints(states, checkpoints).0, checkpoints.len())
+            }
+            Objective::All(objectives) => {
+                children = objectives.iter().map(|o| o.progress(states)).collect();
+                (
+                    children.iter().filter(|child| child.completed).count(),
+                    children.len(),
+                )
+            }
+            Objective::Any(objectives) => {
+                children = objectives.iter().map(|o| o.progress(states)).collect();
+                (0, 1)
+            }
+            Objective::Sequence(objectives) => {
+                // Each objective only sees the states after the previous one was
+                // completed. Objectives after the first incomplete one haven't
+                // been started yet.
+                let mut remaining = Some(states);
+                for objective in objectives {
+                    let history = remaining.unwrap_or(&[]);
+                    children.push(objective.progress(history));
+                    remaining = remaining.and_then(|history| {
+                        objective
+                            .completed_at(history)
+                            .map(|index| &history[index..])
+                    });
+                }
+                (
+                    children.iter().filter(|child| child.completed).count(),
+                    children.len(),
+                )
+            }
+        };
+        ObjectiveProgress {
+            description: self.description(),
+            completed,
+            current: if completed { total } else { current },
+            total,
+            children,
+        }
+    }
+}
+
+/// Returns the index of the first state in which the data point with the
+/// given index was being read.
+fn first_read_at(states: &[State], data_point_index: usize) -> Option<usize> {
+    states.iter().position(|state| {
+        state
+            .data_points
+            .get(data_point_index)
+            .is_some_and(|data_point| data_point.reading)
+    })
+}
+
+/// Returns the number of target positions which have a crate on them.
+fn num_delivered(state: &State, targets: &[Pos]) -> usize {
+    targets
+        .iter()
+        .filter(|target| {
+            state
+                .crates
+                .iter()
+                .any(|crate_| !crate_.held && !crate_.crushed && crate_.pos == **target)
+        })
+        .count()
+}
+
+/// Returns the number of checkpoints which were visited in order, along with
+/// the index of the state in which the last of them was visited.
+fn visit_checkpoints(states: &[State], checkpoints: &[Pos]) -> (usize, Option<usize>) {
+    let mut visited = 0;
+    let mut last_visit = None;
+    for (i, state) in states.iter().enumerate() {
+        if visited == checkpoints.len() {
+            break;
+        }
+        if state
+            .rovers()
+            .any(|rover| rover.pos == checkpoints[visited])
+        {
+            visited += 1;
+            last_visit = Some(i);
+        }
+    }
+    (visited, last_visit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::{Button, ButtonConnection, EnergyCell, Goal, Orientation, Player};
+    use crate::state_maker::StateMaker;
+
+    /// Returns a history of states in which the player moves along the given
+    /// positions (one per step).
+    fn path(initial: State, positions: &[(i32, i32)]) -> Vec<State> {
+        let mut states = vec![initial];
+        for &(x, y) in positions {
+            let mut next = states.last().unwrap().clone();
+            next.player.pos = Pos::new(x, y);
+            for cell in next.energy_cells.iter_mut() {
+                if cell.pos == next.player.pos {
+                    cell.collected = true;
+                }
+            }
+            states.push(next);
+        }
+        states
+    }
+
+    #[test]
+    fn simple_objectives() {
+        let initial = StateMaker::new()
+            .with_player(Player::new(0, 0, 10, Orientation::Right))
+            .with_goals(vec![Goal::new(3, 0)])
+            .with_energy_cells(vec![EnergyCell::new(1, 0), EnergyCell::new(2, 0)])
+            .build();
+        let states = path(initial, &[(1, 0), (2, 0), (3, 0)]);
+
+        assert_eq!(Objective::ReachGoal.completed_at(&states), Some(3));
+        assert_eq!(
+            Objective::CollectAllEnergyCells.completed_at(&states),
+            Some(2)
+        );
+        assert_eq!(Objective::SurviveSteps(3).completed_at(&states), Some(3));
+        assert_eq!(Objective::SurviveSteps(4).completed_at(&states), None);
+        assert_eq!(Objective::PressButton(0).completed_at(&states), None);
+
+        let progress = Objective::CollectAllEnergyCells.progress(&states[..2]);
+        assert!(!progress.completed);
+        assert_eq!((progress.current, progress.total), (1, 2));
+    }
+
+    #[test]
+    fn press_button_and_read_data_points() {
+        let mut initial = State::new();
+        initial.buttons = vec![Button::new(1, 0, ButtonConnection::None)];
+        initial.data_points = vec![
+            crate::simulation::DataPoint::new(0, 1, "a".into()),
+            crate::simulation::DataPoint::new(1, 1, "b".into()),
+        ];
+        let mut states = vec![initial.clone(), initial.clone(), initial.clone(), initial];
+        states[1].data_points[1].reading = true;
+        states[2].buttons[0].currently_pressed = true;
+        states[3].data_points[0].reading = true;
+
+        assert_eq!(Objective::PressButton(0).completed_at(&states), Some(2));
+        assert_eq!(Objective::ReadAllDataPoints.completed_at(&states), Some(3));
+        let progress = Objective::ReadAllDataPoints.progress(&states[..3]);
+        assert_eq!((progress.current, progress.total), (1, 2));
+    }
+
+    #[test]
+    fn checkpoints() {
+        let initial = StateMaker::new()
+            .with_player(Player::new(0, 0, 10, Orientation::Right))
+            .build();
+        let checkpoints = Objective::Checkpoints(vec![Pos::new(2, 0), Pos::new(1, 0)]);
+
+        // Visiting the checkpoints out of order doesn't count.
+        let states = path(initial.clone(), &[(1, 0), (2, 0)]);
+        assert_eq!(checkpoints.completed_at(&states), None);
+        assert_eq!(checkpoints.progress(&states).current, 1);
+
+        let states = path(initial, &[(1, 0), (2, 0), (1, 0)]);
+        assert_eq!(checkpoints.completed_at(&states), Some(3));
+    }
+
+    #[test]
+    fn combinators() {
+        let initial = StateMaker::new()
+            .with_player(Player::new(0, 0, 10, Orientation::Right))
+            .with_goals(vec![Goal::new(1, 0)])
+            .with_energy_cells(vec![EnergyCell::new(2, 0)])
+            .build();
+        // The player passes the goal before collecting the energy cell.
+        let states = path(initial, &[(1, 0), (2, 0)]);
+
+        let all = Objective::All(vec![Objective::CollectAllEnergyCells, Objective::ReachGoal]);
+        assert_eq!(all.completed_at(&states), Some(2));
+
+        let any = Objective::Any(vec![Objective::CollectAllEnergyCells, Objective::ReachGoal]);
+        assert_eq!(any.completed_at(&states), Some(1));
+
+        // The goal needs to be reached *after* collecting the energy cell.
+        let sequence =
+            Objective::Sequence(vec![Objective::CollectAllEnergyCells, Objective::ReachGoal]);
+        assert_eq!(sequence.completed_at(&states), None);
+        let progress = sequence.progress(&states);
+        assert!(!progress.completed);
+        assert_eq!((progress.current, progress.total), (1, 2));
+        assert!(progress.children[0].completed);
+        assert!(!progress.children[1].completed);
+
+        let mut states = states;
+        let mut back_to_goal = states.last().unwrap().clone();
+        back_to_goal.player.pos = Pos::new(1, 0);
+        states.push(back_to_goal);
+        assert_eq!(sequence.completed_at(&states), Some(3));
+        assert!(sequence.progress(&states).completed);
+    }
+}
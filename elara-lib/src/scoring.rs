@@ -0,0 +1,103 @@
+use crate::levels::Outcome;
+use crate::script_runner::ScriptStats;
+
+/// The maximum number of stars which can be awarded for a level.
+pub const MAX_STARS: u8 = 3;
+
+/// Par values for a level. Each value is the target for the corresponding
+/// field in ScriptStats (lower is better). Levels don't need to set a par for
+/// every stat; None means the stat is not scored.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Par {
+    pub time_taken: Option<u32>,
+    pub energy_used: Option<u32>,
+    pub code_len: Option<usize>,
+}
+
+impl Par {
+    /// Returns the number of par values which are set.
+    pub fn num_scored(&self) -> usize {
+        [
+            self.time_taken.is_some(),
+            self.energy_used.is_some(),
+            self.code_len.is_some(),
+        ]
+        .iter()
+        .filter(|&&scored| scored)
+        .count()
+    }
+
+    /// Returns the number of par values which were met (i.e. the stat was
+    /// less than or equal to the par).
+    pub fn num_met(&self, stats: &ScriptStats) -> usize {
+        [
+            self.time_taken.is_some_and(|par| stats.time_taken <= par),
+            self.energy_used.is_some_and(|par| stats.energy_used <= par),
+            self.code_len.is_some_and(|par| stats.code_len <= par),
+        ]
+        .iter()
+        .filter(|&&met| met)
+        .count()
+    }
+
+    /// Returns the star rating for a run of the level. Completing the
+    /// objective is worth 1 star, meeting at least one par is worth 2 stars
+    /// and meeting every par is worth 3 stars. No stars are awarded if the
+    /// objective was not completed.
+    pub fn stars(&self, outcome: &Outcome, stats: &ScriptStats) -> u8 {
+        if *outcome != Outcome::Success {
+            return 0;
+        }
+        let num_met = self.num_met(stats);
+        if num_met == self.num_scored() {
+            MAX_STARS
+        } else if num_met > 0 {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::FailureReason;
+
+    fn stats(code_len: usize, energy_used: u32, time_taken: u32) -> ScriptStats {
+        ScriptStats {
+            code_len,
+            energy_used,
+            time_taken,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn stars() {
+        let par = Par {
+            time_taken: Some(10),
+            energy_used: Some(8),
+            code_len: Some(50),
+        };
+        assert_eq!(par.stars(&Outcome::Success, &stats(50, 8, 10)), 3);
+        assert_eq!(par.stars(&Outcome::Success, &stats(80, 8, 12)), 2);
+        assert_eq!(par.stars(&Outcome::Success, &stats(80, 9, 12)), 1);
+        assert_eq!(par.stars(&Outcome::Continue, &stats(50, 8, 10)), 0);
+        assert_eq!(
+            par.stars(
+                &Outcome::Failure(FailureReason::OutOfEnergy),
+                &stats(50, 8, 10)
+            ),
+            0
+        );
+
+        // Stats without a par are ignored.
+        let par = Par {
+            time_taken: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(par.stars(&Outcome::Success, &stats(1000, 1000, 10)), 3);
+        assert_eq!(par.stars(&Outcome::Success, &stats(1, 1, 11)), 1);
+    }
+}
@@ -0,0 +1,305 @@
+use rhai::{ASTNode, Expr, Stmt, AST};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::diagnostics::raw_engine;
+
+/// Answers questions about the structure of a script (e.g. "does it call
+/// read_data?") based on its AST. This is more reliable than searching the
+/// source code, which can't tell the difference between calling a function
+/// and e.g. a variable with a similar name.
+pub struct ScriptQuery {
+    ast: AST,
+}
+
+impl ScriptQuery {
+    /// Compiles the script so that it can be queried. Returns None if the
+    /// script can't be compiled.
+    pub fn new(script: &str) -> Option<ScriptQuery> {
+        raw_engine()
+            .compile(script)
+            .ok()
+            .map(|ast| ScriptQuery { ast })
+    }
+
+    /// Returns the number of places in the code where the function with the
+    /// given name is called (including method calls, e.g. rover(1).say("hi")).
+    pub fn num_calls(&self, name: &str) -> usize {
+        self.count_nodes(|node| called_function(node) == Some(name))
+    }
+
+    /// Returns the number of places in the code where each function is
+    /// called, indexed by function name.
+    pub fn function_calls(&self) -> BTreeMap<String, usize> {
+        let mut calls = BTreeMap::new();
+        self.ast.walk(&mut |path| {
+            if let Some(name) = called_function(path.last().unwrap()) {
+                *calls.entry(name.to_string()).or_insert(0) += 1;
+            }
+            true
+        });
+        calls
+    }
+
+    /// Returns the number of user-defined functions.
+    pub fn num_function_definitions(&self) -> usize {
+        self.ast.iter_fn_def().count()
+    }
+
+    /// Returns true if the script defines a function with the given name.
+    pub fn defines_function(&self, name: &str) -> bool {
+        self.ast.iter_fn_def().any(|func| func.name == name)
+    }
+
+    /// Returns the number of loops of any kind (loop, while, do, and for).
+    pub fn num_loops(&self) -> usize {
+        self.count_nodes(|node| {
+            matches!(
+                node,
+                ASTNode::Stmt(Stmt::While(..))
+                    | ASTNode::Stmt(Stmt::Do(..))
+                    | ASTNode::Stmt(Stmt::For(..))
+            )
+        })
+    }
+
+    /// Returns the number of while loops (but not plain loops, which have no
+    /// condition).
+    pub fn num_while_loops(&self) -> usize {
+        self.count_nodes(|node| match node {
+            ASTNode::Stmt(Stmt::While(flow, _)) => !matches!(flow.expr, Expr::Unit(_)),
+            _ => false,
+        })
+    }
+
+    /// Returns the number of conditionals (if and switch).
+    pub fn num_conditionals(&self) -> usize {
+        self.count_nodes(|node| {
+            matches!(
+                node,
+                ASTNode::Stmt(Stmt::If(..)) | ASTNode::Stmt(Stmt::Switch(..))
+            )
+        })
+    }
+
+    /// Returns the number of variable declarations (let and const).
+    pub fn num_variable_declarations(&self) -> usize {
+        self.count_nodes(|node| matches!(node, ASTNode::Stmt(Stmt::Var(..))))
+    }
+
+    /// Returns the number of statements, including statements inside blocks
+    /// and function bodies.
+    pub fn num_statements(&self) -> usize {
+        self.count_nodes(|node| match node {
+            ASTNode::Stmt(Stmt::Noop(..)) | ASTNode::Stmt(Stmt::Block(..)) => false,
+            ASTNode::Stmt(_) => true,
+            _ => false,
+        })
+    }
+
+    /// Returns the maximum depth of nested blocks (loops, conditionals, and
+    /// function bodies). A script with only top-level statements has a depth
+    /// of 0.
+    pub fn max_nesting_depth(&self) -> usize {
+        // Top-level statements start at depth 0, while function bodies start
+        // at depth 1.
+        let mut max_depth = 0;
+        let mut visit = |stmt: &Stmt, base_depth: usize| {
+            stmt.walk(&mut vec![], &mut |path| {
+                if is_nesting(path.last().unwrap()) {
+                    let depth = base_depth + path.iter().filter(|node| is_nesting(node)).count();
+                    max_depth = max_depth.max(depth);
+                }
+                true
+            });
+        };
+        for stmt in self.ast.statements() {
+            visit(stmt, 0);
+        }
+        let mut max_depth_in_functions = 0;
+        for func in self.ast.iter_fn_def() {
+            max_depth_in_functions = 1;
+            for stmt in func.body.iter() {
+                visit(stmt, 1);
+            }
+        }
+        max_depth.max(max_depth_in_functions)
+    }
+
+    /// Returns the names of user-defined functions which call themselves,
+    /// either directly or through other user-defined functions.
+    pub fn recursive_functions(&self) -> Vec<String> {
+        // Build a graph of which user-defined functions call each other.
+        let mut calls: HashMap<String, HashSet<String>> = HashMap::new();
+        for func in self.ast.iter_fn_def() {
+            let callees = calls.entry(func.name.to_string()).or_default();
+            for stmt in func.body.iter() {
+                stmt.walk(&mut vec![], &mut |path| {
+                    if let Some(name) = called_function(path.last().unwrap()) {
+                        callees.insert(name.to_string());
+                    }
+                    true
+                });
+            }
+        }
+
+        let mut recursive: Vec<String> = calls
+            .keys()
+            .filter(|name| can_reach(&calls, name, name))
+            .cloned()
+            .collect();
+        recursive.sort();
+        recursive
+    }
+
+    /// Returns the number of nodes in the AST (including function bodies)
+    /// which match the predicate.
+    fn count_nodes(&self, predicate: impl Fn(&ASTNode) -> bool) -> usize {
+        let mut count = 0;
+        self.ast.walk(&mut |path| {
+            if predicate(path.last().unwrap()) {
+                count += 1;
+            }
+            true
+        });
+        count
+    }
+}
+
+/// Returns the name of the function being called if the node is a function
+/// or method call.
+fn called_function<'a>(node: &'a ASTNode) -> Option<&'a str> {
+    match node {
+        ASTNode::Stmt(Stmt::FnCall(call, _))
+        | ASTNode::Expr(Expr::FnCall(call, _))
+        | ASTNode::Expr(Expr::MethodCall(call, _)) => Some(call.name.as_str()),
+        _ => None,
+    }
+}
+
+/// Returns true if the node introduces a new level of nesting.
+fn is_nesting(node: &ASTNode) -> bool {
+    matches!(
+        node,
+        ASTNode::Stmt(Stmt::If(..))
+            | ASTNode::Stmt(Stmt::Switch(..))
+            | ASTNode::Stmt(Stmt::While(..))
+            | ASTNode::Stmt(Stmt::Do(..))
+            | ASTNode::Stmt(Stmt::For(..))
+    )
+}
+
+/// Returns true if there is a path of one or more calls from the function
+/// named from to the function named to.
+fn can_reach(calls: &HashMap<String, HashSet<String>>, from: &str, to: &str) -> bool {
+    let mut visited: HashSet<&str> = HashSet::new();
+    let mut stack: Vec<&str> = vec![from];
+    while let Some(name) = stack.pop() {
+        for callee in calls.get(name).into_iter().flatten() {
+            if callee == to {
+                return true;
+            }
+            if visited.insert(callee.as_str()) {
+                stack.push(callee.as_str());
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calls_and_variables() {
+        let query = ScriptQuery::new(
+            r#"
+            // read_data() in a comment doesn't count.
+            let read_data_count = 0;
+            let read_data = "not a function";
+            say(read_data);
+            rover(1).move_forward(read_data_count);
+            "#,
+        )
+        .unwrap();
+        assert_eq!(query.num_calls("read_data"), 0);
+        assert_eq!(query.num_calls("say"), 1);
+        assert_eq!(query.num_calls("move_forward"), 1);
+        assert_eq!(query.num_calls("rover"), 1);
+        assert_eq!(
+            query.function_calls(),
+            BTreeMap::from([
+                (String::from("move_forward"), 1),
+                (String::from("rover"), 1),
+                (String::from("say"), 1)
+            ])
+        );
+        assert_eq!(query.num_variable_declarations(), 2);
+
+        assert!(ScriptQuery::new("move_forward(1").is_none());
+    }
+
+    #[test]
+    fn loops_and_functions() {
+        let query = ScriptQuery::new(
+            r"
+            fn three_lefts() {
+                for i in 0..3 {
+                    turn_left();
+                }
+            }
+            loop {
+                three_lefts();
+            }
+            ",
+        )
+        .unwrap();
+        assert!(query.defines_function("three_lefts"));
+        assert!(!query.defines_function("turn_left"));
+        assert_eq!(query.num_function_definitions(), 1);
+        assert_eq!(query.num_loops(), 2);
+        assert_eq!(query.num_while_loops(), 0);
+        assert_eq!(query.num_conditionals(), 0);
+        assert!(query.recursive_functions().is_empty());
+        // fn body -> for
+        assert_eq!(query.max_nesting_depth(), 2);
+
+        let query = ScriptQuery::new("while true { if false { break; } }").unwrap();
+        assert_eq!(query.num_while_loops(), 1);
+        assert_eq!(query.num_conditionals(), 1);
+        // while, if, break
+        assert_eq!(query.num_statements(), 3);
+        assert_eq!(query.max_nesting_depth(), 2);
+    }
+
+    #[test]
+    fn recursion() {
+        let query = ScriptQuery::new(
+            r"
+            fn forever() {
+                move_forward(1);
+                forever();
+            }
+            fn ping(n) {
+                if n > 0 { pong(n - 1); }
+            }
+            fn pong(n) {
+                ping(n);
+            }
+            fn not_recursive() {
+                ping(2);
+            }
+            forever();
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            query.recursive_functions(),
+            vec![
+                "forever".to_string(),
+                "ping".to_string(),
+                "pong".to_string()
+            ]
+        );
+    }
+}
@@ -1,21 +1,24 @@
 use rhai::debugger::DebuggerCommand;
 use rhai::{
-    ASTNode, Dynamic, Engine, EvalAltResult, EvalContext, Expr, FnCallExpr, Position, Stmt,
+    ASTNode, Dynamic, Engine, EvalAltResult, EvalContext, Expr, FnCallExpr, NativeCallContext,
+    Position, Stmt,
 };
 use std::cell::RefCell;
-use std::convert::TryInto;
-use std::io::{Error, ErrorKind};
+use std::io::Error;
 use std::rc::Rc;
 use std::sync::mpsc;
 use std::vec;
 
 use crate::actors::{Action, MoveDirection, TurnDirection};
-use crate::better_errors::{convert_err, BetterError};
-use crate::constants::{
-    BAD_INPUT_UNEXPECTED_LINE_BREAK_IN_FUNCTION_CALL, ERR_NO_BUTTON, ERR_NO_DATA_POINT,
-    ERR_SIMULATION_END,
+use crate::better_errors::{
+    convert_err, convert_runtime_err, root_cause, RuntimeError, RuntimeErrorKind,
 };
-use crate::levels::Outcome;
+use crate::challenges::ChallengeProgress;
+use crate::code_metrics::CodeMetrics;
+use crate::diagnostics::{check_script, Diagnostic};
+use crate::levels::{FailureReason, Outcome};
+use crate::lints::lint;
+use crate::objectives::ObjectiveProgress;
 use crate::simulation::{
     get_adjacent_button, get_adjacent_point, Orientation, Pos, Simulation, State,
 };
@@ -23,7 +26,7 @@ use crate::simulation::{
 /// Responsible for running user scripts and coordinating communication
 /// between the Rhai Engine and the Simulation.
 pub struct ScriptRunner {
-    simulation: Rc<RefCell<Simulation>>,
+    simulation: Rc<RefCell<Simulation<'static>>>,
     /// Used to send actions from the script to the PlayerChannelActor.
     player_action_tx: Rc<RefCell<mpsc::Sender<Action>>>,
     /// Used for building up the trace of positions for each step in the simulation.
@@ -46,11 +49,51 @@ pub struct ScriptResult {
     pub outcome: Outcome,
     pub stats: ScriptStats,
     pub passes_challenge: bool,
+    /// The number of stars awarded for the run, or None if the level doesn't
+    /// have any par values.
+    pub stars: Option<u8>,
+    /// Progress towards the level's objective (only for levels with
+    /// objectives).
+    pub objective_progress: Option<ObjectiveProgress>,
+    /// Progress for each rule of the level's challenge (only for levels with
+    /// a challenge).
+    pub challenge_progress: Vec<ChallengeProgress>,
+    /// Warnings about common mistakes in the script. These don't stop the
+    /// script from running.
+    pub warnings: Vec<Diagnostic>,
+}
+
+impl ScriptResult {
+    /// Returns the error which caused the script to fail while running (if
+    /// any).
+    pub fn runtime_error(&self) -> Option<&RuntimeError> {
+        match &self.outcome {
+            Outcome::Failure(FailureReason::RuntimeError(err)) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// A special error value that is thrown when the simulation ends before the
+/// script finishes running. I.e., this is a way for us to abort running a
+/// script if the simulation outcome does not require us to continue running
+/// it.
+#[derive(Clone)]
+struct SimulationEnd;
+
+/// Returns an error of the given kind which can be thrown from one of our
+/// built-in functions. convert_runtime_err turns it into a RuntimeError with
+/// the message for the current language.
+fn runtime_err(kind: RuntimeErrorKind) -> Box<EvalAltResult> {
+    Box::new(EvalAltResult::ErrorRuntime(
+        Dynamic::from(kind),
+        Position::NONE,
+    ))
 }
 
 impl ScriptRunner {
     pub fn new(
-        simulation: Rc<RefCell<Simulation>>,
+        simulation: Rc<RefCell<Simulation<'static>>>,
         player_action_tx: Rc<RefCell<mpsc::Sender<Action>>>,
     ) -> ScriptRunner {
         ScriptRunner {
@@ -63,16 +106,17 @@ impl ScriptRunner {
         }
     }
 
-    /// Runs the user script and returns the result or error.
+    /// Runs the user script and returns the result, or every error which was
+    /// found if the script could not be run.
     ///
     /// avail_funcs is the list of functions that are available to the user.
     /// Some levels have restrictions on which functions are available.
     pub fn run(
         &mut self,
         avail_funcs: &[String],
-        disabled_funcs: &'static [&'static str],
+        disabled_funcs: &[&str],
         script: &str,
-    ) -> Result<ScriptResult, BetterError> {
+    ) -> Result<ScriptResult, Vec<Diagnostic>> {
         // Create and configure the Rhai engine.
         let mut engine = Engine::new();
         set_engine_config(&mut engine);
@@ -82,33 +126,10 @@ impl ScriptRunner {
         register_custom_types(&mut engine);
         self.register_player_funcs(&mut engine, avail_funcs);
 
-        // Try compiling the AST first and check for lexer/parser errors.
-        let ast = match engine.compile(script) {
-            Err(parse_err) => {
-                let alt_result = Box::new(EvalAltResult::ErrorParsing(*parse_err.0, parse_err.1));
-                return Err(convert_err(
-                    avail_funcs,
-                    disabled_funcs,
-                    script.to_string(),
-                    alt_result,
-                ));
-            }
-            Ok(ast) => ast,
-        };
-
-        // Next use a custom check for semicolons at the end of each line
-        // (except for blocks or inside comments).
-        match check_semicolons(script) {
-            Ok(()) => {}
-            Err(err) => {
-                return Err(convert_err(
-                    avail_funcs,
-                    disabled_funcs,
-                    script.to_string(),
-                    err,
-                ))
-            }
-        }
+        // Check for any errors which can be found without running the script
+        // (e.g. syntax errors) before running it.
+        let ast = check_script(&engine, avail_funcs, disabled_funcs, script)?;
+        let warnings = lint(script);
 
         // Reset pending_trace. We always start with an empty list for step 0 (i.e. no
         // active line numbers).
@@ -122,35 +143,45 @@ impl ScriptRunner {
 
         // If the AST looks good, try running the script.
         if let Err(err) = engine.run_ast(&ast) {
-            match *err {
-                EvalAltResult::ErrorRuntime(_, _) => {
-                    if err.to_string().contains(ERR_SIMULATION_END) {
-                        // Special case for when the simulation ends before the script
-                        // finishes running. This is not actually an error, so we continue.
-                    } else {
-                        // Other runtime errors should be considered a failure.
+            let is_simulation_end = matches!(
+                root_cause(&err),
+                EvalAltResult::ErrorRuntime(value, _) if value.is::<SimulationEnd>()
+            );
+            // Special case for when the simulation ends before the script
+            // finishes running. This is not actually an error, so we continue.
+            if !is_simulation_end {
+                match convert_runtime_err(&err) {
+                    Some(runtime_error) => {
+                        // Runtime errors should be considered a failure.
                         // In this case we still return all the states and trace.
-                        let outcome = Outcome::Failure(err.to_string());
+                        let outcome = Outcome::Failure(FailureReason::RuntimeError(runtime_error));
                         let states = self.simulation.borrow().get_history();
                         let trace = self.pending_trace.borrow().to_vec();
                         let stats = compute_stats(&engine, script, &states);
+                        let objective_progress = self.simulation.borrow().objective_progress();
+                        let stars = self
+                            .simulation
+                            .borrow()
+                            .curr_level()
+                            .par()
+                            .map(|par| par.stars(&outcome, &stats));
                         return Ok(ScriptResult {
                             states,
                             trace,
                             outcome,
                             stats,
                             passes_challenge: false,
+                            stars,
+                            objective_progress,
+                            challenge_progress: vec![],
+                            warnings,
                         });
                     }
-                }
-                _ => {
-                    // For all other kinds of errors, we return the error.
-                    return Err(convert_err(
-                        avail_funcs,
-                        disabled_funcs,
-                        script.to_string(),
-                        err,
-                    ));
+                    None => {
+                        // For all other kinds of errors, we return the error.
+                        let err = convert_err(avail_funcs, disabled_funcs, script.to_string(), err);
+                        return Err(vec![Diagnostic::error(script, err)]);
+                    }
                 }
             }
         }
@@ -158,17 +189,21 @@ impl ScriptRunner {
         let states = self.simulation.borrow().get_history();
         let positions = self.pending_trace.borrow().to_vec();
         let outcome = self.simulation.borrow().last_outcome();
+        let objective_progress = self.simulation.borrow().objective_progress();
         let stats = compute_stats(&engine, script, &states);
 
-        // If the outcome is success, and the level has a challenge,
-        // check if it was passed.
-        let mut passes_challenge = false;
-        if let Outcome::Success = outcome {
-            let curr_level = self.simulation.borrow().curr_level();
-            if curr_level.challenge().is_some() {
-                passes_challenge = curr_level.check_challenge(&states, script, &stats);
-            }
-        }
+        // If the level has a challenge, check how close the player is to
+        // passing it. The challenge can only be passed if the outcome is
+        // success.
+        let curr_level = self.simulation.borrow().curr_level();
+        let challenge_progress = match curr_level.challenge() {
+            Some(challenge) => challenge.progress(&*curr_level, &states, script, &stats),
+            None => vec![],
+        };
+        let passes_challenge = outcome == Outcome::Success
+            && !challenge_progress.is_empty()
+            && challenge_progress.iter().all(|progress| progress.passed);
+        let stars = curr_level.par().map(|par| par.stars(&outcome, &stats));
 
         Ok(ScriptResult {
             states,
@@ -176,6 +211,10 @@ impl ScriptRunner {
             outcome,
             stats,
             passes_challenge,
+            stars,
+            objective_progress,
+            challenge_progress,
+            warnings,
         })
     }
 
@@ -205,6 +244,18 @@ impl ScriptRunner {
                             fn_call_expr,
                         )
                     }
+                    ASTNode::Expr(Expr::MethodCall(fn_call_expr, ..)) => {
+                        // Method calls on a rover handle, e.g. rover(1).move_forward(2).
+                        // The arguments do not include the rover itself, so these can be
+                        // handled the same way as regular function calls.
+                        Self::handle_debugger_function_call(
+                            &avail_funcs,
+                            pending_trace.clone(),
+                            context,
+                            pos,
+                            fn_call_expr,
+                        )
+                    }
                     ASTNode::Stmt(Stmt::FnCall(fn_call_expr, ..)) => {
                         // log!(
                         //     "Match on function call statement: {:?}",
@@ -224,7 +275,10 @@ impl ScriptRunner {
                         {
                             Ok(DebuggerCommand::StepInto)
                         } else {
-                            Err(ERR_SIMULATION_END.into())
+                            Err(
+                                EvalAltResult::ErrorRuntime(Dynamic::from(SimulationEnd), pos)
+                                    .into(),
+                            )
                         }
                     }
                 }
@@ -459,7 +513,7 @@ impl ScriptRunner {
                 // rotating the rover to correct orientation, and then moving
                 // forward the given number of spaces.
                 let mut sim = simulation.borrow_mut();
-                match sim.curr_state().player.facing {
+                match sim.curr_rover().facing {
                     Orientation::Right => {}
                     Orientation::Up => {
                         tx.borrow()
@@ -493,7 +547,7 @@ impl ScriptRunner {
             let simulation = self.simulation.clone();
             engine.register_fn("move_left", move |spaces: i64| {
                 let mut sim = simulation.borrow_mut();
-                match sim.curr_state().player.facing {
+                match sim.curr_rover().facing {
                     Orientation::Right => {
                         for _ in 0..2 {
                             tx.borrow().send(Action::Turn(TurnDirection::Left)).unwrap();
@@ -525,7 +579,7 @@ impl ScriptRunner {
             let simulation = self.simulation.clone();
             engine.register_fn("move_up", move |spaces: i64| {
                 let mut sim = simulation.borrow_mut();
-                match sim.curr_state().player.facing {
+                match sim.curr_rover().facing {
                     Orientation::Right => {
                         tx.borrow().send(Action::Turn(TurnDirection::Left)).unwrap();
                         sim.step_forward();
@@ -559,7 +613,7 @@ impl ScriptRunner {
             let simulation = self.simulation.clone();
             engine.register_fn("move_down", move |spaces: i64| {
                 let mut sim = simulation.borrow_mut();
-                match sim.curr_state().player.facing {
+                match sim.curr_rover().facing {
                     Orientation::Right => {
                         tx.borrow()
                             .send(Action::Turn(TurnDirection::Right))
@@ -593,7 +647,7 @@ impl ScriptRunner {
             // of [x, y].
             let simulation = self.simulation.clone();
             engine.register_fn("get_position", move || -> Dynamic {
-                let pos = simulation.borrow().curr_state().player.pos;
+                let pos = simulation.borrow().curr_rover().pos;
                 rhai::Array::from(vec![
                     Dynamic::from(pos.x as i64),
                     Dynamic::from(pos.y as i64),
@@ -606,7 +660,7 @@ impl ScriptRunner {
             // facing as a string.
             let simulation = self.simulation.clone();
             engine.register_fn("get_orientation", move || -> Dynamic {
-                let orientation = simulation.borrow().curr_state().player.facing;
+                let orientation = simulation.borrow().curr_rover().facing;
                 let orientation_str = match orientation {
                     Orientation::Up => "up",
                     Orientation::Down => "down",
@@ -616,6 +670,13 @@ impl ScriptRunner {
                 Dynamic::from(orientation_str)
             });
         }
+        if avail_funcs.contains(&"get_energy".to_string()) {
+            // get_energy returns the current energy of the rover.
+            let simulation = self.simulation.clone();
+            engine.register_fn("get_energy", move || -> i64 {
+                simulation.borrow().curr_rover().energy as i64
+            });
+        }
         if avail_funcs.contains(&"say".to_string()) {
             // say causes the rover to say (i.e. display in a speech bubble)
             // the given expression.
@@ -645,13 +706,13 @@ impl ScriptRunner {
                     simulation.borrow_mut().step_forward();
 
                     let state = simulation.borrow().curr_state();
-                    let pos = &state.player.pos;
+                    let pos = &simulation.borrow().curr_rover().pos;
                     if let Some(point_index) = get_adjacent_point(&state, pos) {
                         let data = state.data_points[point_index].data.clone();
                         Ok(data.into())
                     } else {
                         // TODO(albrow): Change this to a G.R.O.V.E.R. err message.
-                        Err(ERR_NO_DATA_POINT.into())
+                        Err(runtime_err(RuntimeErrorKind::NoDataPoint))
                     }
                 },
             );
@@ -663,14 +724,14 @@ impl ScriptRunner {
             let simulation = self.simulation.clone();
             engine.register_fn("press_button", move || -> Result<(), Box<EvalAltResult>> {
                 let state = simulation.borrow().curr_state();
-                let pos = &state.player.pos;
+                let pos = &simulation.borrow().curr_rover().pos;
                 if get_adjacent_button(&state, pos).is_some() {
                     tx.borrow().send(Action::PressButton).unwrap();
                     simulation.borrow_mut().step_forward();
                     Ok(())
                 } else {
                     // TODO(albrow): Change this to a G.R.O.V.E.R. err message.
-                    Err(ERR_NO_BUTTON.into())
+                    Err(runtime_err(RuntimeErrorKind::NoButton))
                 }
             });
         }
@@ -692,12 +753,84 @@ impl ScriptRunner {
                 Ok(())
             });
         }
+        if avail_funcs.contains(&"select_rover".to_string()) {
+            // select_rover changes which rover is controlled by all subsequent
+            // function calls. It does not cause the simulation to step forward.
+            let simulation = self.simulation.clone();
+            engine.register_fn(
+                "select_rover",
+                move |index: i64| -> Result<(), Box<EvalAltResult>> {
+                    if index < 0 || !simulation.borrow_mut().set_active_rover(index as usize) {
+                        return Err(runtime_err(RuntimeErrorKind::NoRover));
+                    }
+                    Ok(())
+                },
+            );
+        }
+        if avail_funcs.contains(&"rover".to_string()) {
+            // rover returns a handle which can be used to control a specific rover
+            // without changing the selected rover, e.g. rover(1).move_forward(2).
+            let simulation = self.simulation.clone();
+            engine.register_fn(
+                "rover",
+                move |index: i64| -> Result<RoverHandle, Box<EvalAltResult>> {
+                    if index < 0 || index as usize >= simulation.borrow().curr_state().num_rovers()
+                    {
+                        return Err(runtime_err(RuntimeErrorKind::NoRover));
+                    }
+                    Ok(RoverHandle {
+                        index: index as usize,
+                    })
+                },
+            );
+            // Each method temporarily selects the rover and then calls the
+            // corresponding global function. This means methods are only
+            // available if the global function is. (The directional move
+            // functions are left out because the debugger would need to know
+            // the orientation of the rover before it is selected.)
+            for &name in &[
+                "turn_left",
+                "turn_right",
+                "press_button",
+                "pick_up",
+                "drop",
+                "read_data",
+                "get_position",
+                "get_orientation",
+            ] {
+                let simulation = self.simulation.clone();
+                engine.register_fn(
+                    name,
+                    move |context: NativeCallContext,
+                          rover: &mut RoverHandle|
+                          -> Result<Dynamic, Box<EvalAltResult>> {
+                        with_rover(&simulation, rover.index, || {
+                            context.call_fn::<Dynamic>(name, ())
+                        })
+                    },
+                );
+            }
+            for &name in &["move_forward", "move_backward", "wait", "say"] {
+                let simulation = self.simulation.clone();
+                engine.register_fn(
+                    name,
+                    move |context: NativeCallContext,
+                          rover: &mut RoverHandle,
+                          arg: Dynamic|
+                          -> Result<Dynamic, Box<EvalAltResult>> {
+                        with_rover(&simulation, rover.index, || {
+                            context.call_fn::<Dynamic>(name, (arg,))
+                        })
+                    },
+                );
+            }
+        }
         // Our debugger hook *always* needs a way to get the current orientation, so
         // we use this special function even it if the get_orientation function is not
         // available for the user.
         let simulation = self.simulation.clone();
         engine.register_fn("__get_orientation__", move || -> Dynamic {
-            let orientation = simulation.borrow().curr_state().player.facing;
+            let orientation = simulation.borrow().curr_rover().facing;
             let orientation_str = match orientation {
                 Orientation::Up => "up",
                 Orientation::Down => "down",
@@ -709,7 +842,7 @@ impl ScriptRunner {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ScriptStats {
     // Length of the script in bytes.
     pub code_len: usize,
@@ -717,6 +850,8 @@ pub struct ScriptStats {
     pub energy_used: u32,
     // Amount of time (i.e. number of steps) taken to execute the script.
     pub time_taken: u32,
+    // Structural metrics computed from the AST.
+    pub metrics: CodeMetrics,
 }
 
 fn compute_stats(engine: &Engine, script: &str, states: &[State]) -> ScriptStats {
@@ -733,78 +868,10 @@ fn compute_stats(engine: &Engine, script: &str, states: &[State]) -> ScriptStats
         code_len,
         energy_used,
         time_taken,
+        metrics: CodeMetrics::from_script(script),
     }
 }
 
-// Note(albrow): This code is pretty brittle. We want to simplify the learning process by making
-// semicolons required in places where Rhai considers them optional (e.g. like in the last statement
-// of a block). It's difficult to do this right without building a full-blown tokenizer/parser. For
-// now, we just aim to cover common cases. Unfortunately there are still some edge cases which result
-// in false positives or false negatives.
-fn check_semicolons(source: &str) -> Result<(), Box<EvalAltResult>> {
-    let mut in_block_comment = false;
-    let lines: Vec<&str> = source.lines().collect();
-    'lines: for (i, line) in lines.iter().enumerate() {
-        let trimmed = line.trim();
-        if trimmed.is_empty()
-            || trimmed.starts_with("//")
-            || trimmed.ends_with('{')
-            || trimmed.ends_with('}')
-            || trimmed.ends_with("*/")
-        {
-            continue;
-        }
-        for (j, c) in trimmed.chars().enumerate() {
-            if !in_block_comment
-                && c == '/'
-                && j + 1 < trimmed.len()
-                && trimmed.chars().nth(j + 1) == Some('/')
-            {
-                continue 'lines;
-            } else if c == '/' && j + 1 < trimmed.len() && trimmed.chars().nth(j + 1) == Some('*') {
-                in_block_comment = true;
-            } else if c == '*' && j + 1 < trimmed.len() && trimmed.chars().nth(j + 1) == Some('/') {
-                in_block_comment = false;
-            }
-        }
-        // Check if this line ends in an opening paren. This could be splitting a function's arguments
-        // across multiple lines. Ideally, we would allow this, but we can't tell whether and where semicolons
-        // would be required. For now, return an error explaining that arguments need to be on the same line.
-        if trimmed.ends_with('(') {
-            return Err(Box::new(EvalAltResult::ErrorParsing(
-                rhai::ParseErrorType::BadInput(rhai::LexError::UnexpectedInput(String::from(
-                    BAD_INPUT_UNEXPECTED_LINE_BREAK_IN_FUNCTION_CALL,
-                ))),
-                rhai::Position::new(
-                    (i + 1).try_into().unwrap(),
-                    (line.len() - 1).try_into().unwrap(),
-                ),
-            )));
-        }
-
-        // Check if the next line is an opening bracket. This should be allowed.
-        if let Some(next_line) = lines.get(i + 1) {
-            let trimmed_next = next_line.trim();
-            if trimmed_next.starts_with('{') {
-                continue;
-            }
-        }
-        if !in_block_comment && !trimmed.ends_with(';') {
-            let line_num: u16 = (i + 1).try_into().unwrap();
-            let col: u16 = (line.len() - 1).try_into().unwrap();
-            return Err(Box::new(EvalAltResult::ErrorParsing(
-                rhai::ParseErrorType::MissingToken(
-                    String::from(';'),
-                    String::from("at end of line"),
-                ),
-                rhai::Position::new(line_num, col),
-            )));
-        }
-    }
-
-    Ok(())
-}
-
 fn set_engine_config(engine: &mut Engine) {
     // Causes unknown identifiers to be a compile-time error.
     // See: https://rhai.rs/book/language/variables.html?highlight=strict#strict-variables-mode
@@ -832,12 +899,35 @@ fn register_custom_types(engine: &mut Engine) {
         .register_type_with_name::<Pos>("Position")
         .register_get("x", Pos::get_x)
         .register_get("y", Pos::get_y);
+    engine.register_type_with_name::<RoverHandle>("Rover");
+}
+
+/// A handle to one of the rovers controlled by the player. Returned by the
+/// rover function.
+#[derive(Debug, Clone)]
+struct RoverHandle {
+    index: usize,
+}
+
+/// Temporarily makes the given rover the active rover, calls f, and then
+/// restores the previously active rover.
+fn with_rover<T>(
+    simulation: &Rc<RefCell<Simulation<'static>>>,
+    index: usize,
+    f: impl FnOnce() -> Result<T, Box<EvalAltResult>>,
+) -> Result<T, Box<EvalAltResult>> {
+    let prev_index = simulation.borrow().active_rover();
+    if !simulation.borrow_mut().set_active_rover(index) {
+        return Err(runtime_err(RuntimeErrorKind::NoRover));
+    }
+    let result = f();
+    simulation.borrow_mut().set_active_rover(prev_index);
+    result
 }
 
 fn eval_call_args_as_int(context: &EvalContext, fn_call_expr: &FnCallExpr) -> Result<i64, Error> {
     if fn_call_expr.args.len() != 1 {
-        return Err(Error::new(
-            ErrorKind::Other,
+        return Err(Error::other(
             "Expected exactly one argument to function call",
         ));
     }
@@ -847,10 +937,10 @@ fn eval_call_args_as_int(context: &EvalContext, fn_call_expr: &FnCallExpr) -> Re
         Some(dyn_val) => match dyn_val.as_int() {
             Ok(int_val) => int_val,
             Err(actual_type) => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    format!("Expected argument to be an integer but got {}", actual_type),
-                ))
+                return Err(Error::other(format!(
+                    "Expected argument to be an integer but got {}",
+                    actual_type
+                )))
             }
         },
         _ => {
@@ -872,10 +962,7 @@ fn eval_call_args_as_int(context: &EvalContext, fn_call_expr: &FnCallExpr) -> Re
             match arg_val {
                 Ok(val) => val,
                 Err(err) => {
-                    return Err(Error::new(
-                        ErrorKind::Other,
-                        format!("Error evaluating argument: {}", err),
-                    ))
+                    return Err(Error::other(format!("Error evaluating argument: {}", err)))
                 }
             }
         }
@@ -902,148 +989,21 @@ fn eval_curr_orientation(context: &EvalContext) -> Result<Orientation, Error> {
             "down" => Ok(Orientation::Down),
             "left" => Ok(Orientation::Left),
             "right" => Ok(Orientation::Right),
-            _ => Err(Error::new(
-                ErrorKind::Other,
-                format!("Unknown orientation: {}", dir),
-            )),
+            _ => Err(Error::other(format!("Unknown orientation: {}", dir))),
         },
-        Err(err) => Err(Error::new(
-            ErrorKind::Other,
-            format!("Error evaluating orientation: {}", err),
-        )),
+        Err(err) => Err(Error::other(format!(
+            "Error evaluating orientation: {}",
+            err
+        ))),
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::diagnostics::Severity;
     use crate::levels::SANDBOX_LEVEL_WITH_DATA_POINT;
 
-    #[test]
-    fn test_check_semicolons() {
-        let source = r#"
-            // This is a comment
-            fn foo() {
-                // This is a comment
-                let a = 1;
-                let b = 2; // This is an inline comment.
-                let c = 3; /* This is a block comment */
-                let /* this is an intrusive comment */ d = 4;
-                /* 
-                    This is a multiline comment.
-                    It has more than one line.
-                 */
-            }
-        "#;
-        check_semicolons(source).unwrap();
-
-        let source = r#"
-            // This is a comment
-            fn foo() {
-                // This is a comment
-                let a = 1
-                let b = 2; // This is an inline comment.
-                let c = 3; /* This is a block comment */
-                let /* this is an intrusive comment */ d = 4;
-                /* 
-                    This is a multiline comment.
-                    It has more than one line.
-                */
-            }
-        "#;
-        assert!(check_semicolons(source).is_err());
-
-        // Should not be a missing semicolon error if a bracket is on
-        // the next line. This is a regression test for
-        // https://github.com/albrow/elara/issues/67
-        let source = r"
-            loop
-            {
-                move_forward(1);
-            }
-            if true
-            {
-                move_forward(1);
-            }
-            else
-            {
-                move_forward(1);
-            }
-            while true
-            {
-                move_forward(1);
-            }
-        ";
-        check_semicolons(source).unwrap();
-
-        // TODO(albrow): Uncomment this test once we handle the edge case.
-        // let source = r#"
-        //     // This is a comment
-        //     fn foo() {
-        //         // This is a comment
-        //         let a = 1;
-        //         let b = 2 // This is an inline comment.
-        //         let c = 3; /* This is a block comment */
-        //         let /* this is an intrusive comment */ d = 4;
-        //         /*
-        //             This is a multiline comment.
-        //             It has more than one line.
-        //         */
-        //     }
-        // "#;
-        // assert!(check_semicolons(source).is_err());
-
-        // TODO(albrow): Uncomment this test once we handle the edge case.
-        // let source = r#"
-        //     // This is a comment
-        //     fn foo() {
-        //         // This is a comment
-        //         let a = 1;
-        //         let b = 2; // This is an inline comment.
-        //         let c = 3 /* This is a block comment */
-        //         let /* this is an intrusive comment */ d = 4;
-        //         /*
-        //             This is a multiline comment.
-        //             It has more than one line.
-        //         */
-        //     }
-        // "#;
-        // assert!(check_semicolons(source).is_err());
-
-        let source = r#"
-        // This is a comment
-        fn foo() {
-            // This is a comment
-            let a = 1;
-            let b = 2; // This is an inline comment.
-            let c = 3; /* This is a block comment */
-            let /* this is an intrusive comment */ d = 4
-            /* 
-                This is a multiline comment.
-                It has more than one line.
-            */
-        }
-    "#;
-        assert!(check_semicolons(source).is_err());
-    }
-
-    #[test]
-    fn test_unexpected_line_break_in_function_call() {
-        let script = r#"
-            fn foo() {
-                move_forward(
-                    1
-                );
-            }"#;
-        let result = check_semicolons(script);
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert_eq!(
-            err.to_string(),
-            "Syntax error: Unexpected 'line break in function call' (line 3, position 28)"
-        );
-    }
-
     /// Asserts that result is not a failure and then checks the each
     /// line number in results.trace. Note that we only check the line number,
     /// not the column number.
@@ -1185,4 +1145,90 @@ mod test {
             vec![vec![], vec![9, 7, 3], vec![9, 7, 3], vec![9, 7, 4]],
         );
     }
+
+    #[test]
+    fn test_warnings_do_not_block_running() {
+        let mut game = crate::Game::new();
+
+        let script = r#"
+            let unused = 1;
+            move_forward(0);
+            turn_left();
+        "#;
+        let result = game
+            .run_player_script_internal(
+                SANDBOX_LEVEL_WITH_DATA_POINT,
+                &vec!["move_forward".to_string(), "turn_left".to_string()],
+                script.to_string(),
+            )
+            .unwrap();
+        assert_eq!(
+            result
+                .warnings
+                .iter()
+                .map(|warning| (warning.span.line, warning.severity))
+                .collect::<Vec<_>>(),
+            vec![(2, Severity::Warning), (3, Severity::Warning)]
+        );
+        assert_trace_eq(&result, vec![vec![], vec![4]]);
+    }
+
+    #[test]
+    fn test_runtime_errors() {
+        let mut game = crate::Game::new();
+        let funcs = vec![
+            "move_forward".to_string(),
+            "turn_right".to_string(),
+            "read_data".to_string(),
+        ];
+
+        // Errors inside a function should point to the line inside the
+        // function.
+        let script = r"
+            fn go_and_read() {
+                turn_right();
+                move_forward(1);
+                read_data();
+            }
+            go_and_read();
+        ";
+        let result = game
+            .run_player_script_internal(SANDBOX_LEVEL_WITH_DATA_POINT, &funcs, script.to_string())
+            .unwrap();
+        let err = result.runtime_error().unwrap().clone();
+        assert_eq!(err.kind, RuntimeErrorKind::NoDataPoint);
+        assert_eq!(err.line, Some(5));
+        assert_eq!(
+            result.outcome,
+            Outcome::Failure(FailureReason::RuntimeError(err))
+        );
+
+        let script = r"
+            let steps = [1, 2];
+            move_forward(steps[2]);
+        ";
+        let result = game
+            .run_player_script_internal(SANDBOX_LEVEL_WITH_DATA_POINT, &funcs, script.to_string())
+            .unwrap();
+        let err = result.runtime_error().unwrap().clone();
+        assert_eq!(err.kind, RuntimeErrorKind::IndexOutOfBounds);
+        assert_eq!(err.message, "Error: There is no item at index 2 because the array only has 2 items. (Hint: the first item is at index 0 and the last item is at index 1.)");
+        assert_eq!((err.line, err.col), (Some(3), Some(32)));
+
+        let script = r#"
+            let steps = 1;
+            steps += "two";
+            if steps { move_forward(1); }
+        "#;
+        let result = game
+            .run_player_script_internal(SANDBOX_LEVEL_WITH_DATA_POINT, &funcs, script.to_string())
+            .unwrap();
+        let err = result.runtime_error().unwrap().clone();
+        assert_eq!(err.kind, RuntimeErrorKind::TypeMismatch);
+        assert_eq!(
+            err.message,
+            "Error: Expected a boolean (true or false) but got a string instead."
+        );
+        assert_eq!(err.line, Some(4));
+    }
 }
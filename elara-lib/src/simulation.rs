@@ -3,9 +3,10 @@ use std::any::Any;
 use std::fmt;
 
 use crate::{
-    actors::PlayerChannelActor,
-    constants::MAX_ENERGY,
-    levels::{Level, Outcome, LEVELS},
+    actors::{Action, PlayerChannelActor},
+    constants::{ELECTRIFIED_FLOOR_DRAIN, MAX_ENERGY},
+    levels::{objective_check_win, FailureReason, LevelRef, Outcome, LEVELS},
+    objectives::{Objective, ObjectiveProgress},
 };
 
 pub trait Actor {
@@ -14,23 +15,30 @@ pub trait Actor {
     fn as_any(&self) -> &dyn Any;
 }
 
-pub struct Simulation {
+pub struct Simulation<'a> {
     state_idx: usize,
     states: Vec<State>,
     player_actor: PlayerChannelActor,
-    level: &'static dyn Level,
+    /// The actors for the current level. These are created once when the level
+    /// is loaded so that they can keep track of their own state between steps.
+    actors: Vec<Box<dyn Actor>>,
+    level: LevelRef<'a>,
+    /// The objective for the current level, if it has one.
+    objective: Option<Objective>,
     last_outcome: Outcome,
 }
 
-impl Simulation {
-    pub fn new(player_actor: PlayerChannelActor) -> Simulation {
+impl<'a> Simulation<'a> {
+    pub fn new(player_actor: PlayerChannelActor) -> Simulation<'a> {
         let sim = Simulation {
             state_idx: 0,
             states: vec![],
             player_actor,
+            actors: vec![],
             // Start with the first level by default. Will be overwritten by
             // load_level.
-            level: LEVELS.values().next().unwrap().as_ref(),
+            level: LevelRef::Borrowed(LEVELS.values().next().unwrap().as_ref()),
+            objective: None,
             last_outcome: Outcome::Continue,
         };
         sim
@@ -38,28 +46,113 @@ impl Simulation {
 
     /// Loads the given level and creates the initial state using the given
     /// seed. If the level has multiple possible initial states, "seed"
-    /// determines which initial state to use.
-    pub fn load_level(&mut self, level: &'static dyn Level, seed: usize) {
-        self.level = level;
+    /// determines which initial state to use. Also resets the level's
+    /// actors.
+    pub fn load_level(&mut self, level: impl Into<LevelRef<'a>>, seed: usize) {
+        let level = level.into();
         self.state_idx = 0;
-        self.player_actor.set_bounds(level.bounds());
+        self.player_actor
+            .reset(level.bounds(), level.energy_costs());
+        self.actors = level.actors();
+        self.objective = level.objectives();
         self.states.clear();
-        self.states.push(self.level.initial_states()[seed].clone());
+        self.states.push(level.initial_states()[seed].clone());
+        self.level = level;
         self.last_outcome = Outcome::Continue;
     }
 
-    pub fn curr_level(&self) -> &'static dyn Level {
-        self.level
+    pub fn curr_level(&self) -> LevelRef<'a> {
+        self.level.clone()
     }
 
     pub fn curr_state(&self) -> State {
         self.states[self.state_idx].clone()
     }
 
+    /// Returns the index of the rover which is currently being controlled by
+    /// the player's script.
+    pub fn active_rover(&self) -> usize {
+        self.player_actor.active_rover()
+    }
+
+    /// Returns a copy of the rover which is currently being controlled by the
+    /// player's script.
+    pub fn curr_rover(&self) -> Player {
+        self.states[self.state_idx]
+            .rover(self.active_rover())
+            .unwrap()
+            .clone()
+    }
+
+    /// Changes which rover is controlled by subsequent actions. Returns false
+    /// (and leaves the active rover unchanged) if there is no rover with the
+    /// given index.
+    pub fn set_active_rover(&mut self, index: usize) -> bool {
+        if index >= self.states[self.state_idx].num_rovers() {
+            return false;
+        }
+        self.player_actor.set_active_rover(index);
+        true
+    }
+
+    /// Sets the actions to replay for the given rover, one per step. See
+    /// PlayerChannelActor::set_replay.
+    pub fn set_replay(&mut self, rover_index: usize, actions: Vec<Action>) {
+        self.player_actor.set_replay(rover_index, actions);
+    }
+
+    /// Returns the actions which were sent by the player's script for each
+    /// step so far.
+    pub fn received_actions(&self) -> Vec<Action> {
+        self.player_actor.received_actions().to_vec()
+    }
+
     pub fn get_history(&self) -> Vec<State> {
         self.states.to_vec()
     }
 
+    /// Returns the progress towards the current level's objective (if it has
+    /// one) based on the history so far.
+    pub fn objective_progress(&self) -> Option<ObjectiveProgress> {
+        self.objective
+            .as_ref()
+            .map(|objective| objective.progress(&self.states))
+    }
+
+    /// Checks for win or lose conditions. Objectives depend on the whole
+    /// history, so next_state is temporarily added to it and then given back.
+    fn check_win(&mut self, next_state: State) -> (State, Outcome) {
+        let (next_state, outcome) = match &self.objective {
+            None => {
+                let outcome = self.level.check_win(&next_state);
+                (next_state, outcome)
+            }
+            Some(objective) => {
+                self.states.push(next_state);
+                let outcome = objective_check_win(&*self.level, objective, &self.states);
+                (self.states.pop().unwrap(), outcome)
+            }
+        };
+        // If the level has custom energy costs, the rovers might not be able to
+        // afford any more actions even though they have some energy left. That
+        // is the same as running out of energy.
+        match outcome {
+            Outcome::Continue | Outcome::NoObjective
+                if self.can_not_afford_any_action(&next_state) =>
+            {
+                (next_state, Outcome::Failure(FailureReason::OutOfEnergy))
+            }
+            outcome => (next_state, outcome),
+        }
+    }
+
+    /// Returns true if every rover has less energy than the cheapest action
+    /// costs for the current level.
+    fn can_not_afford_any_action(&self, state: &State) -> bool {
+        let min_cost = self.level.energy_costs().min_cost();
+        state.rovers().all(|rover| rover.energy < min_cost)
+    }
+
     // TODO(albrow): Can we avoid cloning the outcome here and in other places?
     pub fn last_outcome(&self) -> Outcome {
         self.last_outcome.clone()
@@ -77,7 +170,7 @@ impl Simulation {
         // 1. Apply the player actor first, separately from the other actors.
         next_state = self.player_actor.apply(next_state);
         // 2. Check for win or lose conditions.
-        let outcome = self.level.check_win(&next_state);
+        let (mut next_state, outcome) = self.check_win(next_state);
         match outcome {
             Outcome::Success => {
                 self.states.push(next_state);
@@ -97,11 +190,11 @@ impl Simulation {
             }
         }
         // 3. Apply the other actors.
-        for actor in &mut self.level.actors() {
+        for actor in self.actors.iter_mut() {
             next_state = actor.apply(next_state);
         }
         // 4. Check for win or lose conditions again.
-        let outcome = self.level.check_win(&next_state);
+        let (next_state, outcome) = self.check_win(next_state);
         match outcome {
             Outcome::Success => {
                 self.states.push(next_state);
@@ -135,6 +228,10 @@ impl Simulation {
 #[derive(Clone, PartialEq)]
 pub struct State {
     pub player: Player,
+    /// Additional friendly rovers which are also controlled by the player's
+    /// script. The main rover (state.player) is always rover 0, and
+    /// extra_rovers[i] is rover i + 1.
+    pub extra_rovers: Vec<Player>,
     pub goals: Vec<Goal>,
     pub obstacles: Vec<Obstacle>,
     pub energy_cells: Vec<EnergyCell>,
@@ -148,12 +245,16 @@ pub struct State {
     pub crates: Vec<Crate>,
     pub asteroid_warnings: Vec<AsteroidWarning>,
     pub asteroids: Vec<Asteroid>,
+    pub turrets: Vec<Turret>,
+    pub charging_pads: Vec<ChargingPad>,
+    pub hazards: Vec<Hazard>,
 }
 
 impl State {
     pub fn new() -> State {
         State {
             player: Player::new(0, 0, MAX_ENERGY, Orientation::Right),
+            extra_rovers: vec![],
             goals: vec![],
             obstacles: vec![],
             energy_cells: vec![],
@@ -167,8 +268,32 @@ impl State {
             crates: vec![],
             asteroid_warnings: vec![],
             asteroids: vec![],
+            turrets: vec![],
+            charging_pads: vec![],
+            hazards: vec![],
+        }
+    }
+
+    /// Returns the total number of rovers controlled by the player
+    /// (including the main rover).
+    pub fn num_rovers(&self) -> usize {
+        1 + self.extra_rovers.len()
+    }
+
+    /// Returns the rover with the given index, where 0 is the main rover.
+    pub fn rover(&self, index: usize) -> Option<&Player> {
+        if index == 0 {
+            Some(&self.player)
+        } else {
+            self.extra_rovers.get(index - 1)
         }
     }
+
+    /// Returns an iterator over all rovers controlled by the player, starting
+    /// with the main rover.
+    pub fn rovers(&self) -> impl Iterator<Item = &Player> {
+        std::iter::once(&self.player).chain(self.extra_rovers.iter())
+    }
 }
 
 impl Default for State {
@@ -195,11 +320,12 @@ impl fmt::Debug for State {
             .field("crates", &self.crates)
             .field("asteroid_warnings", &self.asteroid_warnings)
             .field("asteroids", &self.asteroids)
+            .field("turrets", &self.turrets)
             .finish()
     }
 }
 
-#[derive(Clone, PartialEq, Debug, Copy)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Copy)]
 pub enum Orientation {
     Up,
     Down,
@@ -382,6 +508,40 @@ impl EnergyCell {
     }
 }
 
+/// Charging pads slowly restore energy to a rover which waits on top of them
+/// (up to MAX_ENERGY). Unlike energy cells, they can be used any number of
+/// times.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ChargingPad {
+    pub pos: Pos,
+    /// The amount of energy restored on each step.
+    pub rate: u32,
+    /// The number of steps the pad needs to rest after restoring energy before
+    /// it can restore energy again. 0 means the pad restores energy every step.
+    pub cooldown: u32,
+    /// The number of steps left until the pad can restore energy again.
+    pub cooldown_remaining: u32,
+    /// Whether the pad restored energy during the current step. Used for
+    /// animations.
+    pub charging: bool,
+}
+
+impl ChargingPad {
+    pub fn new(x: u32, y: u32, rate: u32) -> ChargingPad {
+        ChargingPad::new_with_cooldown(x, y, rate, 0)
+    }
+
+    pub fn new_with_cooldown(x: u32, y: u32, rate: u32, cooldown: u32) -> ChargingPad {
+        ChargingPad {
+            pos: Pos::new(x as i32, y as i32),
+            rate,
+            cooldown,
+            cooldown_remaining: 0,
+            charging: false,
+        }
+    }
+}
+
 // Indicates what the button is connected to.
 #[derive(Clone, PartialEq, Debug)]
 pub enum ButtonConnection {
@@ -466,11 +626,40 @@ pub enum BigEnemyAnimState {
     Bumping(BumpAnimData),
 }
 
+/// Determines how much of the board an enemy can see.
+#[derive(Clone, PartialEq, Debug)]
+pub enum EnemyVision {
+    /// The enemy always knows where the rover is and will chase it from the
+    /// very first step. This is the default.
+    Omniscient,
+    /// The enemy can only see in a straight line directly in front of it, up
+    /// to the given number of spaces.
+    Line(u32),
+    /// The enemy can see in a cone in front of it which gets one space wider on
+    /// each side for every space away from the enemy, up to the given number of
+    /// spaces.
+    Cone(u32),
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct Enemy {
     pub pos: Pos,
     pub facing: Orientation,
     pub anim_state: EnemyAnimState,
+    pub vision: EnemyVision,
+    /// Positions that the enemy will visit in order (and then loop back to the
+    /// first one) until it spots the rover. If empty, the enemy stays where it
+    /// is.
+    pub patrol_route: Vec<Pos>,
+    /// The index in patrol_route of the position that the enemy is currently
+    /// moving toward.
+    pub patrol_index: usize,
+    /// Whether or not the enemy has spotted the rover. Once the rover has been
+    /// spotted, the enemy will chase it for the rest of the level.
+    pub spotted_player: bool,
+    /// Whether or not the enemy has been destroyed (e.g. by an asteroid).
+    /// Destroyed enemies can no longer move or harm the rover.
+    pub destroyed: bool,
 }
 
 impl Enemy {
@@ -482,29 +671,113 @@ impl Enemy {
             },
             facing,
             anim_state: EnemyAnimState::Idle,
+            vision: EnemyVision::Omniscient,
+            patrol_route: vec![],
+            patrol_index: 0,
+            spotted_player: false,
+            destroyed: false,
+        }
+    }
+
+    /// Creates an enemy which only chases the rover after spotting it. Until
+    /// then, it follows the given patrol route (or stays still if the route is
+    /// empty).
+    pub fn new_with_vision(
+        x: u32,
+        y: u32,
+        facing: Orientation,
+        vision: EnemyVision,
+        patrol_route: Vec<(u32, u32)>,
+    ) -> Enemy {
+        Enemy {
+            vision,
+            patrol_route: patrol_route
+                .into_iter()
+                .map(|(x, y)| Pos::new(x as i32, y as i32))
+                .collect(),
+            ..Enemy::new(x, y, facing)
         }
     }
 }
 
+/// The default width and height of a BigEnemy.
+pub const DEFAULT_BIG_ENEMY_SIZE: u32 = 3;
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct BigEnemy {
     /// The position of the top left corner of the enemy.
     pub pos: Pos,
     pub facing: OrientationWithDiagonals,
+    /// The number of spaces the enemy takes up horizontally when facing up or
+    /// down. Width and height are swapped when facing left or right.
+    pub width: u32,
+    /// The number of spaces the enemy takes up vertically when facing up or
+    /// down.
+    pub height: u32,
     pub anim_state: BigEnemyAnimState,
+    /// Whether or not the enemy has been destroyed (e.g. by an asteroid).
+    /// Destroyed enemies can no longer move or harm the rover.
+    pub destroyed: bool,
 }
 
 impl BigEnemy {
     pub fn new(x: u32, y: u32, facing: OrientationWithDiagonals) -> BigEnemy {
+        BigEnemy::new_with_size(x, y, facing, DEFAULT_BIG_ENEMY_SIZE, DEFAULT_BIG_ENEMY_SIZE)
+    }
+
+    pub fn new_with_size(
+        x: u32,
+        y: u32,
+        facing: OrientationWithDiagonals,
+        width: u32,
+        height: u32,
+    ) -> BigEnemy {
+        assert!(
+            width > 0 && height > 0,
+            "BigEnemy width and height must be greater than 0"
+        );
         BigEnemy {
             pos: Pos {
                 x: x as i32,
                 y: y as i32,
             },
             facing,
+            width,
+            height,
             anim_state: BigEnemyAnimState::Idle,
+            destroyed: false,
+        }
+    }
+
+    /// Returns the (width, height) of the area taken up by the enemy if it were
+    /// facing the given direction. While facing diagonally (i.e. in the middle
+    /// of turning), the enemy takes up a square large enough to fit either
+    /// orientation.
+    pub fn footprint_for(&self, facing: OrientationWithDiagonals) -> (i32, i32) {
+        let (width, height) = (self.width as i32, self.height as i32);
+        match facing {
+            OrientationWithDiagonals::Up | OrientationWithDiagonals::Down => (width, height),
+            OrientationWithDiagonals::Left | OrientationWithDiagonals::Right => (height, width),
+            _ => {
+                let size = width.max(height);
+                (size, size)
+            }
         }
     }
+
+    /// Returns the (width, height) of the area currently taken up by the enemy.
+    pub fn footprint(&self) -> (i32, i32) {
+        self.footprint_for(self.facing)
+    }
+
+    /// Returns true if any part of the enemy's body is at the given position.
+    pub fn occupies(&self, pos: &Pos) -> bool {
+        let (width, height) = self.footprint();
+        pos.x >= self.pos.x
+            && pos.x < self.pos.x + width
+            && pos.y >= self.pos.y
+            && pos.y < self.pos.y + height
+    }
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -597,6 +870,53 @@ impl AsteroidWarning {
     }
 }
 
+#[derive(Clone, PartialEq, Debug)]
+/// A stationary turret which periodically fires a beam in the direction it is
+/// facing. The beam travels until it hits an obstacle or the edge of the map,
+/// and disables the rover if it hits it.
+pub struct Turret {
+    pub pos: Pos,
+    pub facing: Orientation,
+    /// The number of steps between each shot.
+    pub cadence: u32,
+    /// The number of steps until the turret fires next.
+    pub steps_until_fire: u32,
+    /// How many steps before firing the turret starts visibly warming up.
+    pub warm_up_steps: u32,
+    /// The positions covered by the beam. Only non-empty on the step in which
+    /// the turret fired.
+    pub beam: Vec<Pos>,
+    /// Whether or not the rover was hit by the most recent shot.
+    pub hit_player: bool,
+}
+
+impl Turret {
+    pub fn new(
+        x: u32,
+        y: u32,
+        facing: Orientation,
+        cadence: u32,
+        steps_until_fire: u32,
+        warm_up_steps: u32,
+    ) -> Turret {
+        assert!(cadence > 0, "Turret cadence must be greater than 0");
+        Turret {
+            pos: Pos::new(x as i32, y as i32),
+            facing,
+            cadence,
+            steps_until_fire,
+            warm_up_steps,
+            beam: vec![],
+            hit_player: false,
+        }
+    }
+
+    /// Returns true if the turret is about to fire.
+    pub fn is_warming_up(&self) -> bool {
+        self.beam.is_empty() && self.steps_until_fire <= self.warm_up_steps
+    }
+}
+
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Clone, PartialEq, Debug)]
 pub enum GateVariant {
@@ -841,13 +1161,23 @@ pub fn get_crate_in_front(state: &State) -> Option<usize> {
         Orientation::Right => pos.x += 1,
     }
     for (i, crt) in state.crates.iter().enumerate() {
-        if crt.pos == pos {
+        // Crates which are being used as a bridge can't be picked up again.
+        if crt.pos == pos && !crt.crushed && !is_bridge(state, i) {
             return Some(i);
         }
     }
     None
 }
 
+/// Returns true if the crate with the given index has been dropped into a
+/// hazard and is now acting as a bridge.
+pub fn is_bridge(state: &State, crate_index: usize) -> bool {
+    state
+        .hazards
+        .iter()
+        .any(|hazard| hazard.bridged_by == Some(crate_index))
+}
+
 /// Teleportation pads instantly move a rover from one location to another.
 /// As a side-effect, telepads may also change which direction the rover is
 /// facing.
@@ -876,11 +1206,79 @@ pub enum CrateColor {
     Green,
 }
 
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum HazardKind {
+    Crater,
+    Acid,
+    ElectrifiedFloor,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum HazardEffect {
+    /// Driving onto the hazard causes the level to fail.
+    Fail,
+    /// Driving onto the hazard drains the given amount of energy.
+    Drain(u32),
+}
+
+/// Dangerous terrain. Rovers can drive onto hazards (with consequences), but
+/// enemies are smart enough to avoid them.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Hazard {
+    pub pos: Pos,
+    pub kind: HazardKind,
+    pub effect: HazardEffect,
+    /// Whether dropping a crate onto the hazard turns it into a safe bridge.
+    pub bridgeable: bool,
+    /// The index of the crate which is acting as a bridge over the hazard, if
+    /// any. Bridged hazards are safe to drive over.
+    pub bridged_by: Option<usize>,
+}
+
+impl Hazard {
+    /// Creates a hazard with the default effect for the given kind. Craters
+    /// are deadly but can be bridged with a crate, acid is deadly, and
+    /// electrified floors drain energy.
+    pub fn new(x: u32, y: u32, kind: HazardKind) -> Hazard {
+        let (effect, bridgeable) = match kind {
+            HazardKind::Crater => (HazardEffect::Fail, true),
+            HazardKind::Acid => (HazardEffect::Fail, false),
+            HazardKind::ElectrifiedFloor => (HazardEffect::Drain(ELECTRIFIED_FLOOR_DRAIN), false),
+        };
+        Hazard::new_with_effect(x, y, kind, effect, bridgeable)
+    }
+
+    pub fn new_with_effect(
+        x: u32,
+        y: u32,
+        kind: HazardKind,
+        effect: HazardEffect,
+        bridgeable: bool,
+    ) -> Hazard {
+        Hazard {
+            pos: Pos::new(x as i32, y as i32),
+            kind,
+            effect,
+            bridgeable,
+            bridged_by: None,
+        }
+    }
+
+    /// Returns true if the hazard is still dangerous, i.e. it has not been
+    /// bridged.
+    pub fn is_active(&self) -> bool {
+        self.bridged_by.is_none()
+    }
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct Crate {
     pub pos: Pos,
     pub held: bool,
     pub color: CrateColor,
+    /// Whether or not the crate has been crushed (e.g. by an asteroid).
+    /// Crushed crates can't be picked up and no longer block anything.
+    pub crushed: bool,
 }
 
 impl Crate {
@@ -892,6 +1290,141 @@ impl Crate {
             },
             held: false,
             color,
+            crushed: false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actors::{Action, Bounds, EnergyCosts, MoveDirection};
+    use crate::levels::{std_check_win, Level};
+    use std::{cell::RefCell, rc::Rc, sync::mpsc};
+
+    /// An actor which keeps track of how many times it has been applied and
+    /// writes the count to the player's message.
+    struct CountingActor {
+        count: u32,
+    }
+
+    impl Actor for CountingActor {
+        fn apply(&mut self, state: State) -> State {
+            self.count += 1;
+            let mut state = state;
+            state.player.message = self.count.to_string();
+            state
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    struct CountingLevel {}
+
+    impl Level for CountingLevel {
+        fn name(&self) -> &'static str {
+            "Counting"
+        }
+        fn short_name(&self) -> &'static str {
+            "counting"
+        }
+        fn objective(&self) -> &'static str {
+            "Count the number of steps."
+        }
+        fn initial_code(&self) -> &'static str {
+            ""
+        }
+        fn initial_states(&self) -> Vec<State> {
+            vec![State::new()]
+        }
+        fn actors(&self) -> Vec<Box<dyn Actor>> {
+            vec![Box::new(CountingActor { count: 0 })]
+        }
+        fn check_win(&self, _state: &State) -> Outcome {
+            Outcome::Continue
+        }
+    }
+
+    #[test]
+    fn actors_persist_between_steps() {
+        const LEVEL: &'static dyn Level = &CountingLevel {};
+        let (tx, rx) = mpsc::channel();
+        let player_actor = PlayerChannelActor::new(Rc::new(RefCell::new(rx)), Bounds::default());
+        let mut sim = Simulation::new(player_actor);
+        sim.load_level(LEVEL, 0);
+
+        // The same actor should be used for every step.
+        for _ in 0..3 {
+            tx.send(Action::Wait).unwrap();
+            sim.step_forward();
+        }
+        assert_eq!(sim.curr_state().player.message, "3");
+
+        // Reloading the level should reset the actors.
+        sim.load_level(LEVEL, 0);
+        tx.send(Action::Wait).unwrap();
+        sim.step_forward();
+        assert_eq!(sim.curr_state().player.message, "1");
+    }
+
+    /// A level where every action costs two energy.
+    struct ExpensiveLevel {}
+
+    impl Level for ExpensiveLevel {
+        fn name(&self) -> &'static str {
+            "Expensive"
+        }
+        fn short_name(&self) -> &'static str {
+            "expensive"
+        }
+        fn objective(&self) -> &'static str {
+            "Move the rover ({robot}) to the goal ({goal})."
+        }
+        fn initial_code(&self) -> &'static str {
+            ""
+        }
+        fn initial_states(&self) -> Vec<State> {
+            let mut state = State::new();
+            state.player = Player::new(0, 0, 3, Orientation::Right);
+            state.goals = vec![Goal::new(5, 0)];
+            vec![state]
+        }
+        fn actors(&self) -> Vec<Box<dyn Actor>> {
+            vec![]
+        }
+        fn check_win(&self, state: &State) -> Outcome {
+            std_check_win(state)
+        }
+        fn energy_costs(&self) -> EnergyCosts {
+            EnergyCosts {
+                wait: 2,
+                movement: 2,
+                movement_while_holding: 2,
+                turn: 2,
+                say: 2,
+                read_data: 2,
+                press_button: 2,
+                pick_up: 2,
+                drop: 2,
+            }
+        }
+    }
+
+    #[test]
+    fn can_not_afford_any_action() {
+        const LEVEL: &'static dyn Level = &ExpensiveLevel {};
+        let (tx, rx) = mpsc::channel();
+        let player_actor = PlayerChannelActor::new(Rc::new(RefCell::new(rx)), Bounds::default());
+        let mut sim = Simulation::new(player_actor);
+        sim.load_level(LEVEL, 0);
+
+        // The rover still has one energy left after moving, but that isn't
+        // enough to do anything else.
+        tx.send(Action::Move(MoveDirection::Forward)).unwrap();
+        let outcome = sim.step_forward();
+        assert_eq!(sim.curr_state().player.energy, 1);
+        assert_eq!(outcome, Outcome::Failure(FailureReason::OutOfEnergy));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::simulation::{
-    Asteroid, AsteroidWarning, BigEnemy, Button, Crate, DataPoint, Enemy, EnergyCell, Gate, Goal,
-    Obstacle, PasswordGate, Player, State, Telepad,
+    Asteroid, AsteroidWarning, BigEnemy, Button, ChargingPad, Crate, DataPoint, Enemy, EnergyCell,
+    Gate, Goal, Hazard, Obstacle, PasswordGate, Player, State, Telepad, Turret,
 };
 
 /// A convenience struct for building a State via chainable
@@ -26,6 +26,12 @@ impl StateMaker {
         self
     }
 
+    #[allow(dead_code)]
+    pub fn with_extra_rovers(&mut self, extra_rovers: Vec<Player>) -> &mut Self {
+        self.state.extra_rovers = extra_rovers;
+        self
+    }
+
     pub fn with_obstacles(&mut self, obstacles: Vec<Obstacle>) -> &mut Self {
         self.state.obstacles = obstacles;
         self
@@ -36,6 +42,18 @@ impl StateMaker {
         self
     }
 
+    #[allow(dead_code)]
+    pub fn with_hazards(&mut self, hazards: Vec<Hazard>) -> &mut Self {
+        self.state.hazards = hazards;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_charging_pads(&mut self, charging_pads: Vec<ChargingPad>) -> &mut Self {
+        self.state.charging_pads = charging_pads;
+        self
+    }
+
     pub fn with_energy_cells(&mut self, energy_cells: Vec<EnergyCell>) -> &mut Self {
         self.state.energy_cells = energy_cells;
         self
@@ -94,4 +112,10 @@ impl StateMaker {
         self.state.asteroids = asteroids;
         self
     }
+
+    #[allow(dead_code)]
+    pub fn with_turrets(&mut self, turrets: Vec<Turret>) -> &mut Self {
+        self.state.turrets = turrets;
+        self
+    }
 }
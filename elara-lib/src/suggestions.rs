@@ -0,0 +1,266 @@
+use rhai::{Position, Token};
+
+use crate::diagnostics::{raw_engine, tokenize};
+
+/// Returns the number of single-character edits (insertions, deletions,
+/// substitutions, or swapping two adjacent characters) needed to turn a into
+/// b. Differences in case are ignored.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+
+    // dist[i][j] is the distance between the first i chars of a and the first
+    // j chars of b.
+    let mut dist = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in dist.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in dist[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            dist[i][j] = (dist[i - 1][j] + 1)
+                .min(dist[i][j - 1] + 1)
+                .min(dist[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                dist[i][j] = dist[i][j].min(dist[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    dist[a.len()][b.len()]
+}
+
+/// Returns the maximum edit distance for a candidate to be considered a
+/// likely typo of name. Shorter names allow fewer edits so that we don't
+/// suggest something completely different.
+fn max_distance(name: &str) -> usize {
+    match name.chars().count() {
+        0..=4 => 1,
+        5..=8 => 2,
+        _ => 3,
+    }
+}
+
+/// Returns the candidate which is most likely what the player meant to type
+/// instead of name, or None if none of the candidates are close enough.
+pub fn closest_match<'a, I>(name: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut best: Option<(usize, &'a str)> = None;
+    for candidate in candidates {
+        if candidate == name {
+            continue;
+        }
+        let distance = edit_distance(name, candidate);
+        // If every character would need to change, the candidate isn't
+        // really similar (e.g. x and y).
+        if distance > max_distance(name) || distance >= name.chars().count() {
+            continue;
+        }
+        // Ties are broken alphabetically so the suggestion doesn't depend
+        // on the order of the candidates.
+        if best.is_none_or(|(best_distance, best_candidate)| {
+            (distance, candidate) < (best_distance, best_candidate)
+        }) {
+            best = Some((distance, candidate));
+        }
+    }
+    best.map(|(_, candidate)| candidate)
+}
+
+fn tokens(script: &str) -> Vec<(Token, Position)> {
+    tokenize(&raw_engine(), script)
+        .into_iter()
+        .map(|(token, start, _)| (token, start))
+        .collect()
+}
+
+/// Returns the names of all the functions defined in the script. The script
+/// doesn't need to compile.
+pub fn defined_functions(script: &str) -> Vec<String> {
+    tokens(script)
+        .windows(2)
+        .filter_map(|pair| match (&pair[0].0, &pair[1].0) {
+            (Token::Fn, Token::Identifier(name)) => Some(name.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the names of the variables declared in the script (including
+/// function parameters and loop variables) before the given position. Only
+/// variables which are in scope at the given position are included, i.e.
+/// those in the same function body (or at the top level if the position is
+/// outside of any function) which were not declared in a block that has
+/// already ended. The script doesn't need to compile.
+pub fn declared_variables(script: &str, before: Position) -> Vec<String> {
+    let tokens: Vec<(Token, Position)> = tokens(script)
+        .into_iter()
+        .take_while(|(_, pos)| {
+            before.is_none() || (pos.line(), pos.position()) < (before.line(), before.position())
+        })
+        .collect();
+
+    let token_at = |i: usize, back: usize| i.checked_sub(back).map(|j| &tokens[j].0);
+    // Each declared name along with its scope and the depth of the braces
+    // around it.
+    let mut names: Vec<(String, usize, usize)> = vec![];
+    // Functions can't access variables from outside of the function, so the
+    // top level (scope 0) and each function (scopes 1 and up) are separate
+    // scopes.
+    let mut scope = 0;
+    let mut num_funcs = 0;
+    // The depth of the braces around the current function body, or None if
+    // the body hasn't started yet.
+    let mut body_depth: Option<usize> = None;
+    let mut depth = 0;
+    // True if we are inside the parameter list of a function definition.
+    let mut in_params = false;
+    for (i, (token, _)) in tokens.iter().enumerate() {
+        match token {
+            Token::Fn => {
+                num_funcs += 1;
+                scope = num_funcs;
+                body_depth = None;
+            }
+            Token::LeftBrace | Token::MapStart => {
+                depth += 1;
+                if scope != 0 && body_depth.is_none() {
+                    body_depth = Some(depth);
+                }
+            }
+            Token::RightBrace => {
+                // Variables are only in scope until the end of the block they
+                // were declared in.
+                names.retain(|(_, _, name_depth)| *name_depth < depth);
+                if body_depth == Some(depth) {
+                    scope = 0;
+                    body_depth = None;
+                }
+                depth = depth.saturating_sub(1);
+            }
+            Token::LeftParen if token_at(i, 2) == Some(&Token::Fn) => in_params = true,
+            Token::RightParen => in_params = false,
+            Token::Identifier(name) => {
+                let is_loop_variable = token_at(i, 1) == Some(&Token::For)
+                    // The variables in `for (item, index) in ...`.
+                    || (token_at(i, 1) == Some(&Token::LeftParen)
+                        && token_at(i, 2) == Some(&Token::For))
+                    || (token_at(i, 1) == Some(&Token::Comma)
+                        && token_at(i, 3) == Some(&Token::LeftParen)
+                        && token_at(i, 4) == Some(&Token::For));
+                if in_params || is_loop_variable {
+                    // Parameters and loop variables belong to the block which
+                    // comes after them.
+                    names.push((name.to_string(), scope, depth + 1));
+                } else if matches!(token_at(i, 1), Some(Token::Let) | Some(Token::Const)) {
+                    names.push((name.to_string(), scope, depth));
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+        .into_iter()
+        .filter(|(_, name_scope, _)| *name_scope == scope)
+        .map(|(name, _, _)| name)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("move_forward", "move_forward"), 0);
+        assert_eq!(edit_distance("move_foward", "move_forward"), 1);
+        assert_eq!(edit_distance("trun_left", "turn_left"), 1);
+        assert_eq!(edit_distance("Turn_Left", "turn_left"), 0);
+        assert_eq!(edit_distance("left", "right"), 4);
+        assert_eq!(edit_distance("", "say"), 3);
+    }
+
+    #[test]
+    fn test_closest_match() {
+        let candidates = ["move_forward", "move_backward", "turn_left", "turn_right"];
+        assert_eq!(
+            closest_match("move_foward", candidates),
+            Some("move_forward")
+        );
+        assert_eq!(closest_match("trun_left", candidates), Some("turn_left"));
+        assert_eq!(closest_match("Turn_Right", candidates), Some("turn_right"));
+        assert_eq!(closest_match("jump", candidates), None);
+        assert_eq!(closest_match("x", ["y", "z"]), None);
+        // Ties are broken alphabetically.
+        assert_eq!(closest_match("cat", ["hat", "bat"]), Some("bat"));
+    }
+
+    #[test]
+    fn test_declared_names() {
+        let script = r"
+            fn walk(steps, times) {
+                move_forward(steps);
+            }
+            let count = 1;
+            const limit = 2;
+            for i in 0..3 {}
+            for (item, index) in [1, 2] {}
+            let later = 3;";
+        assert_eq!(defined_functions(script), vec!["walk"]);
+        assert_eq!(
+            declared_variables(script, Position::new(9, 1)),
+            vec!["count", "limit"]
+        );
+        assert_eq!(
+            declared_variables(script, Position::NONE),
+            vec!["count", "limit", "later"]
+        );
+        // Loop variables are only in scope inside the loop.
+        assert_eq!(
+            declared_variables(script, Position::new(7, 28)),
+            vec!["count", "limit", "i"]
+        );
+        // Inside a function, only the parameters and the variables declared in
+        // the function body are in scope.
+        assert_eq!(
+            declared_variables(script, Position::new(3, 17)),
+            vec!["steps", "times"]
+        );
+    }
+
+    #[test]
+    fn test_declared_names_scopes() {
+        let script = r"
+            let outer = #{ a: 1 };
+            fn first(x) {
+                let inner = 1;
+                if x > 0 { let nested = 2; }
+                return inn;
+            }
+            fn second(y) {
+                let other = outer;
+            }
+            let after = 2;";
+        assert_eq!(
+            declared_variables(script, Position::new(6, 24)),
+            vec!["x", "inner"]
+        );
+        // Variables declared in a block are only in scope until it ends.
+        assert_eq!(
+            declared_variables(script, Position::new(5, 44)),
+            vec!["x", "inner", "nested"]
+        );
+        assert_eq!(
+            declared_variables(script, Position::new(9, 29)),
+            vec!["y", "other"]
+        );
+        assert_eq!(
+            declared_variables(script, Position::NONE),
+            vec!["outer", "after"]
+        );
+    }
+}
@@ -0,0 +1,411 @@
+use crate::diagnostics::Diagnostic;
+use crate::levels::{is_on_goal, rover_failure};
+use crate::simulation::State;
+
+/// The number of rovers (and therefore scripts) which take part in a versus
+/// match. The first script controls rover 0 and the second controls rover 1.
+pub const NUM_VERSUS_ROVERS: usize = 2;
+
+/// Each script in a versus match only sees the actions of the other script
+/// which have been recorded so far, so the scripts are run repeatedly until
+/// neither of them changes its actions. Each round is guaranteed to fix at
+/// least one more step, but we put an upper bound on it so that scripts which
+/// run for a very long time can't hang the game. See VersusResult::converged.
+pub const MAX_VERSUS_ROUNDS: usize = 20;
+
+/// The maximum number of steps to simulate across all rounds of a versus
+/// match. Long scripts can stop the match before MAX_VERSUS_ROUNDS is reached.
+pub const MAX_VERSUS_STEPS: usize = 2_000;
+
+/// Functions which are never available in versus matches. Each script may only
+/// control its own rover, since the actions of the other rover are replayed.
+pub const VERSUS_DISABLED_FUNCS: &[&str] = &["select_rover", "rover"];
+
+/// Returned if one of the scripts in a versus match could not be run.
+#[derive(Debug)]
+pub struct VersusError {
+    /// The index of the script (and rover) which caused the error.
+    pub rover: usize,
+    /// Every error which was found in the script.
+    pub errors: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersusRoverStats {
+    /// The step at which the rover first reached a goal (if it did).
+    pub reached_goal_at: Option<usize>,
+    /// The number of energy cells collected by the rover.
+    pub energy_cells_collected: usize,
+    /// The number of steps before the rover was disabled (e.g. destroyed by an
+    /// enemy or ran out of energy). If the rover was never disabled, this is
+    /// the total number of steps in the match.
+    pub steps_survived: usize,
+}
+
+/// What the result of a versus match was based on, in order of priority.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VersusDecider {
+    /// One rover reached a goal before the other.
+    Goal,
+    /// One rover collected more energy cells than the other.
+    EnergyCells,
+    /// One rover survived for longer than the other.
+    Survival,
+    /// Neither rover was better in any of the above.
+    Draw,
+}
+
+#[derive(Debug, Clone)]
+pub struct VersusResult {
+    /// The state corresponding to each step in the match.
+    pub states: Vec<State>,
+    /// Stats for each rover, indexed by rover.
+    pub rovers: Vec<VersusRoverStats>,
+    /// The index of the winning rover, or None in the case of a draw.
+    pub winner: Option<usize>,
+    pub decided_by: VersusDecider,
+    /// False if the scripts were still reacting to each other differently
+    /// after MAX_VERSUS_ROUNDS (or MAX_VERSUS_STEPS). In that case, the match might not be exactly
+    /// what would have happened if both scripts were running at the same time.
+    pub converged: bool,
+}
+
+/// Computes the stats for each rover and determines the winner of a versus
+/// match based on the states for each step. converged should be false if the
+/// actions which produced the states did not converge (see MAX_VERSUS_ROUNDS
+/// and MAX_VERSUS_STEPS).
+pub fn score_versus(states: Vec<State>, converged: bool) -> VersusResult {
+    let last_step = states.len().saturating_sub(1);
+    let mut rovers = vec![
+        VersusRoverStats {
+            reached_goal_at: None,
+            energy_cells_collected: 0,
+            steps_survived: last_step,
+        };
+        NUM_VERSUS_ROVERS
+    ];
+    let mut disabled = [false; NUM_VERSUS_ROVERS];
+
+    for (step, state) in states.iter().enumerate() {
+        for (index, stats) in rovers.iter_mut().enumerate() {
+            let rover = state.rover(index).unwrap();
+            if stats.reached_goal_at.is_none() && is_on_goal(state, rover) {
+                stats.reached_goal_at = Some(step);
+            }
+            if !disabled[index] && rover_failure(state, rover).is_some() {
+                // The rover survived every step up until this one.
+                disabled[index] = true;
+                stats.steps_survived = step.saturating_sub(1);
+            }
+        }
+
+        // Energy cells are collected by whichever rover is on top of them
+        // at the end of the step.
+        if step == 0 {
+            continue;
+        }
+        let prev_state = &states[step - 1];
+        for (cell, prev_cell) in state
+            .energy_cells
+            .iter()
+            .zip(prev_state.energy_cells.iter())
+        {
+            if cell.collected && !prev_cell.collected {
+                if let Some(index) = (0..NUM_VERSUS_ROVERS)
+                    .find(|&i| state.rover(i).is_some_and(|r| r.pos == cell.pos))
+                {
+                    rovers[index].energy_cells_collected += 1;
+                }
+            }
+        }
+    }
+
+    let (winner, decided_by) = decide_winner(&rovers[0], &rovers[1]);
+    VersusResult {
+        states,
+        rovers,
+        winner,
+        decided_by,
+        converged,
+    }
+}
+
+fn decide_winner(a: &VersusRoverStats, b: &VersusRoverStats) -> (Option<usize>, VersusDecider) {
+    match (a.reached_goal_at, b.reached_goal_at) {
+        (Some(a_step), Some(b_step)) if a_step < b_step => return (Some(0), VersusDecider::Goal),
+        (Some(a_step), Some(b_step)) if b_step < a_step => return (Some(1), VersusDecider::Goal),
+        (Some(_), None) => return (Some(0), VersusDecider::Goal),
+        (None, Some(_)) => return (Some(1), VersusDecider::Goal),
+        _ => {}
+    }
+    if a.energy_cells_collected != b.energy_cells_collected {
+        let winner = if a.energy_cells_collected > b.energy_cells_collected {
+            0
+        } else {
+            1
+        };
+        return (Some(winner), VersusDecider::EnergyCells);
+    }
+    if a.steps_survived != b.steps_survived {
+        let winner = if a.steps_survived > b.steps_survived {
+            0
+        } else {
+            1
+        };
+        return (Some(winner), VersusDecider::Survival);
+    }
+    (None, VersusDecider::Draw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actors::EnergyCosts;
+    use crate::levels::{std_check_win, Level, Outcome, VERSUS_LEVELS};
+    use crate::simulation::{Actor, EnergyCell, Goal, Orientation, Player};
+    use crate::state_maker::StateMaker;
+
+    /// A small board where the two rovers can easily get in each other's way.
+    struct TestArena {}
+
+    impl Level for TestArena {
+        fn name(&self) -> &'static str {
+            "Test Arena"
+        }
+        fn short_name(&self) -> &'static str {
+            "test_arena"
+        }
+        fn objective(&self) -> &'static str {
+            "Reach the goal first."
+        }
+        fn initial_code(&self) -> &'static str {
+            ""
+        }
+        fn initial_states(&self) -> Vec<State> {
+            vec![StateMaker::new()
+                .with_player(Player::new(0, 0, 10, Orientation::Right))
+                .with_extra_rovers(vec![Player::new(2, 1, 10, Orientation::Up)])
+                .with_goals(vec![Goal::new(4, 0)])
+                .with_energy_cells(vec![EnergyCell::new(1, 0)])
+                .build()]
+        }
+        fn actors(&self) -> Vec<Box<dyn Actor>> {
+            vec![]
+        }
+        fn check_win(&self, state: &State) -> Outcome {
+            std_check_win(state)
+        }
+    }
+
+    /// A board where both rovers are next to the same space, but only one of
+    /// them can be on it at a time. Moving is free so that the rovers can keep
+    /// going back and forth.
+    struct Doorway {}
+
+    impl Level for Doorway {
+        fn name(&self) -> &'static str {
+            "Doorway"
+        }
+        fn short_name(&self) -> &'static str {
+            "doorway"
+        }
+        fn objective(&self) -> &'static str {
+            "Reach the goal first."
+        }
+        fn initial_code(&self) -> &'static str {
+            ""
+        }
+        fn initial_states(&self) -> Vec<State> {
+            vec![StateMaker::new()
+                .with_player(Player::new(0, 0, 10, Orientation::Right))
+                .with_extra_rovers(vec![Player::new(1, 1, 10, Orientation::Up)])
+                .with_goals(vec![Goal::new(6, 6)])
+                .build()]
+        }
+        fn actors(&self) -> Vec<Box<dyn Actor>> {
+            vec![]
+        }
+        fn check_win(&self, state: &State) -> Outcome {
+            std_check_win(state)
+        }
+        fn energy_costs(&self) -> EnergyCosts {
+            EnergyCosts {
+                movement: 0,
+                ..Default::default()
+            }
+        }
+    }
+
+    fn funcs() -> Vec<String> {
+        [
+            "move_forward",
+            "turn_left",
+            "turn_right",
+            "say",
+            "get_position",
+            "move_backward",
+        ]
+        .iter()
+        .map(|f| f.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn race() {
+        let mut game = crate::Game::new();
+        let level: &'static dyn Level = VERSUS_LEVELS.get("versus_race").unwrap().as_ref();
+
+        // Both rovers take the same number of steps to reach a goal.
+        let script_a = "move_forward(5); turn_right(); move_forward(4);";
+        let result = game
+            .run_versus_internal(level, &funcs(), [script_a, script_a], 0)
+            .unwrap();
+        assert_eq!(result.rovers[0].reached_goal_at, Some(10));
+        assert_eq!(result.rovers[1].reached_goal_at, Some(10));
+        assert_eq!(result.winner, None);
+        assert_eq!(result.decided_by, VersusDecider::Draw);
+
+        // If the second rover wastes a step, the first rover wins.
+        let script_b = "say(\"hi\"); move_forward(5); turn_right(); move_forward(4);";
+        let result = game
+            .run_versus_internal(level, &funcs(), [script_a, script_b], 0)
+            .unwrap();
+        assert_eq!(result.rovers[0].reached_goal_at, Some(10));
+        assert_eq!(result.rovers[1].reached_goal_at, None);
+        assert_eq!(result.winner, Some(0));
+        assert_eq!(result.decided_by, VersusDecider::Goal);
+        assert_eq!(result.states.len(), 11);
+        assert!(result.converged);
+    }
+
+    #[test]
+    fn rovers_react_to_each_other() {
+        let mut game = crate::Game::new();
+        const LEVEL: &'static dyn Level = &TestArena {};
+
+        // The second rover moves into the path of the first rover, which should
+        // then notice that it is stuck.
+        let script_a = r#"
+            move_forward(2);
+            let pos = get_position();
+            if pos[0] < 2 {
+                say("blocked");
+            }
+        "#;
+        let script_b = "move_forward(1);";
+        let result = game
+            .run_versus_internal(LEVEL, &funcs(), [script_a, script_b], 0)
+            .unwrap();
+        assert_eq!(result.states.len(), 4);
+        let last_state = result.states.last().unwrap();
+        assert_eq!(last_state.player.pos.x, 1);
+        assert_eq!(last_state.player.message, "blocked");
+        assert_eq!(last_state.extra_rovers[0].pos.x, 2);
+        assert_eq!(last_state.extra_rovers[0].pos.y, 0);
+
+        // Neither rover reached the goal, but the first rover collected an
+        // energy cell on the way.
+        assert_eq!(result.rovers[0].energy_cells_collected, 1);
+        assert_eq!(result.rovers[1].energy_cells_collected, 0);
+        assert_eq!(result.winner, Some(0));
+        assert_eq!(result.decided_by, VersusDecider::EnergyCells);
+        assert!(result.converged);
+    }
+
+    #[test]
+    fn does_not_converge() {
+        let mut game = crate::Game::new();
+        const LEVEL: &'static dyn Level = &Doorway {};
+
+        // The second rover keeps moving onto the space in front of the first
+        // rover and back again. The first rover only tries to move onto it if
+        // it wasn't blocked the time before last, so each round only fixes a
+        // few more steps and longer scripts run out of rounds.
+        let script_a = r#"
+            let attempt = false;
+            let blocked = false;
+            for i in 0..80 {
+                if get_position()[0] == 1 {
+                    move_backward(1);
+                    blocked = false;
+                    continue;
+                }
+                let attempt_now = attempt;
+                attempt = !blocked;
+                if attempt_now {
+                    move_forward(1);
+                    blocked = get_position()[0] == 0;
+                } else {
+                    say("");
+                    blocked = false;
+                }
+            }
+        "#;
+        let script_b = r#"
+            for i in 0..80 {
+                if get_position()[1] == 0 {
+                    move_backward(1);
+                } else {
+                    move_forward(1);
+                }
+            }
+        "#;
+        let result = game
+            .run_versus_internal(LEVEL, &funcs(), [script_a, script_b], 0)
+            .unwrap();
+        assert!(!result.converged);
+        assert_eq!(result.states.len(), 81);
+
+        // Shorter scripts which do the same thing should converge.
+        let script_a = script_a.replace("80", "20");
+        let script_b = script_b.replace("80", "20");
+        let result = game
+            .run_versus_internal(LEVEL, &funcs(), [&script_a, &script_b], 0)
+            .unwrap();
+        assert!(result.converged);
+        assert_eq!(result.states.len(), 21);
+    }
+
+    #[test]
+    fn can_not_control_other_rover() {
+        let mut game = crate::Game::new();
+        const LEVEL: &'static dyn Level = &TestArena {};
+        let mut funcs = funcs();
+        funcs.push("select_rover".to_string());
+        funcs.push("rover".to_string());
+
+        // Even if they are unlocked, select_rover and rover are disabled since
+        // the actions of the other rover are replayed.
+        let err = game
+            .run_versus_internal(
+                LEVEL,
+                &funcs,
+                ["select_rover(1); move_forward(1);", "say(\"hi\");"],
+                0,
+            )
+            .unwrap_err();
+        assert_eq!(err.rover, 0);
+        assert_eq!(err.errors[0].code, "err_fn_disabled");
+        let err = game
+            .run_versus_internal(
+                LEVEL,
+                &funcs,
+                ["say(\"hi\");", "rover(0).move_forward(1);"],
+                0,
+            )
+            .unwrap_err();
+        assert_eq!(err.rover, 1);
+        assert_eq!(err.errors[0].code, "err_fn_disabled");
+    }
+
+    #[test]
+    fn script_error() {
+        let mut game = crate::Game::new();
+        const LEVEL: &'static dyn Level = &TestArena {};
+
+        let err = game
+            .run_versus_internal(LEVEL, &funcs(), ["move_forward(1);", "move_forward(1"], 0)
+            .unwrap_err();
+        assert_eq!(err.rover, 1);
+    }
+}